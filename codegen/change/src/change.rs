@@ -0,0 +1,84 @@
+// @generated
+// This file is @generated by prost-build.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AssetCreated {
+    /// Checksum address of the app contract
+    #[prost(string, tag="1")]
+    pub asset_address: ::prost::alloc::string::String,
+    /// Publisher account that deployed the app
+    #[prost(string, tag="2")]
+    pub publisher_address: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub package_name: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BuildValidated {
+    #[prost(string, tag="1")]
+    pub asset_address: ::prost::alloc::string::String,
+    /// Build request the validation block finalized
+    #[prost(int64, tag="2")]
+    pub request_id: i64,
+    #[prost(int64, tag="3")]
+    pub version_code: i64,
+    #[prost(int32, tag="4")]
+    pub track_id: i32,
+    /// Validation block of the result
+    #[prost(int64, tag="5")]
+    pub block_id: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AssetVisibilityChanged {
+    #[prost(string, tag="1")]
+    pub asset_address: ::prost::alloc::string::String,
+    #[prost(int64, tag="2")]
+    pub asset_id: i64,
+    /// 0 visible, 1 hidden, 2 blocked
+    #[prost(int32, tag="3")]
+    pub visibility: i32,
+    #[prost(string, tag="4")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OwnerTransferred {
+    #[prost(string, tag="1")]
+    pub asset_address: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub prev_owner: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub new_owner: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(int64, tag="5")]
+    pub log_index: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangeEvent {
+    /// Stable id of the change, the same change always gets the same id
+    #[prost(string, tag="1")]
+    pub id: ::prost::alloc::string::String,
+    /// Block of the change, 0 for changes made by moderators
+    #[prost(int64, tag="2")]
+    pub block_number: i64,
+    /// Unix millis when the change was committed
+    #[prost(int64, tag="3")]
+    pub created_at: i64,
+    #[prost(oneof="change_event::Payload", tags="10, 11, 12, 13")]
+    pub payload: ::core::option::Option<change_event::Payload>,
+}
+/// Nested message and enum types in `ChangeEvent`.
+pub mod change_event {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Payload {
+        #[prost(message, tag="10")]
+        AssetCreated(super::AssetCreated),
+        #[prost(message, tag="11")]
+        BuildValidated(super::BuildValidated),
+        #[prost(message, tag="12")]
+        VisibilityChanged(super::AssetVisibilityChanged),
+        #[prost(message, tag="13")]
+        OwnerTransferred(super::OwnerTransferred),
+    }
+}
+// @@protoc_insertion_point(module)
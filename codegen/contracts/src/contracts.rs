@@ -7,6 +7,13 @@ sol!(
     "./contracts/PublisherAccount.json"
 );
 
+sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    PublisherAccountFactory,
+    "./contracts/PublisherAccountFactory.json"
+);
+
 sol!(
     #[allow(missing_docs)]
     #[sol(rpc)]
@@ -45,7 +45,7 @@ impl EthScanClient {
                 .append_pair("topic0", topic0);
         }
 
-        if let Some(ref address) = params.address {
+        if let [address] = params.addresses.as_slice() {
             url.query_pairs_mut()
                 .append_pair("address", address);
         }
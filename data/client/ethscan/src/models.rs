@@ -18,7 +18,8 @@ pub struct GetLogsParams {
     pub from_block: u64,
     pub to_block: Option<u64>,
     pub topic0: Option<String>,
-    pub address: Option<String>,
+    // EthScan takes a single address, none or several query every emitter of the topic
+    pub addresses: Vec<String>,
     pub page: Option<u32>,
     pub offset: Option<u32>,
 }
@@ -124,6 +124,35 @@ impl GreenfieldClient {
         return Ok(response_obj);
     }
     
+    // Path-style url on the primary SP of the object's bucket, public objects are served without auth
+    pub async fn get_object_view_url(&self, object_id: &str) -> GfResult<Url> {
+        let meta = self.get_object_meta_by_id(object_id)
+            .await?;
+
+        let object_name = &meta.object_info.object_name;
+        let bucket_name = &meta.object_info.bucket_name;
+
+        let endpoint = self.get_sp_endpoint_by_bucket(bucket_name)
+            .await?;
+
+        let endpoint = endpoint.trim_end_matches('/');
+        let url = Url::parse(format!("{endpoint}/view/{bucket_name}/{object_name}").as_str())?;
+
+        return Ok(url);
+    }
+
+    // Range is inclusive like in the http header, the SP answers 206 for it
+    pub async fn get_object_by_url(&self, url: Url, range: Option<(u64, u64)>) -> GfResult<Response> {
+        let header_map = self.request_headers_with_range(Method::GET, &url, range)
+            .await?;
+
+        let builder = self.client.request(Method::GET, url);
+        let response = self.execute(builder, Some(header_map))
+            .await?;
+
+        return Ok(response);
+    }
+
     pub async fn get_object_logo_info(&self, bucket_name: &String, package_name: &String) -> GfResult<Option<String>> {
         let formatted_package = package_name.replace('.', "_");
         let object_name = format!("open-store-external/{}/logo.png", formatted_package);
@@ -227,9 +256,21 @@ impl GreenfieldClient {
 
     async fn request_headers(
         &self, method: Method, url: &Url
+    ) -> GfResult<HeaderMap> {
+        return self.request_headers_with_range(method, url, None)
+            .await;
+    }
+
+    // Range goes before signing since it's a part of the canonical headers
+    async fn request_headers_with_range(
+        &self, method: Method, url: &Url, range: Option<(u64, u64)>
     ) -> GfResult<HeaderMap> {
         let mut headers = self.default_request_headers(None);
 
+        if let Some((start, end)) = range {
+            headers.insert(HTTP_HEADER_RANGE, format!("bytes={start}-{end}"));
+        }
+
         if let Some(ref pk) = self.pk {
             let auth_token = self.auth_token(method, &url, &headers, pk)
                 .await?;
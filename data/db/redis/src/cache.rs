@@ -1,4 +1,6 @@
+use futures_util::StreamExt;
 use redis::{AsyncCommands, RedisError};
+use std::future::Future;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use thiserror::Error;
@@ -99,6 +101,40 @@ impl RedisCache {
         }
     }
 
+    // Increments the counter and refreshes its TTL atomically, returns the new value
+    pub async fn incr(&self, key: &str, ttl_seconds: u64) -> KeyValueResult<u64> {
+        let mut conn = self.client.get_connection()
+            .await?;
+
+        let (value,): (u64,) = redis::pipe()
+            .atomic()
+            .incr(key, 1)
+            .expire(key, ttl_seconds as i64).ignore()
+            .query_async(&mut conn)
+            .await?;
+
+        trace!("Executed INCR for key: {}, value: {}", key, value);
+        Ok(value)
+    }
+
+    // Sets the key only if it doesn't exist yet, returns whether it was set
+    pub async fn set_nx(&self, key: &str, value: &str, ttl_seconds: u64) -> KeyValueResult<bool> {
+        let mut conn = self.client.get_connection()
+            .await?;
+
+        let result: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl_seconds)
+            .query_async(&mut conn)
+            .await?;
+
+        trace!("Executed SET NX for key: {}, set: {}", key, result.is_some());
+        Ok(result.is_some())
+    }
+
     pub async fn delete(&self, key: &str) -> KeyValueResult<bool> {
         debug!("Deleting key: {}", key);
 
@@ -110,4 +146,66 @@ impl RedisCache {
 
         Ok(deleted_count > 0)
     }
+
+    // Deletes every key matching the glob pattern, SCAN keeps redis responsive on big keyspaces
+    pub async fn delete_pattern(&self, pattern: &str) -> KeyValueResult<u64> {
+        let mut conn = self.client.get_connection()
+            .await?;
+
+        let keys: Vec<String> = {
+            let mut iter = conn.scan_match::<_, String>(pattern)
+                .await?;
+
+            let mut keys = vec![];
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+
+            keys
+        };
+
+        let mut deleted = 0;
+        for chunk in keys.chunks(500) {
+            let count: u64 = conn.del(chunk)
+                .await?;
+
+            deleted += count;
+        }
+
+        debug!("Deleted {} keys by pattern: {}", deleted, pattern);
+        Ok(deleted)
+    }
+
+    pub async fn publish(&self, channel: &str, message: &str) -> KeyValueResult<()> {
+        let mut conn = self.client.get_connection()
+            .await?;
+
+        conn.publish::<_, _, ()>(channel, message)
+            .await?;
+
+        trace!("Published to channel: {}", channel);
+        Ok(())
+    }
+
+    // Passes every message of the channel to the handler, returns when the connection is dropped
+    pub async fn listen<F, Fut>(&self, channel: &str, mut handler: F) -> KeyValueResult<()>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output=()> {
+        let mut pubsub = self.client.get_pubsub()
+            .await?;
+
+        pubsub.subscribe(channel)
+            .await?;
+
+        debug!("Subscribed to channel: {}", channel);
+
+        let mut messages = pubsub.into_on_message();
+        while let Some(message) = messages.next().await {
+            let payload: String = message.get_payload()?;
+            handler(payload).await;
+        }
+
+        Ok(())
+    }
 }
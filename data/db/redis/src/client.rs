@@ -16,4 +16,10 @@ impl RedisClient {
             .get_multiplexed_tokio_connection()
             .await
     }
-}
\ No newline at end of file
+
+    pub async fn get_pubsub(&self) -> RedisResult<redis::aio::PubSub> {
+        return self.client
+            .get_async_pubsub()
+            .await
+    }
+}
@@ -22,16 +22,24 @@ impl EventLogService {
         Self { client }
     }
 
+    // RPC filters by several addresses at once, EthScan only by one
+    pub fn filters_many_addresses(&self) -> bool {
+        return matches!(self.client, EventLogClient::Eth(_));
+    }
+
     pub async fn get_logs(&self, params: &GetLogsParams) -> Result<LogsResponse, EventError> {
         match self.client {
             EventLogClient::Eth(ref client) => {
                 let mut filter = Filter::new()
                     .from_block(params.from_block);
 
-                if let Some(ref addr) = params.address {
-                    if let Ok(address) = Address::parse_checksummed(addr.as_str(), None) {
-                        filter = filter.address(address);
-                    }
+                // Addresses are lowercase hex across the store, they don't pass EIP-55 validation
+                let addresses = params.addresses.iter()
+                    .filter_map(|addr| addr.parse::<Address>().ok())
+                    .collect::<Vec<Address>>();
+
+                if !addresses.is_empty() {
+                    filter = filter.address(addresses);
                 }
 
                 if let Some(ref topic0) = params.topic0 {
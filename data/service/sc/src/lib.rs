@@ -3,5 +3,6 @@ extern crate core;
 pub mod store;
 pub mod assetlinks;
 pub mod obj;
+pub mod publisher;
 pub mod default;
 pub mod env;
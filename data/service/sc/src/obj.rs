@@ -30,6 +30,8 @@ impl ScObjService {
     
     pub const APP_CREATED_HASH: B256 = DevAccountAppsPluginV1::AppCreated::SIGNATURE_HASH;
     pub const APP_OWNER_CHANGED_HASH: B256 = AppOwnerPluginV1::AppOwnerChanged::SIGNATURE_HASH;
+    // Ownable event, every ownable contract emits it and not only apps
+    pub const OWNERSHIP_TRANSFERRED_HASH: B256 = AppOwnerPluginV1::OwnershipTransferred::SIGNATURE_HASH;
 
     pub fn new(client: Arc<Web3Provider>) -> Self {
         Self { provider: client }
@@ -40,10 +42,23 @@ impl ScObjService {
         return result;
     }
 
+    pub fn decode_ownership_transferred(data: &Log) -> alloy::sol_types::Result<Log<AppOwnerPluginV1::OwnershipTransferred>> {
+        let result = AppOwnerPluginV1::OwnershipTransferred::decode_log(data);
+        return result;
+    }
+
+    // Publisher account the app belongs to
+    pub async fn get_owner(&self, obj: Address) -> EthResult<Address> {
+        let contract = App::new(obj, &self.provider);
+        let owner = contract.owner().call()
+            .await?;
+
+        return Ok(owner);
+    }
+
     // TODO optimize
     pub async fn get_owner_name(&self, obj: Address) -> EthResult<String> {
-        let contract = App::new(obj, &self.provider);
-        let dev_address = contract.owner().call()
+        let dev_address = self.get_owner(obj)
             .await?;
         
         let dev_account = DevAccount::new(dev_address, &self.provider);
@@ -0,0 +1,22 @@
+use alloy::primitives::{Log, B256};
+use alloy::sol_types::SolEvent;
+use codegen_contracts::contracts::{DevAccountAppsPluginV1, PublisherAccountFactory};
+
+pub struct ScPublisherService;
+
+impl ScPublisherService {
+
+    pub const ACCOUNT_CREATED_HASH: B256 = PublisherAccountFactory::PublisherAccountCreated::SIGNATURE_HASH;
+    // Emitted by the publisher account itself, the apps plugin runs in its context
+    pub const APP_CREATED_HASH: B256 = DevAccountAppsPluginV1::AppCreated::SIGNATURE_HASH;
+
+    pub fn decode_account_created(p0: &Log) -> alloy::sol_types::Result<Log<PublisherAccountFactory::PublisherAccountCreated>> {
+        let result = PublisherAccountFactory::PublisherAccountCreated::decode_log(p0);
+        return result;
+    }
+
+    pub fn decode_app_created(p0: &Log) -> alloy::sol_types::Result<Log<DevAccountAppsPluginV1::AppCreated>> {
+        let result = DevAccountAppsPluginV1::AppCreated::decode_log(p0);
+        return result;
+    }
+}
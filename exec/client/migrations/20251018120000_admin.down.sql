@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS chart_order;
+DROP TABLE IF EXISTS audit_log;
+DROP TABLE IF EXISTS admin_wallet;
+DROP TABLE IF EXISTS admin_key;
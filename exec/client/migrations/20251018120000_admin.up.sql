@@ -0,0 +1,39 @@
+CREATE TABLE admin_key (
+    id BIGSERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    key_hash VARCHAR(64) NOT NULL,
+    role INT NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE UNIQUE INDEX idx_admin_key_hash ON admin_key(key_hash);
+
+CREATE TABLE admin_wallet (
+    address VARCHAR(100) PRIMARY KEY,
+    role INT NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    actor VARCHAR(255) NOT NULL,
+    role INT NOT NULL,
+    action VARCHAR(50) NOT NULL,
+    target VARCHAR(255) NOT NULL,
+    payload TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX idx_audit_log_actor ON audit_log(actor);
+CREATE INDEX idx_audit_log_created_at ON audit_log(created_at);
+
+-- category_id = 0 is the platform-wide chart
+CREATE TABLE chart_order (
+    id BIGSERIAL PRIMARY KEY,
+    platform_id INT NOT NULL,
+    category_id INT NOT NULL DEFAULT 0,
+    asset_id BIGINT NOT NULL REFERENCES obj(id) ON DELETE CASCADE,
+    position INT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE UNIQUE INDEX idx_chart_order_asset ON chart_order(platform_id, category_id, asset_id);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_object_visibility;
+ALTER TABLE obj DROP COLUMN IF EXISTS visibility_reason;
+ALTER TABLE obj DROP COLUMN IF EXISTS visibility;
@@ -0,0 +1,5 @@
+-- 0 visible, 1 hidden, 2 blocked
+ALTER TABLE obj ADD COLUMN visibility INT NOT NULL DEFAULT 0;
+ALTER TABLE obj ADD COLUMN visibility_reason VARCHAR(255);
+UPDATE obj SET visibility = 1 WHERE is_hidden = true;
+CREATE INDEX idx_object_visibility ON obj(visibility);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_reviews_signature;
+ALTER TABLE review DROP COLUMN IF EXISTS updated_at;
+ALTER TABLE review DROP COLUMN IF EXISTS signed_at;
+ALTER TABLE review DROP COLUMN IF EXISTS signature;
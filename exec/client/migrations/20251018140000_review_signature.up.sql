@@ -0,0 +1,4 @@
+ALTER TABLE review ADD COLUMN signature VARCHAR(132);
+ALTER TABLE review ADD COLUMN signed_at BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE review ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP;
+CREATE UNIQUE INDEX idx_reviews_signature ON review(signature);
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_object_platform_rating;
+ALTER TABLE obj DROP COLUMN IF EXISTS rating_5;
+ALTER TABLE obj DROP COLUMN IF EXISTS rating_4;
+ALTER TABLE obj DROP COLUMN IF EXISTS rating_3;
+ALTER TABLE obj DROP COLUMN IF EXISTS rating_2;
+ALTER TABLE obj DROP COLUMN IF EXISTS rating_1;
+ALTER TABLE obj DROP COLUMN IF EXISTS rating_count;
@@ -0,0 +1,34 @@
+ALTER TABLE obj ADD COLUMN rating_count BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE obj ADD COLUMN rating_1 BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE obj ADD COLUMN rating_2 BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE obj ADD COLUMN rating_3 BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE obj ADD COLUMN rating_4 BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE obj ADD COLUMN rating_5 BIGINT NOT NULL DEFAULT 0;
+
+UPDATE obj SET
+    rating_1 = summary.rating_1,
+    rating_2 = summary.rating_2,
+    rating_3 = summary.rating_3,
+    rating_4 = summary.rating_4,
+    rating_5 = summary.rating_5,
+    rating_count = summary.rating_count
+FROM (
+    SELECT
+        asset_id,
+        COUNT(*) FILTER (WHERE rating = 1) AS rating_1,
+        COUNT(*) FILTER (WHERE rating = 2) AS rating_2,
+        COUNT(*) FILTER (WHERE rating = 3) AS rating_3,
+        COUNT(*) FILTER (WHERE rating = 4) AS rating_4,
+        COUNT(*) FILTER (WHERE rating = 5) AS rating_5,
+        COUNT(*) FILTER (WHERE rating BETWEEN 1 AND 5) AS rating_count
+    FROM review
+    GROUP BY asset_id
+) AS summary
+WHERE obj.id = summary.asset_id;
+
+UPDATE obj SET rating = CASE WHEN rating_count > 0
+    THEN (rating_1 + 2 * rating_2 + 3 * rating_3 + 4 * rating_4 + 5 * rating_5)::REAL / rating_count
+    ELSE 0
+END;
+
+CREATE INDEX idx_object_platform_rating ON obj(platform_id, rating);
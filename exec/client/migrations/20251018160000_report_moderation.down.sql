@@ -0,0 +1,10 @@
+DROP INDEX IF EXISTS idx_object_flagged;
+ALTER TABLE obj DROP COLUMN IF EXISTS is_flagged;
+
+DROP INDEX IF EXISTS idx_reports_status;
+DROP INDEX IF EXISTS idx_reports_ip;
+DROP INDEX IF EXISTS idx_reports_pending_dedup;
+ALTER TABLE report DROP COLUMN IF EXISTS updated_at;
+ALTER TABLE report DROP COLUMN IF EXISTS note;
+ALTER TABLE report DROP COLUMN IF EXISTS ip;
+ALTER TABLE report DROP COLUMN IF EXISTS status;
@@ -0,0 +1,11 @@
+-- 0 open, 1 triaged, 2 actioned, 3 dismissed
+ALTER TABLE report ADD COLUMN status INT NOT NULL DEFAULT 0;
+ALTER TABLE report ADD COLUMN ip VARCHAR(45);
+ALTER TABLE report ADD COLUMN note TEXT;
+ALTER TABLE report ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP;
+CREATE UNIQUE INDEX idx_reports_pending_dedup ON report(asset_address, email, category_id) WHERE status IN (0, 1);
+CREATE INDEX idx_reports_ip ON report(ip, created_at);
+CREATE INDEX idx_reports_status ON report(status);
+
+ALTER TABLE obj ADD COLUMN is_flagged BOOLEAN NOT NULL DEFAULT false;
+CREATE INDEX idx_object_flagged ON obj(is_flagged) WHERE is_flagged = true;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_object_platform_downloads_id;
+DROP INDEX IF EXISTS idx_reviews_asset_id_id;
@@ -0,0 +1,2 @@
+CREATE INDEX idx_reviews_asset_id_id ON review(asset_id, id DESC);
+CREATE INDEX idx_object_platform_downloads_id ON obj(platform_id, downloads DESC, id DESC);
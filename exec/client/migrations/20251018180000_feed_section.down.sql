@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_publishing_track_created_at;
+DROP INDEX IF EXISTS idx_publishing_track_updated_at;
+ALTER TABLE publishing DROP COLUMN IF EXISTS updated_at;
+DROP TABLE IF EXISTS feed_section;
@@ -0,0 +1,30 @@
+-- Home feed is composed from these sections, see `FeedBuilder`
+CREATE TABLE IF NOT EXISTS feed_section (
+    id BIGSERIAL PRIMARY KEY,
+    type_id INT NOT NULL,
+    position INT NOT NULL,
+    layout INT NOT NULL,
+    source INT NOT NULL DEFAULT 0,
+    title_key VARCHAR(64),
+    covers TEXT[] NOT NULL DEFAULT '{}',
+    asset_ids BIGINT[] NOT NULL DEFAULT '{}',
+    category_ids INT[] NOT NULL DEFAULT '{}',
+    size INT NOT NULL DEFAULT 10 CHECK (size BETWEEN 1 AND 50),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_feed_section_type_position ON feed_section(type_id, position);
+
+-- Set when a new version is published to the track
+ALTER TABLE publishing ADD COLUMN updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP;
+CREATE INDEX idx_publishing_track_updated_at ON publishing(track_id, updated_at DESC);
+CREATE INDEX idx_publishing_track_created_at ON publishing(track_id, created_at DESC);
+
+-- Default feed for apps and games: top chart, new releases, recently updated
+INSERT INTO feed_section (type_id, position, layout, source, title_key) VALUES
+    (1, 0, 3, 4, 'top_chart'),
+    (1, 1, 2, 2, 'new_releases'),
+    (1, 2, 2, 3, 'recently_updated'),
+    (2, 0, 3, 4, 'top_chart'),
+    (2, 1, 2, 2, 'new_releases'),
+    (2, 2, 2, 3, 'recently_updated');
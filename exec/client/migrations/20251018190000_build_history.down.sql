@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_build_request_address_id;
+
+ALTER TABLE build_request DROP COLUMN IF EXISTS finalized_at;
+ALTER TABLE build_request DROP COLUMN IF EXISTS block_id;
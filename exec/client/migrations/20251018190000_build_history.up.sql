@@ -0,0 +1,5 @@
+-- Set once the validation block with the request is finalized on chain
+ALTER TABLE build_request ADD COLUMN block_id BIGINT;
+ALTER TABLE build_request ADD COLUMN finalized_at TIMESTAMPTZ;
+
+CREATE INDEX idx_build_request_address_id ON build_request(asset_address, id DESC);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_object_package_name;
+
+ALTER TABLE validation_proof DROP COLUMN IF EXISTS fingerprints;
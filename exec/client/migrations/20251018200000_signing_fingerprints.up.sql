@@ -0,0 +1,4 @@
+-- SHA-256 signing certificate fingerprints of the owner version, lowercase hex without separators
+ALTER TABLE validation_proof ADD COLUMN fingerprints TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX idx_object_package_name ON obj(package_name);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS publisher_app;
+DROP TABLE IF EXISTS publisher;
@@ -0,0 +1,23 @@
+CREATE TABLE publisher (
+    id BIGSERIAL PRIMARY KEY,
+    address VARCHAR(100) NOT NULL,
+    owner VARCHAR(100) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    block_number BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE UNIQUE INDEX idx_publisher_address ON publisher(address);
+CREATE INDEX idx_publisher_owner ON publisher(owner);
+
+-- Apps created through the publisher account, asset rows appear only once the first build is requested
+CREATE TABLE publisher_app (
+    id BIGSERIAL PRIMARY KEY,
+    publisher_address VARCHAR(100) NOT NULL,
+    asset_address VARCHAR(100) NOT NULL,
+    package_name VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    block_number BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE UNIQUE INDEX idx_publisher_app_asset ON publisher_app(asset_address);
+CREATE INDEX idx_publisher_app_publisher ON publisher_app(publisher_address);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS stat_checkpoint;
+
+ALTER TABLE obj DROP COLUMN IF EXISTS installs;
@@ -0,0 +1,9 @@
+ALTER TABLE obj ADD COLUMN installs BIGINT NOT NULL DEFAULT 0;
+
+-- Upper bound of stat events already applied, moved in the same transaction as the counters
+CREATE TABLE stat_checkpoint (
+    name VARCHAR(50) PRIMARY KEY,
+    event_time TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+INSERT INTO stat_checkpoint (name, event_time) VALUES ('downloads_recount', 'epoch');
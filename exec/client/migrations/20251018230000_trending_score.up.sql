@@ -0,0 +1,9 @@
+-- Materialized by the daemon from download velocity, rows are replaced on every refresh
+CREATE TABLE trending_score (
+    asset_id BIGINT PRIMARY KEY REFERENCES obj(id) ON DELETE CASCADE,
+    score REAL NOT NULL,
+    downloads_24h BIGINT NOT NULL,
+    downloads_7d BIGINT NOT NULL,
+    baseline_daily REAL NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
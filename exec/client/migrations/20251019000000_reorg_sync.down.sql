@@ -0,0 +1,12 @@
+DROP TABLE IF EXISTS publishing_change;
+
+DROP INDEX IF EXISTS idx_publisher_app_block_number;
+DROP INDEX IF EXISTS idx_publisher_block_number;
+
+ALTER TABLE validation_proof DROP COLUMN IF EXISTS block_number;
+ALTER TABLE assetlink_sync DROP COLUMN IF EXISTS block_number;
+ALTER TABLE artifact DROP COLUMN IF EXISTS block_number;
+ALTER TABLE build_request DROP COLUMN IF EXISTS block_number;
+ALTER TABLE obj DROP COLUMN IF EXISTS block_number;
+
+DROP TABLE IF EXISTS sync_block;
@@ -0,0 +1,34 @@
+-- Hashes of synced blocks, compared with the chain to detect reorgs
+CREATE TABLE sync_block (
+    block_number BIGINT PRIMARY KEY,
+    block_hash VARCHAR(66) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Block of the event that created the row, NULL for rows synced before reorg tracking
+ALTER TABLE obj ADD COLUMN block_number BIGINT;
+ALTER TABLE build_request ADD COLUMN block_number BIGINT;
+ALTER TABLE artifact ADD COLUMN block_number BIGINT;
+ALTER TABLE assetlink_sync ADD COLUMN block_number BIGINT;
+ALTER TABLE validation_proof ADD COLUMN block_number BIGINT;
+
+CREATE INDEX idx_object_block_number ON obj(block_number) WHERE block_number IS NOT NULL;
+CREATE INDEX idx_build_request_block_number ON build_request(block_number) WHERE block_number IS NOT NULL;
+CREATE INDEX idx_artifact_block_number ON artifact(block_number) WHERE block_number IS NOT NULL;
+CREATE INDEX idx_assetlink_sync_block_number ON assetlink_sync(block_number) WHERE block_number IS NOT NULL;
+CREATE INDEX idx_proof_block_number ON validation_proof(block_number) WHERE block_number IS NOT NULL;
+CREATE INDEX idx_publisher_block_number ON publisher(block_number);
+CREATE INDEX idx_publisher_app_block_number ON publisher_app(block_number);
+
+-- Track state before each synced change, a rollback restores the earliest change past the fork.
+-- NULL previous version means the track row didn't exist
+CREATE TABLE publishing_change (
+    id BIGSERIAL PRIMARY KEY,
+    asset_address VARCHAR(100) NOT NULL,
+    track_id INT NOT NULL,
+    prev_version_code BIGINT,
+    prev_is_active BOOLEAN,
+    block_number BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX idx_publishing_change_block_number ON publishing_change(block_number);
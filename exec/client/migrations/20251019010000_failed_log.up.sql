@@ -0,0 +1,23 @@
+-- Logs that failed to sync, retried by the daemon with backoff.
+-- kind: 0 fatal (decode), 1 error (lookup); status: 0 pending, 1 resolved, 2 dead.
+-- log is the serialized rpc log the handler is replayed with
+CREATE TABLE failed_log (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    topic0 VARCHAR(66),
+    log TEXT NOT NULL,
+    kind INT NOT NULL,
+    error TEXT NOT NULL,
+    status INT NOT NULL DEFAULT 0,
+    attempts INT NOT NULL DEFAULT 1,
+    next_retry_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_failed_log_tx_log ON failed_log(tx_hash, log_index);
+CREATE INDEX idx_failed_log_due ON failed_log(next_retry_at) WHERE status = 0;
+CREATE INDEX idx_failed_log_status ON failed_log(status, id DESC);
+CREATE INDEX idx_failed_log_block_number ON failed_log(block_number);
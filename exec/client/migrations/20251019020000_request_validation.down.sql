@@ -0,0 +1,9 @@
+DELETE FROM validation_proof WHERE request_id IS NOT NULL;
+
+DROP INDEX IF EXISTS idx_proof_request;
+DROP INDEX IF EXISTS idx_proof_validation;
+CREATE UNIQUE INDEX idx_proof_validation ON validation_proof(asset_address, owner_version);
+
+ALTER TABLE validation_proof DROP COLUMN IF EXISTS file_hash;
+ALTER TABLE validation_proof DROP COLUMN IF EXISTS version_code;
+ALTER TABLE validation_proof DROP COLUMN IF EXISTS request_id;
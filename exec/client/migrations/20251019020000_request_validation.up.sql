@@ -0,0 +1,9 @@
+-- Validation results of finalized blocks, one row per request.
+-- Ownership proofs of assetlink syncs keep `request_id` NULL
+ALTER TABLE validation_proof ADD COLUMN request_id BIGINT;
+ALTER TABLE validation_proof ADD COLUMN version_code BIGINT;
+ALTER TABLE validation_proof ADD COLUMN file_hash VARCHAR(128);
+
+DROP INDEX idx_proof_validation;
+CREATE UNIQUE INDEX idx_proof_validation ON validation_proof(asset_address, owner_version) WHERE request_id IS NULL;
+CREATE UNIQUE INDEX idx_proof_request ON validation_proof(request_id) WHERE request_id IS NOT NULL;
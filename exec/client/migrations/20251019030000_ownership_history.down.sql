@@ -0,0 +1,3 @@
+ALTER TABLE obj DROP COLUMN IF EXISTS owner;
+
+DROP TABLE IF EXISTS ownership_history;
@@ -0,0 +1,25 @@
+-- Owner changes of apps, kind: 0 created (AppCreated), 1 transferred (OwnershipTransferred).
+-- prev_owner is NULL for created apps, changed_at is the block timestamp of the event
+CREATE TABLE ownership_history (
+    id BIGSERIAL PRIMARY KEY,
+    asset_address VARCHAR(100) NOT NULL,
+    prev_owner VARCHAR(100),
+    new_owner VARCHAR(100) NOT NULL,
+    kind INT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    changed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_ownership_history_tx_log ON ownership_history(tx_hash, log_index);
+CREATE INDEX idx_ownership_history_asset ON ownership_history(asset_address, block_number DESC, log_index DESC);
+CREATE INDEX idx_ownership_history_block_number ON ownership_history(block_number);
+
+-- Current owner, the newest entry of the history or the publisher account that created the app
+ALTER TABLE obj ADD COLUMN owner VARCHAR(100);
+
+UPDATE obj SET owner = publisher_app.publisher_address
+FROM publisher_app
+WHERE publisher_app.asset_address = obj.address;
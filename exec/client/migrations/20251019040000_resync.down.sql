@@ -0,0 +1 @@
+DROP INDEX IF EXISTS idx_assetlink_sync_result;
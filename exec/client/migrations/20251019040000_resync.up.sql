@@ -0,0 +1,10 @@
+-- Replayed sync results are kept once, so a block range can be synced again
+DELETE FROM assetlink_sync a
+USING assetlink_sync b
+WHERE a.id > b.id
+AND a.asset_address = b.asset_address
+AND a.owner_version = b.owner_version
+AND a.domain = b.domain
+AND a.status = b.status;
+
+CREATE UNIQUE INDEX idx_assetlink_sync_result ON assetlink_sync(asset_address, owner_version, domain, status);
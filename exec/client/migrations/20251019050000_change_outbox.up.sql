@@ -0,0 +1,19 @@
+-- Change events waiting for kafka, written in the transaction of the change itself.
+-- kind: 0 asset created, 1 build validated, 2 visibility changed, 3 owner transferred.
+-- payload is the encoded `change.ChangeEvent`, published_at stays NULL until kafka acks it
+CREATE TABLE change_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    event_id VARCHAR(255) NOT NULL,
+    kind INT NOT NULL,
+    asset_address VARCHAR(100) NOT NULL,
+    payload BYTEA NOT NULL,
+    block_number BIGINT,
+    attempts INT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    published_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_change_outbox_event_id ON change_outbox(event_id);
+CREATE INDEX idx_change_outbox_pending ON change_outbox(id) WHERE published_at IS NULL;
+CREATE INDEX idx_change_outbox_block_number ON change_outbox(block_number);
@@ -1,16 +1,27 @@
-use axum::{routing::{get, post}, Router};
+use axum::{middleware, routing::{delete, get, post, put}, Router};
+use client::data::feed_builder::FeedBuilder;
 use client::data::models::Artifact;
+use client::data::repo::admin_repo::AdminRepo;
 use client::data::repo::artifact_repo::ArtifactRepo;
 use client::data::repo::cache_repo::CacheRepo;
 use client::data::repo::category_repo::CategoryRepo;
+use client::data::repo::error_repo::ErrorRepo;
+use client::data::repo::feed_repo::FeedRepo;
 use client::data::repo::object_repo::ObjectRepo;
+use client::data::repo::publisher_repo::PublisherRepo;
 use client::data::repo::publishing_repo::PublishingRepo;
 use client::data::repo::report_repo::ReportRepo;
 use client::data::repo::review_repo::ReviewRepo;
 use client::data::repo::search_repo::SearchRepo;
 use client::data::repo::validation_repo::ValidationRepo;
+use client::data::repo::ownership_repo::OwnershipRepo;
+use client::data::repo::outbox_repo::OutboxRepo;
 use client::env::{psql_url, redis_url};
+use client::net::admin_auth::admin_auth;
+use client::net::api_version::negotiate_api_version;
 use client::net::etag_handler::EtagHandler;
+use client::net::invalidation::listen_invalidations;
+use client::net::rate_limit::{rate_limit, RateLimitPolicy, RateLimitStore, RateLimiter, RedisRateLimitStore};
 use client::state::ClientState;
 use client::{env, handler};
 use client_gf::client::GreenfieldClient;
@@ -25,6 +36,7 @@ use net_client::http::HttpProviderFactory;
 use prost::Message;
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
+use std::sync::Arc;
 use axum::http::Method;
 use axum_prometheus::{MetricLayerBuilder, PrometheusMetricLayer};
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
@@ -47,6 +59,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = RedisClient::new(redis_url())
         .expect("Failed to connect to redis");
     let redis_client = arc!(RedisCache::new(client));
+    let rate_limits: Arc<dyn RateLimitStore> = arc!(RedisRateLimitStore::new(redis_client.clone()));
     info!("Database connected successfully.");
 
     // --- Application State ---
@@ -56,6 +69,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let object_repo = arc!(ObjectRepo::new(pg_client.clone()));
     let assetlink_repo = arc!(AssetlinkRepo::new(pg_client.clone()));
     let validation_repo = arc!(ValidationRepo::new(pg_client.clone()));
+    let feed_repo = arc!(FeedRepo::new(pg_client.clone()));
+
+    let http_client = HttpProviderFactory::http_client()
+        .expect("Failed to create http client");
+    let greenfield = arc!(GreenfieldClient::new(http_client, env::gf_node_url(), None));
     
     let state = ClientState {
         object_repo: object_repo.clone(),
@@ -67,15 +85,32 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         validation_repo: validation_repo.clone(),
         assetlink_repo: assetlink_repo.clone(),
         report_repo: arc!(ReportRepo::new(pg_client.clone())),
-        etag_handler: arc!(EtagHandler::new(cache)),
+        admin_repo: arc!(AdminRepo::new(pg_client.clone())),
+        cache_repo: cache.clone(),
+        etag_handler: arc!(EtagHandler::new(cache.clone())),
+        feed_repo: feed_repo.clone(),
+        feed_builder: arc!(FeedBuilder::new(feed_repo, object_repo.clone())),
+        greenfield,
+        publisher_repo: arc!(PublisherRepo::new(pg_client.clone())),
+        error_repo: arc!(ErrorRepo::new(pg_client.clone())),
+        ownership_repo: arc!(OwnershipRepo::new(pg_client.clone())),
+        outbox_repo: arc!(OutboxRepo::new(pg_client.clone())),
     };
 
     info!("Application state created.");
 
+    // Malformed deprecation settings fail on start instead of on the first request
+    info!(
+        "Api versions up to {} are deprecated (at {:?}, sunset {:?})",
+        env::deprecated_api_version(), env::api_deprecated_at(), env::api_sunset_at()
+    );
+
+    tokio::spawn(listen_invalidations(cache.clone()));
+
     // --- CORS Configuration ---
     let cors = CorsLayer::new()
         .allow_origin(tower_http::cors::Any) // Example: Allow any origin
-        .allow_methods([Method::GET, Method::POST]) // Allow all methods or specify
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE]) // Allow all methods or specify
         .allow_headers(tower_http::cors::Any); // Allow all headers or specify
 
     // --- Metrics ---
@@ -83,7 +118,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // --- API Routes ---
     let api_router = Router::new()
-        .nest("/v1", v1_routes()) // Group all v1 routes
+        .nest("/v1", v1_routes(rate_limits.clone())) // Group all v1 routes
+        .nest("/v1/admin", admin_routes(rate_limits, state.clone()))
         .route("/metrics", get(|| async move { metric_handle.render() }));
 
     // --- Main Router ---
@@ -103,7 +139,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let listener = tokio::net::TcpListener::bind(addr)
         .await?;
 
-    axum::serve(listener, app)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(shutdown_signal())
         .await
         .expect("Failed to start server");
@@ -112,10 +148,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 // Define v1 routes
-fn v1_routes() -> Router<ClientState> {
+fn v1_routes(rate_limits: Arc<dyn RateLimitStore>) -> Router<ClientState> {
+    let limit = |policy: RateLimitPolicy| {
+        middleware::from_fn_with_state(RateLimiter::new(rate_limits.clone(), policy), rate_limit)
+    };
+
     Router::new()
-        // Admin Routes (Consider adding auth middleware layer)
-        // .route("/admin/set_categories", post(handler::admin::set_categories))
         // Store Routes
         .route("/feed", get(handler::store::get_feed))
         .route("/store/categories", get(handler::store::get_categories))
@@ -123,15 +161,44 @@ fn v1_routes() -> Router<ClientState> {
         .route("/asset/chart", get(handler::store::get_chart))
         .route("/asset/id/{asset_id}", get(handler::object::get_object_by_id))
         .route("/asset/address/{address}", get(handler::object::get_object_by_address))
-        .route("/asset/search", get(handler::search::search_objects))
+        .route("/asset/search", get(handler::search::search_objects).layer(limit(RateLimitPolicy::search())))
         .route("/asset/status/{address}", get(handler::object::get_object_status_by_address))
         // Artifact
+        .route("/asset/{asset_id}/artifact", get(handler::artifact::get_latest_artifact))
+        .route("/asset/{asset_id}/download", get(handler::download::download_artifact).layer(limit(RateLimitPolicy::download())))
+        .route("/asset/{asset_id}/versions", get(handler::object::get_asset_versions))
+        .route("/asset/{asset_id}/owners", get(handler::object::get_asset_owners))
+        .route("/asset/updates", post(handler::update::check_updates).layer(limit(RateLimitPolicy::update_check())))
         .route("/asset/{asset_id}/{track_id}/artifact", get(handler::artifact::get_artifact))
+        // Publisher
+        .route("/publisher/{address}", get(handler::publisher::get_publisher))
         // Review Routes
         .route("/review/{asset_id}", get(handler::review::get_reviews_for_object))
-        .route("/review/create", post(handler::review::create_review))
+        .route("/review/create", post(handler::review::create_review).layer(limit(RateLimitPolicy::review())))
         // Report Route
-        .route("/report/create", post(handler::report::create_report))
+        .route("/report/create", post(handler::report::create_report).layer(limit(RateLimitPolicy::report())))
         // Utils
         .route("/health", get(handler::util::handle_health))
+        // Every public route is served in the shape of the negotiated `X-API-VERSION`
+        .layer(middleware::from_fn(negotiate_api_version))
+}
+
+// Admin routes, `admin_auth` resolves `AdminAuth` and every handler checks its role
+fn admin_routes(rate_limits: Arc<dyn RateLimitStore>, state: ClientState) -> Router<ClientState> {
+    let limiter = RateLimiter::new(rate_limits, RateLimitPolicy::admin());
+
+    Router::new()
+        .route("/categories", put(handler::admin::set_categories))
+        .route("/categories/{category_id}", delete(handler::admin::delete_category))
+        .route("/asset/{asset_id}/visibility", put(handler::admin::set_visibility))
+        .route("/feed", get(handler::admin::get_feed_sections).put(handler::admin::set_feed))
+        .route("/chart/order", get(handler::admin::get_chart_order).put(handler::admin::set_chart_order))
+        .route("/reports", get(handler::admin::get_reports))
+        .route("/reports/{report_id}", put(handler::admin::resolve_report))
+        .route("/reports/flagged", get(handler::admin::get_flagged_assets))
+        .route("/audit", get(handler::admin::get_audit_log))
+        .route("/failed-logs", get(handler::admin::get_failed_logs))
+        .route("/failed-logs/{failed_log_id}/retry", post(handler::admin::redrive_failed_log))
+        .layer(middleware::from_fn_with_state(state, admin_auth))
+        .layer(middleware::from_fn_with_state(limiter, rate_limit))
 }
@@ -1,21 +1,31 @@
 use alloy::sol_types::sol_data::String;
 use alloy::transports::http::reqwest::Url;
+use client::daemon::cli::DaemonCommand;
 use client::daemon::data::data_sync::DataSyncHandler;
 use client::daemon::data::object_factory::ObjectFactory;
+use client::daemon::data::stat_repo::StatSyncHandler;
 use client::daemon::handler::chain_sync_v0::ChainSyncHandlerV0;
-use client::daemon::handler::chain_sync_v1::ChainSyncHandlerV1;
-use client::daemon::handler::sync::add_to_track::AddToTrack;
-use client::daemon::handler::sync::block_finalized::BlockFinalizedHandler;
-use client::daemon::handler::sync::new_req::NewRequestHandler;
+use client::daemon::handler::change_outbox::ChangeOutboxHandler;
+use client::daemon::handler::downloads_recount::DownloadsRecountHandler;
+use client::daemon::handler::failed_log_retry::FailedLogRetryHandler;
+use client::daemon::handler::resync::ResyncHandler;
+use client::daemon::handler::trending::TrendingHandler;
+use client::daemon::handler::sync::add_to_track_v0::AddToTrackHandlerV0;
+use client::daemon::handler::sync::block_finalized_v0::BlockFinalizedHandlerV0;
 use client::daemon::handler::sync::new_req_v0::NewRequestHandlerV0;
-use client::daemon::handler::sync::sync_finish::SyncFinishedHandler;
+use client::daemon::handler::sync::ownership_v0::OwnershipHandlerV0;
+use client::daemon::handler::sync::publisher_v0::PublisherHandlerV0;
 use client::daemon::handler::sync::sync_finish_v0::SyncFinishedHandlerV0;
 use client::daemon::launcher::{DaemonAction, DaemonEventHandler, DaemonQueue};
 use client::data::repo::artifact_repo::ArtifactRepo;
 use client::data::repo::assetlink_repo::AssetlinkRepo;
 use client::data::repo::batch_repo::BatchRepo;
+use client::data::repo::cache_repo::CacheRepo;
 use client::data::repo::error_repo::ErrorRepo;
 use client::data::repo::object_repo::ObjectRepo;
+use client::data::repo::outbox_repo::OutboxRepo;
+use client::data::repo::ownership_repo::OwnershipRepo;
+use client::data::repo::publisher_repo::PublisherRepo;
 use client::data::repo::publishing_repo::PublishingRepo;
 use client::data::repo::validation_repo::ValidationRepo;
 use client::env;
@@ -29,7 +39,11 @@ use core_std::arc;
 use core_std::profile::is_debug;
 use core_std::shutdown::shutdown_signal;
 use core_std::url::Localhost;
+use db_ch::client::ChClient;
+use db_kf::client::KfProducer;
 use db_psql::client::PgClient;
+use db_redis::cache::RedisCache;
+use db_redis::client::RedisClient;
 use dotenvy::dotenv;
 use lazy_static::lazy_static;
 use net_client::http::HttpProviderFactory;
@@ -54,6 +68,14 @@ use tracing::{error, info, warn};
 //     .build();
 #[tokio::main]
 async fn main() {
+    let command = match DaemonCommand::parse(&std::env::args().skip(1).collect::<Vec<_>>()) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
     info!("Starting daemon!");
     dotenv().ok();
     let _guard = core_log::init_tracer();
@@ -83,6 +105,10 @@ async fn main() {
     };
     info!("Database connected successfully.");
 
+    let redis_client = RedisClient::new(env::redis_url())
+        .expect("Failed to connect to redis");
+    let cache_repo = arc!(CacheRepo::new(arc!(RedisCache::new(redis_client))));
+
 
     info!("Create DB repositories...");
     let publishing_repo = arc!(PublishingRepo::new(pg_client.clone()));
@@ -91,7 +117,10 @@ async fn main() {
     let object_repo = arc!(ObjectRepo::new(pg_client.clone()));
     let validation_repo = arc!(ValidationRepo::new(pg_client.clone()));
     let error_repo = arc!(ErrorRepo::new(pg_client.clone()));
+    let ownership_repo = arc!(OwnershipRepo::new(pg_client.clone()));
+    let outbox_repo = arc!(OutboxRepo::new(pg_client.clone()));
     let batch_repo = arc!(BatchRepo::new(pg_client.clone()));
+    let publisher_repo = arc!(PublisherRepo::new(pg_client.clone()));
 
     info!("Create Web3 providers...");
     let node_url = env::eth_node_url().parse::<Url>()
@@ -162,6 +191,27 @@ async fn main() {
         error_repo.clone(),
     ));
 
+    let add_to_track_handler = arc!(AddToTrackHandlerV0::new(
+        factory.clone(),
+        error_repo.clone(),
+    ));
+
+    let block_finalized_handler = arc!(BlockFinalizedHandlerV0::new(
+        factory.clone(),
+        store_service.clone(),
+        object_repo.clone(),
+        artifact_repo.clone(),
+        error_repo.clone(),
+    ));
+
+    let publisher_handler = arc!(PublisherHandlerV0::new(
+        error_repo.clone(),
+    ));
+
+    let ownership_handler = arc!(OwnershipHandlerV0::new(
+        error_repo.clone(),
+    ));
+
     let data_sync_handler = arc!(DataSyncHandler::new(
         pg_client.clone(),
         object_repo.clone(),
@@ -170,23 +220,111 @@ async fn main() {
         artifact_repo.clone(),
         validation_repo.clone(),
         publishing_repo.clone(),
+        publisher_repo.clone(),
+        ownership_repo.clone(),
+        outbox_repo.clone(),
         error_repo.clone(),
+        cache_repo.clone(),
     ));
 
+    let publisher_factory = env::publisher_factory_address();
+    if publisher_factory.is_none() {
+        warn!("[DAEMON_SYNC] `PUBLISHER_FACTORY_ADDRESS` isn't set, publisher accounts and their apps aren't synced");
+    }
+
     let sync = arc!(ChainSyncHandlerV0::new(
         env::historical_sync_block(),
+        env::sync_confirmations(),
+        env::sync_reorg_depth(),
         Duration::from_millis(env::sync_retry_ms()),
         Duration::from_millis(env::sync_timeout_ms()),
+        publisher_factory,
         web3.clone(),
         event_service.clone(),
         graph.clone(),
         data_sync_handler.clone(),
         sync_finish_handler.clone(),
         req_new_handler.clone(),
+        add_to_track_handler.clone(),
+        block_finalized_handler.clone(),
+        publisher_handler.clone(),
+        ownership_handler.clone(),
+    ));
+
+    // Maintenance commands run once and exit instead of launching the daemon
+    if command != DaemonCommand::Run {
+        let resync = ResyncHandler::new(
+            sync.clone(),
+            data_sync_handler.clone(),
+            factory.clone(),
+            obj_service.clone(),
+            object_repo.clone(),
+            artifact_repo.clone(),
+        );
+
+        let code = match resync.handle(command).await {
+            Ok(diffs) => {
+                for diff in diffs.iter() {
+                    println!("{}", diff);
+                }
+                0
+            }
+            Err(e) => {
+                error!("[RESYNC] Failed: {}", e);
+                eprintln!("{}", e);
+                1
+            }
+        };
+        std::process::exit(code);
+    }
+
+    let ch_client = arc!(ChClient::new_client(
+        env::ch_url(),
+        env::ch_db_env().ok(),
+        env::ch_user_env().ok(),
+        env::ch_pass_env().ok(),
+    ));
+    let stat = arc!(StatSyncHandler::new(ch_client, pg_client.clone()));
+    let recount = arc!(DownloadsRecountHandler::new(
+        Duration::from_secs(env::downloads_recount_interval_sec()),
+        Duration::from_secs(env::downloads_recount_lag_sec()),
+        stat.clone(),
+        cache_repo.clone(),
+    ));
+    let trending = arc!(TrendingHandler::new(
+        Duration::from_secs(env::trending_refresh_sec()),
+        stat.clone(),
+        cache_repo.clone(),
+    ));
+    let failed_log_retry = arc!(FailedLogRetryHandler::new(
+        Duration::from_secs(env::failed_log_retry_interval_sec()),
+        env::failed_log_retry_batch(),
+        sync.clone(),
+        data_sync_handler.clone(),
+        error_repo.clone(),
+    ));
+    let producer = env::kf_broker_env().ok().map(|brokers| arc!(
+        KfProducer::new_client(brokers, Some(env::kf_client()))
+            .expect("Failed to create kafka producer")
+    ));
+    let change_outbox = arc!(ChangeOutboxHandler::new(
+        Duration::from_secs(env::change_outbox_interval_sec()),
+        env::change_outbox_batch(),
+        Duration::from_secs(env::change_outbox_retention_sec()),
+        env::change_topic(),
+        producer,
+        data_sync_handler.committed(),
+        outbox_repo.clone(),
     ));
 
     info!("Launch daemon...");
-    let daemon = arc!(DaemonEventHandler::new(sync.clone()));
+    let daemon = arc!(DaemonEventHandler::new(
+        sync.clone(),
+        recount.clone(),
+        trending.clone(),
+        failed_log_retry.clone(),
+        change_outbox.clone(),
+    ));
     let queue = arc!(DaemonQueue::new(100));
 
     info!("Demon deps created.");
@@ -0,0 +1,64 @@
+use alloy::primitives::Address;
+use std::str::FromStr;
+
+pub const USAGE: &str = "Usage: daemon-client [resync <from_block> <to_block> | refresh <asset_address>]";
+
+// Maintenance commands of the daemon binary, without arguments the daemon runs as usual
+#[derive(Debug, Clone, PartialEq)]
+pub enum DaemonCommand {
+    Run,
+    // Syncs the inclusive block range again
+    Resync { from_block: u64, to_block: u64 },
+    // Refetches one asset from its contracts and Greenfield
+    Refresh { address: Address },
+}
+
+impl DaemonCommand {
+
+    pub fn parse<S: AsRef<str>>(args: &[S]) -> Result<Self, String> {
+        let args = args.iter()
+            .map(|arg| arg.as_ref())
+            .collect::<Vec<&str>>();
+
+        return match args.as_slice() {
+            [] => Ok(Self::Run),
+            ["resync", from_block, to_block] => {
+                let from_block = parse_block(from_block)?;
+                let to_block = parse_block(to_block)?;
+                if from_block > to_block {
+                    return Err(format!("Range start {} is after its end {}", from_block, to_block));
+                }
+
+                Ok(Self::Resync { from_block, to_block })
+            }
+            ["refresh", address] => Address::from_str(address)
+                .map(|address| Self::Refresh { address })
+                .map_err(|_| format!("Invalid asset address: {}", address)),
+            _ => Err(USAGE.to_string()),
+        };
+    }
+}
+
+fn parse_block(value: &str) -> Result<u64, String> {
+    return value.parse::<u64>()
+        .map_err(|_| format!("Invalid block number: {}", value));
+}
+
+#[test]
+fn check_parse_command() {
+    let none: [&str; 0] = [];
+    assert_eq!(DaemonCommand::parse(&none), Ok(DaemonCommand::Run));
+    assert_eq!(
+        DaemonCommand::parse(&["resync", "100", "200"]),
+        Ok(DaemonCommand::Resync { from_block: 100, to_block: 200 })
+    );
+    assert_eq!(
+        DaemonCommand::parse(&["refresh", "0x0000000000000000000000000000000000000001"]),
+        Ok(DaemonCommand::Refresh { address: Address::with_last_byte(1) })
+    );
+
+    assert!(DaemonCommand::parse(&["resync", "200", "100"]).is_err());
+    assert!(DaemonCommand::parse(&["resync", "a", "100"]).is_err());
+    assert!(DaemonCommand::parse(&["refresh", "0x01"]).is_err());
+    assert_eq!(DaemonCommand::parse(&["sync"]), Err(USAGE.to_string()));
+}
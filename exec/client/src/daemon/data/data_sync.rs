@@ -1,15 +1,25 @@
-use crate::data::models::{AssetlinkSync, NewArtifact, NewAsset, NewBuildRequest, Publishing, ValidationProof};
+use crate::data::change_event;
+use crate::data::id::OwnershipChangeKind;
+use crate::data::models::{AssetlinkSync, CacheEvent, NewArtifact, NewAsset, NewBuildRequest, NewChangeEvent, NewOwnershipChange, NewPublisher, NewPublisherApp, Publishing, RequestValidation, ValidationProof};
 use crate::data::repo::artifact_repo::ArtifactRepo;
 use crate::data::repo::assetlink_repo::AssetlinkRepo;
-use crate::data::repo::batch_repo::{BatchRepo, TransactionBatch, TransactionStatus};
+use crate::data::repo::batch_repo::{BatchRepo, SyncBlock, TransactionBatch, TransactionStatus};
+use crate::data::repo::cache_repo::CacheRepo;
 use crate::data::repo::error_repo::ErrorRepo;
 use crate::data::repo::object_repo::ObjectRepo;
+use crate::data::repo::outbox_repo::OutboxRepo;
+use crate::data::repo::ownership_repo::OwnershipRepo;
 use crate::data::repo::validation_repo::ValidationRepo;
+use codegen_block::status::ApkValidationStatus;
 use db_psql::client::PgClient;
+use sqlx::{Postgres, Transaction};
 use service_graph::client::AppAsset;
+use std::collections::BTreeSet;
 use std::sync::Arc;
-use tracing::error;
+use tokio::sync::Notify;
+use tracing::{error, warn};
 use crate::data::repo::publishing_repo::PublishingRepo;
+use crate::data::repo::publisher_repo::PublisherRepo;
 use crate::result::ClientResult;
 
 pub enum LogResultData {
@@ -23,6 +33,32 @@ pub enum LogResultData {
         Option<AssetlinkSync>,
         Option<ValidationProof>,
     ),
+    AddToTrack(
+        Option<Publishing>,
+    ),
+    BlockFinalized(
+        Vec<NewBuildRequest>,
+        Vec<RequestValidation>,
+        Vec<NewArtifact>,
+        Vec<NewAsset>,
+        Vec<Publishing>,
+    ),
+    NewPublisher(
+        Option<NewPublisher>,
+    ),
+    NewPublisherApp(
+        Option<NewPublisherApp>,
+        Option<NewOwnershipChange>,
+    ),
+    OwnershipTransferred(
+        Option<NewOwnershipChange>,
+    ),
+}
+
+// Rows are tagged with the block of their event, so a reorg can roll them back
+pub struct BlockLogData {
+    pub block_number: u64,
+    pub data: LogResultData,
 }
 
 pub struct DataSyncHandler {
@@ -33,7 +69,13 @@ pub struct DataSyncHandler {
     art_repo: Arc<ArtifactRepo>,
     validation_repo: Arc<ValidationRepo>,
     publishing_repo: Arc<PublishingRepo>,
+    publisher_repo: Arc<PublisherRepo>,
+    ownership_repo: Arc<OwnershipRepo>,
+    outbox_repo: Arc<OutboxRepo>,
     error_repo: Arc<ErrorRepo>,
+    cache_repo: Arc<CacheRepo>,
+    // Wakes the outbox relay after a commit of logs, they may have written change events
+    committed: Arc<Notify>,
 }
 
 impl DataSyncHandler {
@@ -46,7 +88,11 @@ impl DataSyncHandler {
         art_repo: Arc<ArtifactRepo>,
         validation_repo: Arc<ValidationRepo>,
         publishing_repo: Arc<PublishingRepo>,
+        publisher_repo: Arc<PublisherRepo>,
+        ownership_repo: Arc<OwnershipRepo>,
+        outbox_repo: Arc<OutboxRepo>,
         error_repo: Arc<ErrorRepo>,
+        cache_repo: Arc<CacheRepo>,
     ) -> Self {
         Self {
             client,
@@ -56,88 +102,696 @@ impl DataSyncHandler {
             art_repo,
             validation_repo,
             publishing_repo,
+            publisher_repo,
+            ownership_repo,
+            outbox_repo,
             error_repo,
+            cache_repo,
+            committed: Arc::new(Notify::new()),
         }
     }
 
+    pub fn committed(&self) -> Arc<Notify> {
+        return self.committed.clone();
+    }
+
     pub async fn last_sync_batch(&self) -> ClientResult<Option<TransactionBatch>> {
         return self.batch_repo.get_last_batch().await;
     }
 
+    pub async fn known_publishers(&self) -> ClientResult<Vec<String>> {
+        return self.publisher_repo.find_addresses().await;
+    }
+
+    pub async fn known_apps(&self) -> ClientResult<Vec<String>> {
+        return self.ownership_repo.find_app_addresses().await;
+    }
+
+    pub async fn synced_blocks(&self, limit: u64) -> ClientResult<Vec<SyncBlock>> {
+        return self.batch_repo.get_block_hashes(limit as i64).await;
+    }
+
+    // Start of the sync after a rollback to the last canonical block
+    pub async fn rollback_point(&self, canonical_block: u64) -> ClientResult<Option<u64>> {
+        let end = self.batch_repo.get_batch_end_before(canonical_block as i64 + 1)
+            .await?;
+
+        return Ok(end.map(|end| end as u64));
+    }
+
+    pub async fn rollback(&self, block_number: u64) -> ClientResult<()> {
+        let addresses = self.batch_repo.rollback(block_number as i64)
+            .await?;
+
+        if !addresses.is_empty() {
+            let event = CacheEvent::AssetChanged { addresses };
+            if let Err(e) = self.cache_repo.publish_event(&event).await {
+                warn!("[DAEMON_SYNC] Can't publish cache event: {}", e);
+            }
+        }
+
+        return Ok(());
+    }
+
+    // Writes of the batch and its checkpoint commit together, after a crash the batch is synced again from scratch
     pub async fn sync(
         &self,
-        new_data: &Vec<LogResultData>,
+        new_data: &Vec<BlockLogData>,
         apps: &Option<Vec<AppAsset>>,
+        blocks: &[SyncBlock],
         from_block: u64,
         last_block_number: u64,
-    ) {
-        // if let Ok(transaction) = self.client.start().await {
-            for data in new_data.iter() {
-                match data {
-                    LogResultData::NewRequest(request, artifact, asset, publish) => {
-                        if let Some(obj) = asset {
-                            if let Err(e) = self.object_repo.insert_or_update(&obj).await {
-                                error!("[NEW_REQ_HANDLER] Can't insert asset with {}: {}", obj.address, e);
-                            };
-                        }
+    ) -> ClientResult<()> {
+        let mut tx = self.client.start()
+            .await?;
 
-                        if let Some(artifact) = artifact {
-                            if let Err(e) = self.art_repo.insert_artifact(&artifact).await {
-                                error!("[NEW_REQ_HANDLER] Can't insert artifact for {} with ref {}: {}", artifact.asset_address, artifact.object_ref, e);
-                            };
-                        }
+        self.apply(&mut tx, new_data, apps, blocks, from_block, last_block_number)
+            .await?;
 
-                        if let Some(request) = request {
-                            if let Err(e) = self.validation_repo.insert_or_update(&request).await {
-                                error!("[NEW_REQ_HANDLER] Can't insert build for {}: {}", request.asset_address, e);
-                            };
-                        }
+        tx.commit()
+            .await
+            .inspect_err(|e| error!("[DAEMON_SYNC] Can't commit batch from {} to {}: {}", from_block, last_block_number, e))?;
+
+        self.publish_changes(new_data, apps).await;
+        if !new_data.is_empty() {
+            self.committed.notify_one();
+        }
+
+        return Ok(());
+    }
+
+    // Nothing is visible until the caller commits, the first failed write aborts the whole batch
+    pub async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        new_data: &Vec<BlockLogData>,
+        apps: &Option<Vec<AppAsset>>,
+        blocks: &[SyncBlock],
+        from_block: u64,
+        last_block_number: u64,
+    ) -> ClientResult<()> {
+        self.apply_logs(tx, new_data)
+            .await?;
+
+        if let Some(apps) = apps {
+            for app in apps {
+                self.object_repo.update_app_graph(&mut **tx, app)
+                    .await
+                    .inspect_err(|e| error!("[DAEMON_SYNC] Graph batch update failed: {}", e))?;
+            }
+        }
 
-                        if let Some(publish) = publish {
-                            if let Err(e) = self.publishing_repo.insert_or_update(&publish).await {
-                                error!("[NEW_REQ_HANDLER] Can't insert publish for {} with track {} and version {}: {}", publish.asset_address, publish.track_id, publish.version_code, e);
-                            };
+        self.batch_repo.save_block_hashes(&mut **tx, blocks)
+            .await
+            .inspect_err(|e| error!("[DAEMON_SYNC] Can't save block hashes from {} to {}: {}", from_block, last_block_number, e))?;
+
+        self.batch_repo
+            .save_batch(&mut **tx, TransactionBatch {
+                from_block_number: from_block as i64,
+                to_block_number: last_block_number as i64,
+                status: TransactionStatus::Confirmed,
+            })
+            .await
+            .inspect_err(|e| error!("[DAEMON_SYNC] Can't save batch from {} to {}: {}", from_block, last_block_number, e))?;
+
+        return Ok(());
+    }
+
+    // Retried logs belong to blocks that are already checkpointed, only their rows are written
+    pub async fn sync_logs(&self, new_data: &Vec<BlockLogData>) -> ClientResult<()> {
+        let mut tx = self.client.start()
+            .await?;
+
+        self.apply_logs(&mut tx, new_data)
+            .await?;
+
+        tx.commit()
+            .await
+            .inspect_err(|e| error!("[DAEMON_SYNC] Can't commit retried logs: {}", e))?;
+
+        self.publish_changes(new_data, &None).await;
+        if !new_data.is_empty() {
+            self.committed.notify_one();
+        }
+
+        return Ok(());
+    }
+
+    async fn apply_logs(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        new_data: &Vec<BlockLogData>,
+    ) -> ClientResult<()> {
+        for BlockLogData { block_number, data } in new_data.iter() {
+            let block_number = Some(*block_number as i64);
+            match data {
+                LogResultData::NewRequest(request, artifact, asset, publish) => {
+                    if let Some(obj) = asset {
+                        let created = self.object_repo.insert_or_update(&mut **tx, &obj, block_number)
+                            .await
+                            .inspect_err(|e| error!("[NEW_REQ_HANDLER] Can't insert asset with {}: {}", obj.address, e))?;
+
+                        if let Some(owner) = created {
+                            self.insert_event(tx, &change_event::asset_listed(obj, &owner, block_number))
+                                .await?;
                         }
                     }
-                    LogResultData::FinishSync(sync, proof) => {
-                        if let Some(sync) = sync {
-                            let result = self.assetlink_repo.insert_assetlink_status(&sync).await;
 
-                            if let Err(e) = result {
-                                error!("[SYNC_FINISH_HANDLER] Failed to insert assetlink status for asset {} with version {}: {}", sync.asset_address, sync.owner_version, e);
-                            }
-                        }
+                    if let Some(artifact) = artifact {
+                        self.art_repo.insert_artifact(&mut **tx, &artifact, block_number)
+                            .await
+                            .inspect_err(|e| error!("[NEW_REQ_HANDLER] Can't insert artifact for {} with ref {}: {}", artifact.asset_address, artifact.object_ref, e))?;
+                    }
 
-                        if let Some(proof) = proof {
-                           let err = self.assetlink_repo.insert_validation_proof(proof).await;
+                    if let Some(request) = request {
+                        self.validation_repo.insert_or_update(&mut **tx, &request, block_number)
+                            .await
+                            .inspect_err(|e| error!("[NEW_REQ_HANDLER] Can't insert build for {}: {}", request.asset_address, e))?;
+                    }
+
+                    if let Some(publish) = publish {
+                        self.publishing_repo.insert_or_update(&mut **tx, &publish, block_number)
+                            .await
+                            .inspect_err(|e| error!("[NEW_REQ_HANDLER] Can't insert publish for {} with track {} and version {}: {}", publish.asset_address, publish.track_id, publish.version_code, e))?;
+                    }
+                }
+                LogResultData::FinishSync(sync, proof) => {
+                    if let Some(sync) = sync {
+                        self.assetlink_repo.insert_assetlink_status(&mut **tx, &sync, block_number)
+                            .await
+                            .inspect_err(|e| error!("[SYNC_FINISH_HANDLER] Failed to insert assetlink status for asset {} with version {}: {}", sync.asset_address, sync.owner_version, e))?;
+                    }
 
-                            if let Err(e) = err {
-                                error!("[SYNC_FINISH_HANDLER] Failed to insert proof status for asset {} with version {}: {}", proof.asset_address, proof.owner_version, e);
-                            }
+                    if let Some(proof) = proof {
+                        self.assetlink_repo.insert_validation_proof(&mut **tx, proof, block_number)
+                            .await
+                            .inspect_err(|e| error!("[SYNC_FINISH_HANDLER] Failed to insert proof status for asset {} with version {}: {}", proof.asset_address, proof.owner_version, e))?;
+                    }
+                }
+                LogResultData::AddToTrack(publish) => {
+                    if let Some(publish) = publish {
+                        self.publishing_repo.insert_or_update(&mut **tx, &publish, block_number)
+                            .await
+                            .inspect_err(|e| error!("[ADD_TO_TRACK] Can't insert publish for {} with track {} and version {}: {}", publish.asset_address, publish.track_id, publish.version_code, e))?;
+                    }
+                }
+                LogResultData::BlockFinalized(requests, validations, artifacts, assets, publishings) => {
+                    for obj in assets.iter() {
+                        let created = self.object_repo.insert_or_update(&mut **tx, obj, block_number)
+                            .await
+                            .inspect_err(|e| error!("[BLOCK_FINALIZED] Can't insert asset with {}: {}", obj.address, e))?;
+
+                        if let Some(owner) = created {
+                            self.insert_event(tx, &change_event::asset_listed(obj, &owner, block_number))
+                                .await?;
                         }
                     }
+
+                    for artifact in artifacts.iter() {
+                        self.art_repo.insert_artifact(&mut **tx, artifact, block_number)
+                            .await
+                            .inspect_err(|e| error!("[BLOCK_FINALIZED] Can't insert artifact for {} with ref {}: {}", artifact.asset_address, artifact.object_ref, e))?;
+                    }
+
+                    for request in requests.iter() {
+                        self.validation_repo.insert_or_update(&mut **tx, request, block_number)
+                            .await
+                            .inspect_err(|e| error!("[BLOCK_FINALIZED] Can't insert build {} for {}: {}", request.id, request.asset_address, e))?;
+                    }
+
+                    let success = Some(ApkValidationStatus::Success.code() as i32);
+                    for request in requests.iter().filter(|request| request.status == success) {
+                        self.insert_event(tx, &change_event::build_validated(request, block_number))
+                            .await?;
+                    }
+
+                    for validation in validations.iter() {
+                        self.assetlink_repo.insert_request_validation(&mut **tx, validation, block_number)
+                            .await
+                            .inspect_err(|e| error!("[BLOCK_FINALIZED] Can't insert validation of request {}: {}", validation.request_id, e))?;
+                    }
+
+                    for publish in publishings.iter() {
+                        self.publishing_repo.insert_or_update(&mut **tx, publish, block_number)
+                            .await
+                            .inspect_err(|e| error!("[BLOCK_FINALIZED] Can't insert publish for {} with track {} and version {}: {}", publish.asset_address, publish.track_id, publish.version_code, e))?;
+                    }
                 }
-            }
+                LogResultData::NewPublisher(publisher) => {
+                    if let Some(publisher) = publisher {
+                        self.publisher_repo.insert_publisher(&mut **tx, &publisher)
+                            .await
+                            .inspect_err(|e| error!("[PUBLISHER] Can't insert publisher {}: {}", publisher.address, e))?;
+                    }
+                }
+                LogResultData::NewPublisherApp(app, ownership) => {
+                    if let Some(app) = app {
+                        let inserted = self.publisher_repo.insert_app(&mut **tx, &app)
+                            .await
+                            .inspect_err(|e| error!("[PUBLISHER] Can't insert app {} for {}: {}", app.asset_address, app.publisher_address, e))?;
+
+                        if inserted {
+                            self.insert_event(tx, &change_event::asset_created(&app, block_number))
+                                .await?;
+                        } else {
+                            warn!("[PUBLISHER] Skip app {} of unknown publisher {}", app.asset_address, app.publisher_address);
+                        }
+                    }
 
-            if let Some(apps) = apps {
-                for app in apps {
-                    if let Err(e) = self.object_repo.update_app_graph(app).await {
-                        error!("[DAEMON_SYNC] Graph batch update failed: {}", e);
+                    if let Some(ownership) = ownership {
+                        self.insert_ownership(tx, ownership, block_number)
+                            .await?;
+                    }
+                }
+                LogResultData::OwnershipTransferred(ownership) => {
+                    if let Some(ownership) = ownership {
+                        self.insert_ownership(tx, ownership, block_number)
+                            .await?;
                     }
                 }
             }
+        }
+
+        return Ok(());
+    }
+
+    // Asset data refetched from its contracts, written outside of any batch
+    pub async fn refresh_asset(&self, asset: &NewAsset, artifacts: &[NewArtifact], owner: &str) -> ClientResult<()> {
+        let mut tx = self.client.start()
+            .await?;
+
+        self.object_repo.insert_or_update(&mut *tx, asset, None)
+            .await
+            .inspect_err(|e| error!("[RESYNC] Can't update asset {}: {}", asset.address, e))?;
+
+        for artifact in artifacts.iter() {
+            self.art_repo.insert_artifact(&mut *tx, artifact, None)
+                .await
+                .inspect_err(|e| error!("[RESYNC] Can't update artifact of {} with version {}: {}", artifact.asset_address, artifact.version_code, e))?;
+        }
+
+        self.object_repo.update_owner(&mut *tx, &asset.address, owner)
+            .await
+            .inspect_err(|e| error!("[RESYNC] Can't update owner of {}: {}", asset.address, e))?;
+
+        tx.commit()
+            .await?;
+
+        let event = CacheEvent::AssetChanged { addresses: vec![asset.address.clone()] };
+        if let Err(e) = self.cache_repo.publish_event(&event).await {
+            warn!("[RESYNC] Can't publish cache event: {}", e);
+        }
+
+        return Ok(());
+    }
+
+    async fn insert_ownership(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        change: &NewOwnershipChange,
+        block_number: Option<i64>,
+    ) -> ClientResult<()> {
+        let inserted = self.ownership_repo.insert_change(tx, change, block_number.unwrap_or_default())
+            .await
+            .inspect_err(|e| error!("[OWNERSHIP] Can't insert owner change of {} in {}: {}", change.asset_address, change.tx_hash, e))?;
+
+        // The first owner is a part of `asset_created`
+        if inserted && change.kind == OwnershipChangeKind::Transferred {
+            self.insert_event(tx, &change_event::owner_transferred(change, block_number))
+                .await?;
+        }
+
+        return Ok(());
+    }
+
+    async fn insert_event(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        event: &NewChangeEvent,
+    ) -> ClientResult<()> {
+        self.outbox_repo.insert_event(&mut **tx, event)
+            .await
+            .inspect_err(|e| error!("[CHANGE_OUTBOX] Can't insert event {}: {}", event.event_id, e))?;
+
+        return Ok(());
+    }
+
+    // Api keeps etag caches of charts and feeds, they are purged once the batch is saved
+    async fn publish_changes(&self, new_data: &Vec<BlockLogData>, apps: &Option<Vec<AppAsset>>) {
+        let addresses = changed_addresses(new_data, apps);
+        if addresses.is_empty() {
+            return;
+        }
+
+        let event = CacheEvent::AssetChanged { addresses };
+        if let Err(e) = self.cache_repo.publish_event(&event).await {
+            warn!("[DAEMON_SYNC] Can't publish cache event: {}", e);
+        }
+    }
+}
 
-            let _ = self.batch_repo
-                .save_batch(TransactionBatch {
-                    from_block_number: from_block as i64,
-                    to_block_number: last_block_number as i64,
-                    status: TransactionStatus::Confirmed,
-                })
-                .await;
+pub fn changed_addresses(new_data: &Vec<BlockLogData>, apps: &Option<Vec<AppAsset>>) -> Vec<String> {
+    let mut addresses = BTreeSet::new();
 
-            // if let Err(e) = transaction.commit().await {
-            //     error!("[NEW_REQ_HANDLER] Can't commit transaction {}, from {} | to {}", e, from_block, last_block_number);
-            // };
-        // }
+    for BlockLogData { data, .. } in new_data.iter() {
+        match data {
+            LogResultData::NewRequest(request, _, asset, publish) => {
+                addresses.extend(asset.iter().map(|asset| asset.address.clone()));
+                addresses.extend(request.iter().map(|request| request.asset_address.clone()));
+                addresses.extend(publish.iter().map(|publish| publish.asset_address.clone()));
+            }
+            LogResultData::FinishSync(sync, proof) => {
+                addresses.extend(sync.iter().map(|sync| sync.asset_address.clone()));
+                addresses.extend(proof.iter().map(|proof| proof.asset_address.clone()));
+            }
+            LogResultData::AddToTrack(publish) => {
+                addresses.extend(publish.iter().map(|publish| publish.asset_address.clone()));
+            }
+            LogResultData::BlockFinalized(requests, _, _, assets, publishings) => {
+                addresses.extend(assets.iter().map(|asset| asset.address.clone()));
+                addresses.extend(requests.iter().map(|request| request.asset_address.clone()));
+                addresses.extend(publishings.iter().map(|publish| publish.asset_address.clone()));
+            }
+            LogResultData::OwnershipTransferred(ownership) => {
+                addresses.extend(ownership.iter().map(|ownership| ownership.asset_address.clone()));
+            }
+            // Publisher profiles aren't behind etag caches
+            LogResultData::NewPublisher(_) | LogResultData::NewPublisherApp(..) => {}
+        }
     }
+
+    if let Some(apps) = apps {
+        addresses.extend(apps.iter().map(|app| app.id.clone()));
+    }
+
+    return addresses.into_iter().collect();
+}
+
+// Simulates a crash before commit and a failed write in the middle of a batch. Every case runs in
+// a savepoint of one transaction that is never committed, the database is left as it was
+#[tokio::test]
+#[ignore = "needs a migrated DATABASE_URL"]
+async fn check_batch_is_atomic() {
+    use crate::env;
+    use crate::data::repo::cache_repo::CacheRepo;
+    use db_redis::cache::RedisCache;
+    use db_redis::client::RedisClient;
+    use sqlx::{Acquire, PgConnection};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let client = PgClient::connect(env::psql_url().as_ref()).await.unwrap();
+    // Nothing listens there, cache events are dropped with a warning
+    let redis = RedisClient::new("redis://127.0.0.1:1".to_string()).unwrap();
+
+    let handler = DataSyncHandler::new(
+        client.clone(),
+        Arc::new(ObjectRepo::new(client.clone())),
+        Arc::new(BatchRepo::new(client.clone())),
+        Arc::new(AssetlinkRepo::new(client.clone())),
+        Arc::new(ArtifactRepo::new(client.clone())),
+        Arc::new(ValidationRepo::new(client.clone())),
+        Arc::new(PublishingRepo::new(client.clone())),
+        Arc::new(PublisherRepo::new(client.clone())),
+        Arc::new(OwnershipRepo::new(client.clone())),
+        Arc::new(OutboxRepo::new(client.clone())),
+        Arc::new(ErrorRepo::new(client.clone())),
+        Arc::new(CacheRepo::new(Arc::new(RedisCache::new(redis)))),
+    );
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    let publisher = format!("0x{:040x}", nanos);
+    let asset = format!("0x{:040x}", nanos + 1);
+    let from_block = (1u64 << 60) + (nanos as u64 % 1_000_000) * 10;
+    let block = from_block + 1;
+
+    let batch = |publisher_name: &str| vec![
+        BlockLogData {
+            block_number: block,
+            data: LogResultData::NewPublisher(Some(NewPublisher {
+                address: publisher.clone(),
+                owner: publisher.clone(),
+                name: publisher_name.to_string(),
+                block_number: block as i64,
+            })),
+        },
+        BlockLogData {
+            block_number: block,
+            data: LogResultData::NewPublisherApp(Some(NewPublisherApp {
+                publisher_address: publisher.clone(),
+                asset_address: asset.clone(),
+                package_name: "com.crash.test".to_string(),
+                name: "Crash".to_string(),
+                block_number: block as i64,
+            }), None),
+        },
+        BlockLogData {
+            block_number: block,
+            data: LogResultData::FinishSync(
+                Some(AssetlinkSync { asset_address: asset.clone(), domain: "crash.test".to_string(), owner_version: 1, status: 1 }),
+                Some(ValidationProof { asset_address: asset.clone(), owner_version: 1, status: 1, fingerprints: vec![] }),
+            ),
+        },
+    ];
+    let blocks = vec![SyncBlock { block_number: block as i64, block_hash: format!("0x{:064x}", nanos) }];
+
+    async fn saved(conn: &mut PgConnection, publisher: &str, asset: &str, from_block: u64, block: u64) -> [i64; 7] {
+        let mut counts = [0; 7];
+        let queries = [
+            ("SELECT COUNT(*) FROM publisher WHERE address = $1", publisher.to_string()),
+            ("SELECT COUNT(*) FROM publisher_app WHERE asset_address = $1", asset.to_string()),
+            ("SELECT COUNT(*) FROM assetlink_sync WHERE asset_address = $1", asset.to_string()),
+            ("SELECT COUNT(*) FROM validation_proof WHERE asset_address = $1", asset.to_string()),
+            ("SELECT COUNT(*) FROM change_outbox WHERE asset_address = $1", asset.to_string()),
+            ("SELECT COUNT(*) FROM transactions_batch WHERE from_block_number = $1::BIGINT", from_block.to_string()),
+            ("SELECT COUNT(*) FROM sync_block WHERE block_number = $1::BIGINT", block.to_string()),
+        ];
+        for (i, (query, value)) in queries.into_iter().enumerate() {
+            counts[i] = sqlx::query_scalar::<_, i64>(query)
+                .bind(value)
+                .fetch_one(&mut *conn)
+                .await
+                .unwrap();
+        }
+
+        return counts;
+    }
+
+    let mut outer = client.start().await.unwrap();
+
+    // Crash after every write of the batch but before commit
+    let mut tx = outer.begin().await.unwrap();
+    handler.apply(&mut tx, &batch("Crash"), &None, &blocks, from_block, block + 1).await.unwrap();
+    drop(tx);
+    assert_eq!(saved(&mut outer, &publisher, &asset, from_block, block).await, [0; 7]);
+
+    // Publisher name is VARCHAR(255), the first write of the batch fails
+    let mut tx = outer.begin().await.unwrap();
+    assert!(handler.apply(&mut tx, &batch(&"x".repeat(300)), &None, &blocks, from_block, block + 1).await.is_err());
+    drop(tx);
+    assert_eq!(saved(&mut outer, &publisher, &asset, from_block, block).await, [0; 7]);
+
+    // Proof insert fails after the publisher rows are written
+    let mut broken = batch("Crash");
+    broken.push(BlockLogData {
+        block_number: block,
+        data: LogResultData::FinishSync(Some(AssetlinkSync { asset_address: asset.clone(), domain: "x".repeat(300), owner_version: 2, status: 1 }), None),
+    });
+    let mut tx = outer.begin().await.unwrap();
+    assert!(handler.apply(&mut tx, &broken, &None, &blocks, from_block, block + 1).await.is_err());
+    drop(tx);
+    assert_eq!(saved(&mut outer, &publisher, &asset, from_block, block).await, [0; 7]);
+
+    let mut tx = outer.begin().await.unwrap();
+    handler.apply(&mut tx, &batch("Crash"), &None, &blocks, from_block, block + 1).await.unwrap();
+    tx.commit().await.unwrap();
+    assert_eq!(saved(&mut outer, &publisher, &asset, from_block, block).await, [1; 7]);
+
+    outer.rollback().await.unwrap();
+}
+
+// Apps of a chain without a publisher factory are only seen through their builds
+#[tokio::test]
+#[ignore = "needs a migrated DATABASE_URL"]
+async fn check_asset_created_without_publisher_app() {
+    use crate::data::id::{CategoryId, ObjTypeId, PlatformId};
+    use crate::data::repo::cache_repo::CacheRepo;
+    use crate::env;
+    use db_redis::cache::RedisCache;
+    use db_redis::client::RedisClient;
+    use sqlx::Acquire;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let client = PgClient::connect(env::psql_url().as_ref()).await.unwrap();
+    // Nothing listens there, cache events are dropped with a warning
+    let redis = RedisClient::new("redis://127.0.0.1:1".to_string()).unwrap();
+
+    let handler = DataSyncHandler::new(
+        client.clone(),
+        Arc::new(ObjectRepo::new(client.clone())),
+        Arc::new(BatchRepo::new(client.clone())),
+        Arc::new(AssetlinkRepo::new(client.clone())),
+        Arc::new(ArtifactRepo::new(client.clone())),
+        Arc::new(ValidationRepo::new(client.clone())),
+        Arc::new(PublishingRepo::new(client.clone())),
+        Arc::new(PublisherRepo::new(client.clone())),
+        Arc::new(OwnershipRepo::new(client.clone())),
+        Arc::new(OutboxRepo::new(client.clone())),
+        Arc::new(ErrorRepo::new(client.clone())),
+        Arc::new(CacheRepo::new(Arc::new(RedisCache::new(redis)))),
+    );
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    let block = (1u64 << 60) + (nanos as u64 % 1_000_000) * 10;
+    let asset = |address: String| NewAsset {
+        name: "Finalized".to_string(),
+        id: "com.finalized.test".to_string(),
+        address,
+        logo: None,
+        description: None,
+        type_id: ObjTypeId::App,
+        category_id: CategoryId::Business,
+        platform_id: PlatformId::Android,
+        is_os_verified: false,
+        is_hidden: false,
+        price: 0,
+    };
+    let finalized = format!("0x{:040x}", nanos);
+    let requested = format!("0x{:040x}", nanos + 1);
+
+    let batch = vec![
+        BlockLogData {
+            block_number: block,
+            data: LogResultData::BlockFinalized(vec![], vec![], vec![], vec![asset(finalized.clone())], vec![]),
+        },
+        BlockLogData {
+            block_number: block,
+            data: LogResultData::NewRequest(None, None, Some(asset(requested.clone())), None),
+        },
+        // Updates of known rows and a late publisher app don't repeat the event
+        BlockLogData {
+            block_number: block + 1,
+            data: LogResultData::BlockFinalized(vec![], vec![], vec![], vec![asset(finalized.clone()), asset(requested.clone())], vec![]),
+        },
+        BlockLogData {
+            block_number: block + 1,
+            data: LogResultData::NewPublisherApp(Some(NewPublisherApp {
+                publisher_address: format!("0x{:040x}", nanos + 2),
+                asset_address: finalized.clone(),
+                package_name: "com.finalized.test".to_string(),
+                name: "Finalized".to_string(),
+                block_number: block as i64 + 1,
+            }), None),
+        },
+    ];
+
+    let mut outer = client.start().await.unwrap();
+    let mut tx = outer.begin().await.unwrap();
+    handler.apply_logs(&mut tx, &batch).await.unwrap();
+
+    for address in [&finalized, &requested] {
+        let event_ids = sqlx::query_scalar::<_, String>("SELECT event_id FROM change_outbox WHERE asset_address = $1")
+            .bind(address)
+            .fetch_all(&mut *tx)
+            .await
+            .unwrap();
+        assert_eq!(event_ids, vec![format!("asset_created:{}", address)]);
+    }
+
+    drop(tx);
+    outer.rollback().await.unwrap();
+}
+
+// A log retried after a later block was synced must not bring back its older result
+#[tokio::test]
+#[ignore = "needs a migrated DATABASE_URL"]
+async fn check_retried_log_keeps_newer_state() {
+    use crate::data::id::{ReqTypeId, TrackId};
+    use crate::data::repo::cache_repo::CacheRepo;
+    use crate::env;
+    use db_redis::cache::RedisCache;
+    use db_redis::client::RedisClient;
+    use sqlx::Acquire;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let client = PgClient::connect(env::psql_url().as_ref()).await.unwrap();
+    // Nothing listens there, cache events are dropped with a warning
+    let redis = RedisClient::new("redis://127.0.0.1:1".to_string()).unwrap();
+
+    let handler = DataSyncHandler::new(
+        client.clone(),
+        Arc::new(ObjectRepo::new(client.clone())),
+        Arc::new(BatchRepo::new(client.clone())),
+        Arc::new(AssetlinkRepo::new(client.clone())),
+        Arc::new(ArtifactRepo::new(client.clone())),
+        Arc::new(ValidationRepo::new(client.clone())),
+        Arc::new(PublishingRepo::new(client.clone())),
+        Arc::new(PublisherRepo::new(client.clone())),
+        Arc::new(OwnershipRepo::new(client.clone())),
+        Arc::new(OutboxRepo::new(client.clone())),
+        Arc::new(ErrorRepo::new(client.clone())),
+        Arc::new(CacheRepo::new(Arc::new(RedisCache::new(redis)))),
+    );
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    let asset = format!("0x{:040x}", nanos);
+    let request_id = (1i64 << 60) + (nanos % 1_000_000_000) as i64;
+    let block = (1u64 << 60) + (nanos as u64 % 1_000_000) * 10;
+    let success = ApkValidationStatus::Success.code() as i32;
+
+    // The same request finalized in two validator blocks, each one publishing another version
+    let finalized = |block_id: i64, status: i32, version_code: i64| LogResultData::BlockFinalized(
+        vec![NewBuildRequest {
+            id: request_id,
+            request_type_id: ReqTypeId::AndroidBuild,
+            asset_address: asset.clone(),
+            track_id: TrackId::Release,
+            status: Some(status),
+            version_code: 1,
+            owner_version: 1,
+            created_at: None,
+            block_id: Some(block_id),
+            finalized_at: None,
+        }],
+        vec![RequestValidation {
+            request_id,
+            asset_address: asset.clone(),
+            version_code: 1,
+            owner_version: 1,
+            status,
+            file_hash: None,
+        }],
+        vec![],
+        vec![],
+        vec![Publishing { asset_address: asset.clone(), track_id: TrackId::Release, version_code, is_active: true }],
+    );
+
+    let mut outer = client.start().await.unwrap();
+    let mut tx = outer.begin().await.unwrap();
+    handler.apply_logs(&mut tx, &vec![BlockLogData { block_number: block + 2, data: finalized(20, success, 2) }]).await.unwrap();
+    // Retried log of the earlier block
+    handler.apply_logs(&mut tx, &vec![BlockLogData { block_number: block + 1, data: finalized(10, success + 1, 1) }]).await.unwrap();
+
+    let (status, block_id) = sqlx::query_as::<_, (Option<i32>, Option<i64>)>("SELECT status, block_id FROM build_request WHERE id = $1")
+        .bind(request_id)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!((status, block_id), (Some(success), Some(20)));
+
+    let validation = sqlx::query_scalar::<_, i32>("SELECT status FROM validation_proof WHERE request_id = $1")
+        .bind(request_id)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(validation, success);
+
+    let version_code = sqlx::query_scalar::<_, i64>("SELECT version_code FROM publishing WHERE asset_address = $1 AND track_id = 1")
+        .bind(&asset)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(version_code, 2);
+
+    drop(tx);
+    outer.rollback().await.unwrap();
 }
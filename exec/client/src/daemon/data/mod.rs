@@ -1,4 +1,5 @@
 
 pub mod stat_repo;
+pub mod trending;
 pub mod object_factory;
 pub mod data_sync;
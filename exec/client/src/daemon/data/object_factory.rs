@@ -1,5 +1,6 @@
 use crate::data::id::{CategoryId, PlatformId, ReqTypeId, TrackId};
 use crate::data::models::{NewArtifact, NewAsset, NewBuildRequest, Publishing};
+use crate::result::ClientError;
 use alloy::hex::ToHexExt;
 use alloy::primitives::Address;
 use chrono::DateTime;
@@ -22,6 +23,15 @@ pub enum DaemonError {
     
     #[error("Gf error: {0}")]
     Gf(#[from] GfError),
+
+    #[error("Database error: {0}")]
+    Sqlx(#[from] sqlx::Error),
+
+    #[error("ClickHouse error: {0}")]
+    ClickHouse(#[from] clickhouse::error::Error),
+
+    #[error("Client error: {0}")]
+    Client(#[from] ClientError),
 }
 
 pub struct ObjectFactory {
@@ -74,7 +84,9 @@ impl ObjectFactory {
             status: Some(ApkValidationStatus::Success.code() as i32),
             version_code,
             owner_version,
-            created_at: time
+            created_at: time,
+            block_id: None,
+            finalized_at: None,
         };
 
         return build_request;
@@ -104,7 +116,9 @@ impl ObjectFactory {
             status,
             version_code,
             owner_version,
-            created_at: time
+            created_at: time,
+            block_id: None,
+            finalized_at: None,
         };
         
         return build_request;
@@ -1,34 +1,84 @@
-use chrono::{DateTime, Utc};
+use crate::daemon::data::object_factory::DaemonResult;
+use crate::daemon::data::trending::{TrendingCounts, TrendingScore, BASELINE_DAYS, LONG_WINDOW_DAYS, SHORT_WINDOW_DAYS};
+use chrono::{DateTime, TimeDelta, Utc};
 use clickhouse::Row;
 use db_ch::client::ChClient;
 use db_psql::client::{PgClient, SqlxResult};
 use serde::Deserialize;
+use sqlx::{Postgres, Transaction};
+use std::sync::Arc;
 use tracing::instrument;
 
-#[derive(Row, Deserialize, Debug, Clone)]
+const DOWNLOADS_CHECKPOINT: &str = "downloads_recount";
+
+#[derive(Row, Deserialize, Debug, Clone, PartialEq)]
 pub struct ObjectSummary {
-    object_id: i64,
-    install_count: u64,
+    pub object_id: i64,
+    pub download_count: u64,
+    pub install_count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecountResult {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub updated: u64,
 }
 
 pub struct StatSyncHandler {
-    ch_client: ChClient,
+    ch_client: Arc<ChClient>,
     pg_client: PgClient,
 }
 
 impl StatSyncHandler {
-    
-    pub async fn get_summary_since(
+
+    pub fn new(ch_client: Arc<ChClient>, pg_client: PgClient) -> Self {
+        Self { ch_client, pg_client }
+    }
+
+    // Applies events of (checkpoint, to] and moves the checkpoint in one transaction,
+    // so a crash or a concurrent daemon can't count the same window twice
+    pub async fn recount_downloads(&self, to: DateTime<Utc>) -> DaemonResult<Option<RecountResult>> {
+        let mut tx = self.pg_client.start()
+            .await?;
+
+        let from = self.lock_checkpoint(&mut tx, DOWNLOADS_CHECKPOINT)
+            .await?;
+
+        if to <= from {
+            return Ok(None);
+        }
+
+        let summaries = self.get_summary_between(from, to)
+            .await?;
+
+        let updated = self.increment_downloads(&mut tx, &summaries)
+            .await?;
+
+        self.save_checkpoint(&mut tx, DOWNLOADS_CHECKPOINT, to)
+            .await?;
+
+        tx.commit()
+            .await?;
+
+        return Ok(Some(RecountResult { from, to, updated }));
+    }
+
+    pub async fn get_summary_between(
         &self,
-        last_event_time: DateTime<Utc>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
     ) -> clickhouse::error::Result<Vec<ObjectSummary>> {
         let query = "
             SELECT
                 object_id,
-                count(*) AS install_count
+                countIf(event_name = 'ObjectDownloaded') AS download_count,
+                countIf(event_name = 'ObjectInstalled') AS install_count
             FROM default.stat_events
             WHERE
-                event_name = 'ObjectInstalled' AND event_time > ?
+                event_name IN ('ObjectDownloaded', 'ObjectInstalled')
+                AND event_time > fromUnixTimestamp64Milli(?)
+                AND event_time <= fromUnixTimestamp64Milli(?)
             GROUP BY object_id
             ORDER BY object_id
         ";
@@ -36,11 +86,12 @@ impl StatSyncHandler {
         let mut cursor = self.ch_client
             .client
             .query(query)
-            .bind(last_event_time)
+            .bind(from.timestamp_millis())
+            .bind(to.timestamp_millis())
             .fetch::<ObjectSummary>()?;
 
         let mut results = Vec::new();
-        
+
         while let Some(summary) = cursor.next().await? {
             results.push(summary);
         }
@@ -48,26 +99,151 @@ impl StatSyncHandler {
         Ok(results)
     }
 
-    #[instrument(skip(self, updates), fields(count = updates.len()))]
+    pub async fn get_trending_counts(&self, to: DateTime<Utc>) -> clickhouse::error::Result<Vec<TrendingCounts>> {
+        let query = "
+            SELECT
+                object_id,
+                countIf(event_time > fromUnixTimestamp64Milli(?)) AS downloads_24h,
+                countIf(event_time > fromUnixTimestamp64Milli(?)) AS downloads_7d,
+                countIf(event_time <= fromUnixTimestamp64Milli(?)) AS baseline
+            FROM default.stat_events
+            WHERE
+                event_name = 'ObjectDownloaded'
+                AND event_time > fromUnixTimestamp64Milli(?)
+                AND event_time <= fromUnixTimestamp64Milli(?)
+            GROUP BY object_id
+            HAVING downloads_7d > 0
+        ";
+
+        let short_from = to - TimeDelta::days(SHORT_WINDOW_DAYS);
+        let long_from = to - TimeDelta::days(LONG_WINDOW_DAYS);
+        let baseline_from = long_from - TimeDelta::days(BASELINE_DAYS);
+
+        let mut cursor = self.ch_client
+            .client
+            .query(query)
+            .bind(short_from.timestamp_millis())
+            .bind(long_from.timestamp_millis())
+            .bind(long_from.timestamp_millis())
+            .bind(baseline_from.timestamp_millis())
+            .bind(to.timestamp_millis())
+            .fetch::<TrendingCounts>()?;
+
+        let mut results = Vec::new();
+
+        while let Some(counts) = cursor.next().await? {
+            results.push(counts);
+        }
+
+        Ok(results)
+    }
+
+    // Scores of the previous run are dropped, assets without recent downloads fall out of trending
+    #[instrument(skip(self, scores), fields(count = scores.len()))]
+    pub async fn replace_trending_scores(&self, scores: &[TrendingScore]) -> SqlxResult<u64> {
+        let ids: Vec<i64> = scores.iter().map(|score| score.asset_id).collect();
+        let values: Vec<f32> = scores.iter().map(|score| score.score).collect();
+        let downloads_24h: Vec<i64> = scores.iter().map(|score| score.downloads_24h).collect();
+        let downloads_7d: Vec<i64> = scores.iter().map(|score| score.downloads_7d).collect();
+        let baseline_daily: Vec<f32> = scores.iter().map(|score| score.baseline_daily).collect();
+
+        let mut tx = self.pg_client.start()
+            .await?;
+
+        sqlx::query!("DELETE FROM trending_score")
+            .execute(&mut *tx)
+            .await?;
+
+        // Stat events may reference deleted assets
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO trending_score (asset_id, score, downloads_24h, downloads_7d, baseline_daily)
+            SELECT u.asset_id, u.score, u.downloads_24h, u.downloads_7d, u.baseline_daily
+            FROM UNNEST($1::BIGINT[], $2::REAL[], $3::BIGINT[], $4::BIGINT[], $5::REAL[])
+                AS u(asset_id, score, downloads_24h, downloads_7d, baseline_daily)
+            INNER JOIN obj ON obj.id = u.asset_id
+            "#,
+            &ids,
+            &values,
+            &downloads_24h,
+            &downloads_7d,
+            &baseline_daily,
+        )
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit()
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip(self, tx, updates), fields(count = updates.len()))]
     pub async fn increment_downloads(
         &self,
-        updates: Vec<ObjectSummary>,
+        tx: &mut Transaction<'static, Postgres>,
+        updates: &[ObjectSummary],
+    ) -> SqlxResult<u64> {
+        if updates.is_empty() {
+            return Ok(0);
+        }
+
+        let ids: Vec<i64> = updates.iter().map(|update| update.object_id).collect();
+        let downloads: Vec<i64> = updates.iter().map(|update| update.download_count as i64).collect();
+        let installs: Vec<i64> = updates.iter().map(|update| update.install_count as i64).collect();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE obj SET
+                downloads = obj.downloads + u.downloads,
+                installs = obj.installs + u.installs
+            FROM UNNEST($1::BIGINT[], $2::BIGINT[], $3::BIGINT[]) AS u(id, downloads, installs)
+            WHERE obj.id = u.id
+            "#,
+            &ids,
+            &downloads,
+            &installs,
+        )
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn lock_checkpoint(
+        &self,
+        tx: &mut Transaction<'static, Postgres>,
+        name: &str,
+    ) -> SqlxResult<DateTime<Utc>> {
+        let result = sqlx::query_scalar!(
+            "SELECT event_time FROM stat_checkpoint WHERE name = $1 FOR UPDATE",
+            name
+        )
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        Ok(result.unwrap_or(DateTime::UNIX_EPOCH))
+    }
+
+    async fn save_checkpoint(
+        &self,
+        tx: &mut Transaction<'static, Postgres>,
+        name: &str,
+        event_time: DateTime<Utc>,
     ) -> SqlxResult<()> {
-        // let mut tx = self.pg_client.start()
-        //     .await?;
-        // 
-        // for update in updates {
-        //     sqlx::query!(
-        //         "UPDATE obj SET downloads = downloads + $1 WHERE id = $2",
-        //         update.install_count as i64, // Cast u64 to i64 for SQL query parameter
-        //         update.object_id
-        //     )
-        //         .execute(&mut *tx)
-        //         .await?;
-        // }
-        // 
-        // tx.commit()
-        //     .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO stat_checkpoint (name, event_time)
+            VALUES ($1, $2)
+            ON CONFLICT (name) DO UPDATE SET
+                event_time = EXCLUDED.event_time,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+            name,
+            event_time
+        )
+            .execute(&mut **tx)
+            .await?;
 
         Ok(())
     }
@@ -0,0 +1,71 @@
+use clickhouse::Row;
+use serde::Deserialize;
+
+pub const SHORT_WINDOW_DAYS: i64 = 1;
+pub const LONG_WINDOW_DAYS: i64 = 7;
+// Period right before the long window, its daily average is what growth is measured against
+pub const BASELINE_DAYS: i64 = 28;
+
+// Pseudo downloads per day added to both sides of the ratio, so 1 -> 3 downloads isn't a 3x growth
+const DAMPENING: f64 = 10.0;
+const SHORT_WEIGHT: f64 = 0.6;
+const LONG_WEIGHT: f64 = 0.4;
+
+#[derive(Row, Deserialize, Debug, Clone, PartialEq)]
+pub struct TrendingCounts {
+    pub object_id: i64,
+    pub downloads_24h: u64,
+    pub downloads_7d: u64,
+    pub baseline: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendingScore {
+    pub asset_id: i64,
+    pub score: f32,
+    pub downloads_24h: i64,
+    pub downloads_7d: i64,
+    pub baseline_daily: f32,
+}
+
+impl TrendingScore {
+
+    // Weighted growth of daily rates over the baseline, scaled by log volume so a big jump
+    // of a handful of downloads stays below a steady growth of a popular app
+    pub fn from_counts(counts: &TrendingCounts) -> Self {
+        let baseline_daily = counts.baseline as f64 / BASELINE_DAYS as f64;
+        let short_daily = counts.downloads_24h as f64 / SHORT_WINDOW_DAYS as f64;
+        let long_daily = counts.downloads_7d as f64 / LONG_WINDOW_DAYS as f64;
+
+        let short_growth = (short_daily + DAMPENING) / (baseline_daily + DAMPENING);
+        let long_growth = (long_daily + DAMPENING) / (baseline_daily + DAMPENING);
+        let growth = SHORT_WEIGHT * short_growth + LONG_WEIGHT * long_growth;
+
+        let score = growth * (counts.downloads_7d as f64).ln_1p();
+
+        return TrendingScore {
+            asset_id: counts.object_id,
+            score: score as f32,
+            downloads_24h: counts.downloads_24h as i64,
+            downloads_7d: counts.downloads_7d as i64,
+            baseline_daily: baseline_daily as f32,
+        };
+    }
+}
+
+#[test]
+fn check_trending_score() {
+    let score = |downloads_24h: u64, downloads_7d: u64, baseline: u64| TrendingScore::from_counts(
+        &TrendingCounts { object_id: 1, downloads_24h, downloads_7d, baseline }
+    ).score;
+
+    let incumbent = score(1_000, 7_000, 28_000);
+    let rising = score(100, 300, 0);
+    let tiny = score(2, 3, 0);
+
+    assert!(rising > incumbent);
+    assert!(incumbent > tiny);
+    assert!(score(300, 1_000, 0) > rising);
+    assert!(score(1_000, 7_000, 2_800) > incumbent);
+    assert_eq!(score(0, 0, 1_000), 0.0);
+}
@@ -1,21 +1,23 @@
-use crate::daemon::data::data_sync::{DataSyncHandler, LogResultData};
-use crate::daemon::handler::sync::add_to_track::AddToTrack;
-use crate::daemon::handler::sync::block_finalized::BlockFinalizedHandler;
-use crate::daemon::handler::sync::new_req::NewRequestHandler;
+use crate::daemon::data::data_sync::{BlockLogData, DataSyncHandler, LogResultData};
+use crate::daemon::handler::log_fetcher::LogFetcher;
+use crate::daemon::handler::sync::add_to_track_v0::AddToTrackHandlerV0;
+use crate::daemon::handler::sync::block_finalized_v0::BlockFinalizedHandlerV0;
 use crate::daemon::handler::sync::new_req_v0::NewRequestHandlerV0;
-use crate::daemon::handler::sync::sync_finish::SyncFinishedHandler;
+use crate::daemon::handler::sync::ownership_v0::OwnershipHandlerV0;
+use crate::daemon::handler::sync::publisher_v0::PublisherHandlerV0;
 use crate::daemon::handler::sync::sync_finish_v0::SyncFinishedHandlerV0;
 use crate::daemon::launcher::{DaemonAction, DaemonContex};
 use crate::data::id::{CategoryId, PlatformId};
 use crate::data::models::{AssetlinkSync, NewArtifact, NewAsset, NewBuildRequest, Publishing};
 use crate::data::repo::assetlink_repo::AssetlinkRepo;
-use crate::data::repo::batch_repo::{BatchRepo, TransactionBatch, TransactionStatus};
+use crate::data::repo::batch_repo::{BatchRepo, SyncBlock, TransactionBatch, TransactionStatus};
 use crate::data::repo::object_repo::ObjectRepo;
 use crate::env;
 use crate::result::{ClientError, ClientResult};
 use alloy::hex::ToHexExt;
-use alloy::primitives::{Address, B256};
+use alloy::primitives::Address;
 use alloy::providers::Provider;
+use alloy::rpc::types::BlockNumberOrTag;
 use alloy::rpc::types::{Filter, Log};
 use client_tg::{tg_alert, tg_msg};
 use codegen_contracts::ext::ToChecksum;
@@ -24,12 +26,14 @@ use net_client::node::result::EthError;
 use net_client::node::watcher::TxWorkaround;
 use client_ethscan::client::EthScanClient;
 use client_ethscan::error::EthScanError;
-use client_ethscan::models::{GetLogsParams, LogsResponse};
+use client_ethscan::models::LogsResponse;
 use service_graph::client::GraphClient;
 use service_sc::assetlinks::ScAssetLinkService;
+use service_sc::obj::ScObjService;
+use service_sc::publisher::ScPublisherService;
 use service_sc::store::ScStoreService;
 use std::cmp::max;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -38,42 +42,61 @@ use service_event::service::EventLogService;
 
 pub struct ChainSyncHandlerV0 {
     store_created_block: u64,
+    confirmations: u64,
+    reorg_depth: u64,
     retry_timeout: Duration,
     empty_timeout: Duration,
-    page_timeout: Duration,
+    publisher_factory: Option<Address>,
     eth: Arc<Web3Provider>,
-    log_client: Arc<EventLogService>,
+    logs: LogFetcher,
 	graph: Arc<GraphClient>,
     data_sync: Arc<DataSyncHandler>,
     sync_finished: Arc<SyncFinishedHandlerV0>,
     new_request: Arc<NewRequestHandlerV0>,
+    add_to_track: Arc<AddToTrackHandlerV0>,
+    block_finalized: Arc<BlockFinalizedHandlerV0>,
+    publisher: Arc<PublisherHandlerV0>,
+    ownership: Arc<OwnershipHandlerV0>,
 }
 
 impl ChainSyncHandlerV0 {
 
     pub fn new(
         store_created_block: u64,
+        confirmations: u64,
+        reorg_depth: u64,
         retry_timeout: Duration,
         empty_timeout: Duration,
+        publisher_factory: Option<Address>,
         eth: Arc<Web3Provider>,
         log_client: Arc<EventLogService>,
         graph: Arc<GraphClient>,
         data_sync: Arc<DataSyncHandler>,
         sync_finished: Arc<SyncFinishedHandlerV0>,
         new_request: Arc<NewRequestHandlerV0>,
+        add_to_track: Arc<AddToTrackHandlerV0>,
+        block_finalized: Arc<BlockFinalizedHandlerV0>,
+        publisher: Arc<PublisherHandlerV0>,
+        ownership: Arc<OwnershipHandlerV0>,
     ) -> Self {
 		Self {
             store_created_block,
+            confirmations,
+            reorg_depth,
             retry_timeout,
             empty_timeout,
+            publisher_factory,
             eth,
-            log_client,
+            logs: LogFetcher::new(log_client, retry_timeout, Duration::from_millis(1_000)), // TODO page timeout to config
             graph,
             data_sync,
             sync_finished,
             new_request,
-            page_timeout: Duration::from_millis(1_000)
-        } // TODO page_timeout to config
+            add_to_track,
+            block_finalized,
+            publisher,
+            ownership,
+        }
     }
 
     pub async fn handle(&self, ctx: Arc<DaemonContex>) {
@@ -100,37 +123,9 @@ impl ChainSyncHandlerV0 {
             }
         };
 
-        let offset = env::max_logs_per_request();
-
-        // Sync EtherScan events
-        let assetlink_address = env::assetlink_address();
-        let mut assetlink_params = GetLogsParams {
-            from_block: 0,
-            to_block: None,
-            address: Some(assetlink_address.checksum()),
-            offset: Some(offset),
-
-            topic0: Some(ScAssetLinkService::SYNC_FINISH_HASH.encode_hex_with_prefix()),
-            page: None,
-        };
-
-        // Sync Open Store events
-        let openstore_address = env::openstore_address();
-        let mut openstore_params = GetLogsParams {
-            from_block: 0,
-            to_block: None,
-            address: Some(openstore_address.checksum()),
-            offset: Some(offset),
-
-            topic0: Some(ScStoreService::NEW_REQUEST_HASH.encode_hex_with_prefix()),
-            page: None,
-        };
-
         let mut from_block = next_block_number;
-        let mut page = 1u32;
 
         // TODO max size within sync
-        let mut logs = Vec::with_capacity(64);
         let mut new_data = Vec::with_capacity(64);
 
         // Main loop - always use RPC for real-time syncing
@@ -145,89 +140,89 @@ impl ChainSyncHandlerV0 {
                 from_block
             );
 
-            let last_block_number = self.eth.get_block_number().await
+            let head_block_number = self.eth.get_block_number().await
                 .unwrap_or_else(|e| {
                     warn!("[DAEMON_SYNC] Can't sync last block number {e}");
                     from_block
                 });
 
+            // Blocks within the confirmation depth can still be reorged
+            let last_block_number = head_block_number.saturating_sub(self.confirmations);
+
             if last_block_number < from_block {
-                info!("[DAEMON_SYNC] No new blocks found, sleeping for 1 sec");
+                info!("[DAEMON_SYNC] No new confirmed blocks found, sleeping for 1 sec");
                 sleep(self.empty_timeout).await;
                 continue;
             }
 
-            assetlink_params.from_block = from_block;
-            assetlink_params.to_block = Some(last_block_number);
-            
-            openstore_params.from_block = from_block;
-            openstore_params.to_block = Some(last_block_number);
-
-            page = 1;
-            loop {
-                assetlink_params.page = Some(page);
-
-                info!("[DAEMON_SYNC] Fetching ASSETS logs (with topic) page {} (offset: {})", page, offset);
-                let response = match self.log_client.get_logs(&assetlink_params).await {
-                    Ok(response) => response,
-                    Err(err) => {
-                        error!("[DAEMON_SYNC] Error getting ASSETS logs: {}", err);
-                        sleep(self.retry_timeout).await;
-                        continue;
+            match self.find_rollback_point().await {
+                Ok(None) => {}
+                Ok(Some(block)) => {
+                    warn!("[DAEMON_SYNC] Reorg detected, rolling back to block {}", block);
+                    tg_alert!(format!("[DAEMON_SYNC] Reorg detected, rolling back to block {}", block));
+
+                    match self.data_sync.rollback(block).await {
+                        Ok(()) => from_block = block,
+                        Err(e) => {
+                            error!("[DAEMON_SYNC] Rollback to block {} failed: {}", block, e);
+                            sleep(self.retry_timeout).await;
+                        }
                     }
-                };
 
-                let results_count = response.result.len();
-                info!("[DAEMON_SYNC] Got {} results for ASSETS page {}", results_count, page);
+                    continue;
+                }
+                Err(e) => {
+                    error!("[DAEMON_SYNC] Can't check synced blocks for reorg: {}", e);
+                    sleep(self.retry_timeout).await;
+                    continue;
+                }
+            }
 
-                // Use block numbers from EthScan to fetch actual logs via RPC
-                logs.extend(response.result);
+            let last_block_hash = match self.block_hash(last_block_number).await {
+                Ok(Some(hash)) => hash,
+                Ok(None) => {
+                    warn!("[DAEMON_SYNC] Block {} not found", last_block_number);
+                    sleep(self.retry_timeout).await;
+                    continue;
+                }
+                Err(e) => {
+                    error!("[DAEMON_SYNC] Can't get hash of block {}: {}", last_block_number, e);
+                    sleep(self.retry_timeout).await;
+                    continue;
+                }
+            };
 
-                if (results_count as u32) < offset {
-                    info!("[DAEMON_SYNC] Reached end of results for ASSETS (got {} < {})", results_count, offset);
-                    break;
+            let logs = match self.fetch_range(from_block, last_block_number).await {
+                Ok(logs) => logs,
+                Err(e) => {
+                    error!("[DAEMON_SYNC] Can't fetch logs from {} to {}: {}", from_block, last_block_number, e);
+                    sleep(self.retry_timeout).await;
+                    continue;
                 }
+            };
 
-                page += 1;
-                sleep(self.page_timeout).await;
-            }
+            let mut block_hashes = BTreeMap::new();
+            block_hashes.insert(last_block_number, last_block_hash);
 
-            page = 1;
-            loop {
-                openstore_params.page = Some(page);
-
-                info!("[DAEMON_SYNC] Fetching OPENSTORE logs (with topic) page {} (offset: {})", page, offset);
-                let response = match self.log_client.get_logs(&openstore_params).await {
-                    Ok(response) => response,
-                    Err(err) => {
-                        error!("[DAEMON_SYNC] Error getting OPENSTORE logs: {}", err);
-                        sleep(self.retry_timeout).await;
-                        continue;
-                    }
+            for log in logs.iter() {
+                let Some(block_number) = log.block_number else {
+                    warn!("[DAEMON_SYNC] Skip log without block number: {:?}", log.transaction_hash);
+                    continue;
                 };
 
-                let results_count = response.result.len();
-                info!("[DAEMON_SYNC] Got {} results for OPENSTORE page {}", results_count, page);
-
-                // Use block numbers from EthScan to fetch actual logs via RPC
-                logs.extend(response.result);
-
-                if (results_count as u32) < offset {
-                    info!("[DAEMON_SYNC] Reached end of results for OPENSTORE (got {} < {})", results_count, offset);
-                    break;
+                if let Some(block_hash) = log.block_hash {
+                    block_hashes.entry(block_number)
+                        .or_insert_with(|| block_hash.encode_hex_with_prefix());
                 }
 
-                page += 1;
-                sleep(self.page_timeout).await;
-            }
-
-            for log in logs.iter() {
-                if let Some(result) = self.handle_log(log).await {
-                    new_data.push(result);
+                if let Some(data) = self.handle_log(log).await {
+                    new_data.push(BlockLogData { block_number, data });
                 }
             }
 
-            logs.clear();
+            let blocks = block_hashes.into_iter()
+                .map(|(block_number, block_hash)| SyncBlock { block_number: block_number as i64, block_hash })
+                .collect::<Vec<SyncBlock>>();
 
             info!("[DAEMON_SYNC] Fetching updated AppAssets since block {}", from_block);
             let apps = match self.graph.fetch_app_assets_since(from_block).await {
@@ -240,10 +235,19 @@ impl ChainSyncHandlerV0 {
 
 
             let next_block = last_block_number + 1;
-            self.data_sync.sync(&new_data, &apps, from_block, next_block)
+            let synced = self.data_sync.sync(&new_data, &apps, &blocks, from_block, next_block)
                 .await;
 
             new_data.clear();
+
+            // Nothing of the batch is saved, the same range is fetched again
+            if let Err(e) = synced {
+                error!("[DAEMON_SYNC] Batch from {} to {} failed, retry in {:?}: {}", from_block, next_block, self.retry_timeout, e);
+                tg_alert!(format!("[DAEMON_SYNC] Batch from {} to {} failed: {}", from_block, next_block, e));
+                sleep(self.retry_timeout).await;
+                continue;
+            }
+
             from_block = next_block;
 
             info!("[DAEMON_SYNC] Events synced, next block: {}", from_block);
@@ -251,7 +255,124 @@ impl ChainSyncHandlerV0 {
         }
     }
     
-    async fn handle_log(&self, item: &Log) -> Option<LogResultData> {
+    // Walks synced hashes from the head down to the last one still on chain,
+    // `None` while the sync head is canonical
+    async fn find_rollback_point(&self) -> ClientResult<Option<u64>> {
+        let blocks = self.data_sync.synced_blocks(self.reorg_depth)
+            .await?;
+
+        let (Some(head), Some(deepest)) = (blocks.first(), blocks.last()) else {
+            return Ok(None);
+        };
+
+        let mut canonical = None;
+        for block in blocks.iter() {
+            let hash = self.block_hash(block.block_number as u64)
+                .await?;
+
+            if hash.as_ref() == Some(&block.block_hash) {
+                canonical = Some(block.block_number as u64);
+                break;
+            }
+        }
+
+        let canonical = match canonical {
+            Some(canonical) if canonical == head.block_number as u64 => return Ok(None),
+            Some(canonical) => canonical,
+            None => {
+                error!("[DAEMON_SYNC] Reorg is deeper than {} synced blocks", blocks.len());
+                tg_alert!(format!("[DAEMON_SYNC] Reorg is deeper than {} synced blocks", blocks.len()));
+                (deepest.block_number as u64).saturating_sub(1)
+            }
+        };
+
+        let block = self.data_sync.rollback_point(canonical)
+            .await?
+            .unwrap_or(self.store_created_block);
+
+        return Ok(Some(block));
+    }
+
+    async fn block_hash(&self, block_number: u64) -> ClientResult<Option<String>> {
+        let block = self.eth.get_block_by_number(BlockNumberOrTag::Number(block_number))
+            .await
+            .map_err(EthError::from)?;
+
+        return Ok(block.map(|block| block.header.hash.encode_hex_with_prefix()));
+    }
+
+    // Logs of every synced event in [from_block, to_block], in chain order
+    pub async fn fetch_range(&self, from_block: u64, to_block: u64) -> ClientResult<Vec<Log>> {
+        let openstore_address = env::openstore_address().checksum();
+
+        let sources = [
+            // Sync EtherScan events
+            ("ASSETS", Some(env::assetlink_address().checksum()), ScAssetLinkService::SYNC_FINISH_HASH),
+            // Sync Open Store events
+            ("OPENSTORE", Some(openstore_address.clone()), ScStoreService::NEW_REQUEST_HASH),
+            // Track membership, published builds move between tracks with `AddedToTrack`
+            ("TRACKS", Some(openstore_address.clone()), ScStoreService::ADDED_TO_TRACK_HASH),
+            // Validation results, a finalized block carries the status of every request it covers
+            ("BLOCKS", Some(openstore_address), ScStoreService::BLOCK_FINALIZED_HASH),
+        ];
+
+        let mut logs = Vec::with_capacity(64);
+        for (name, address, topic0) in sources {
+            self.logs.fetch_source(name, from_block, to_block, address, topic0, &mut logs).await;
+        }
+
+        if let Some(factory) = self.publisher_factory {
+            // Publisher accounts are deployed by the factory
+            let mut accounts = Vec::new();
+            self.logs.fetch_source("PUBLISHERS", from_block, to_block, Some(factory.checksum()), ScPublisherService::ACCOUNT_CREATED_HASH, &mut accounts).await;
+
+            // Apps are created by publisher accounts, accounts deployed within the range are queried too
+            let mut publishers = self.data_sync.known_publishers()
+                .await?
+                .into_iter()
+                .collect::<BTreeSet<String>>();
+            publishers.extend(
+                accounts.iter()
+                    .filter_map(|log| ScPublisherService::decode_account_created(log.as_ref()).ok())
+                    .map(|log| log.data.account.checksum())
+            );
+            logs.extend(accounts);
+
+            self.logs.fetch_addresses("PUBLISHER_APPS", from_block, to_block, publishers, ScPublisherService::APP_CREATED_HASH, &mut logs).await;
+        }
+
+        // `OwnershipTransferred` is the generic Ownable event, so only logs of known apps are queried,
+        // apps created or first requested within the range included
+        let mut apps = self.data_sync.known_apps()
+            .await?
+            .into_iter()
+            .collect::<BTreeSet<String>>();
+        for log in logs.iter() {
+            match log.topic0() {
+                Some(&ScPublisherService::APP_CREATED_HASH) => {
+                    if let Ok(created) = ScPublisherService::decode_app_created(log.as_ref()) {
+                        apps.insert(created.data.appAddress.checksum());
+                    }
+                }
+                Some(&ScStoreService::NEW_REQUEST_HASH) => {
+                    if let Ok(request) = ScStoreService::decode_new_request(log.as_ref()) {
+                        apps.insert(request.data.target.checksum());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.logs.fetch_addresses("OWNERSHIP", from_block, to_block, apps, ScObjService::OWNERSHIP_TRANSFERRED_HASH, &mut logs).await;
+
+        // Queries are per event type, so a track change must not be applied before its request
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+        return Ok(logs);
+    }
+
+    // Also replays stored failed logs, handlers record a repeated failure themselves
+    pub async fn handle_log(&self, item: &Log) -> Option<LogResultData> {
         let topic0 = match item.topic0() {
             Some(topic0) => topic0.clone(),
             None => {
@@ -271,6 +392,31 @@ impl ChainSyncHandlerV0 {
                 Some(LogResultData::NewRequest(result.0, result.1, result.2, result.3))
             }
 
+            ScStoreService::ADDED_TO_TRACK_HASH => {
+                let result = self.add_to_track.handle(item).await;
+                Some(LogResultData::AddToTrack(result))
+            }
+
+            ScStoreService::BLOCK_FINALIZED_HASH => {
+                let (requests, validations, artifacts, assets, publishings) = self.block_finalized.handle(item).await?;
+                Some(LogResultData::BlockFinalized(requests, validations, artifacts, assets, publishings))
+            }
+
+            ScPublisherService::ACCOUNT_CREATED_HASH => {
+                let result = self.publisher.handle_account_created(item).await;
+                Some(LogResultData::NewPublisher(result))
+            }
+
+            ScPublisherService::APP_CREATED_HASH => {
+                let result = self.publisher.handle_app_created(item).await;
+                Some(LogResultData::NewPublisherApp(result.0, result.1))
+            }
+
+            ScObjService::OWNERSHIP_TRANSFERRED_HASH => {
+                let result = self.ownership.handle(item).await;
+                Some(LogResultData::OwnershipTransferred(result))
+            }
+
             _ => None,
         }
     }
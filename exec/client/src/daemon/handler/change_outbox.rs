@@ -0,0 +1,103 @@
+use crate::daemon::launcher::DaemonContex;
+use crate::data::repo::outbox_repo::OutboxRepo;
+use crate::result::ClientResult;
+use db_kf::client::KfProducer;
+use rdkafka::producer::FutureRecord;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+// Relays committed change events from the outbox to kafka. An event acked by kafka but not marked
+// as published is sent again, consumers dedupe on the event id
+pub struct ChangeOutboxHandler {
+    interval: Duration,
+    batch_size: i64,
+    retention: Duration,
+    topic: String,
+    producer: Option<Arc<KfProducer>>,
+    committed: Arc<Notify>,
+    outbox_repo: Arc<OutboxRepo>,
+}
+
+impl ChangeOutboxHandler {
+
+    pub fn new(
+        interval: Duration,
+        batch_size: i64,
+        retention: Duration,
+        topic: String,
+        producer: Option<Arc<KfProducer>>,
+        committed: Arc<Notify>,
+        outbox_repo: Arc<OutboxRepo>,
+    ) -> Self {
+        Self { interval, batch_size, retention, topic, producer, committed, outbox_repo }
+    }
+
+    pub async fn handle(&self, ctx: Arc<DaemonContex>) {
+        let Some(producer) = self.producer.as_ref() else {
+            warn!("[CHANGE_OUTBOX] Kafka isn't configured, change events stay in the outbox");
+            return;
+        };
+
+        loop {
+            if ctx.queue.is_shutdown() {
+                info!("[CHANGE_OUTBOX] Daemon queue is shutdown!");
+                break;
+            }
+
+            let full = match self.publish_pending(producer).await {
+                Ok(0) => false,
+                Ok(count) => {
+                    info!("[CHANGE_OUTBOX] Published {} events", count);
+                    count as i64 == self.batch_size
+                }
+                Err(e) => {
+                    error!("[CHANGE_OUTBOX] Publish failed, next run in {:?}: {}", self.interval, e);
+                    false
+                }
+            };
+
+            if let Err(e) = self.outbox_repo.delete_published(self.retention.as_secs() as i64).await {
+                warn!("[CHANGE_OUTBOX] Can't delete published events: {}", e);
+            }
+
+            // A full batch means more events are waiting
+            if full {
+                continue;
+            }
+
+            tokio::select! {
+                _ = sleep(self.interval) => {}
+                _ = self.committed.notified() => {}
+            }
+        }
+    }
+
+    // Stops at the first failed event, so later events of an asset never overtake it
+    async fn publish_pending(&self, producer: &KfProducer) -> ClientResult<usize> {
+        let entries = self.outbox_repo.find_pending(self.batch_size)
+            .await?;
+
+        let mut published = 0;
+        for entry in entries.iter() {
+            let record = FutureRecord::to(&self.topic)
+                .key(&entry.asset_address)
+                .payload(&entry.payload);
+
+            if let Err((e, _)) = producer.send(record).await {
+                warn!("[CHANGE_OUTBOX] Can't publish event {} after {} attempts: {}", entry.event_id, entry.attempts, e);
+                self.outbox_repo.set_failed(entry.id, &e.to_string())
+                    .await?;
+                break;
+            }
+
+            self.outbox_repo.set_published(entry.id)
+                .await?;
+            published += 1;
+        }
+
+        return Ok(published);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::daemon::data::stat_repo::StatSyncHandler;
+use crate::daemon::launcher::DaemonContex;
+use crate::data::models::CacheEvent;
+use crate::data::repo::cache_repo::CacheRepo;
+use chrono::{TimeDelta, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+pub struct DownloadsRecountHandler {
+    interval: Duration,
+    lag: Duration,
+    stat: Arc<StatSyncHandler>,
+    cache_repo: Arc<CacheRepo>,
+}
+
+impl DownloadsRecountHandler {
+
+    pub fn new(
+        interval: Duration,
+        lag: Duration,
+        stat: Arc<StatSyncHandler>,
+        cache_repo: Arc<CacheRepo>,
+    ) -> Self {
+        Self { interval, lag, stat, cache_repo }
+    }
+
+    // Window ends `lag` behind now, stat service flushes events in batches and they must land before the checkpoint
+    pub async fn handle(&self, ctx: Arc<DaemonContex>) {
+        loop {
+            if ctx.queue.is_shutdown() {
+                info!("[DOWNLOADS_RECOUNT] Daemon queue is shutdown!");
+                break;
+            }
+
+            let to = Utc::now() - TimeDelta::from_std(self.lag).unwrap_or_default();
+            match self.stat.recount_downloads(to).await {
+                Ok(Some(result)) => {
+                    info!("[DOWNLOADS_RECOUNT] Applied events from {} to {}, assets updated: {}", result.from, result.to, result.updated);
+
+                    if result.updated > 0 {
+                        self.publish_changes().await;
+                    }
+                }
+                Ok(None) => info!("[DOWNLOADS_RECOUNT] Checkpoint is ahead of {}, nothing to apply", to),
+                Err(e) => error!("[DOWNLOADS_RECOUNT] Recount failed, retry in {:?}: {}", self.interval, e),
+            }
+
+            sleep(self.interval).await;
+        }
+    }
+
+    // Charts are sorted by downloads
+    async fn publish_changes(&self) {
+        if let Err(e) = self.cache_repo.publish_event(&CacheEvent::ChartChanged).await {
+            warn!("[DOWNLOADS_RECOUNT] Can't publish cache event: {}", e);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::daemon::data::data_sync::{BlockLogData, DataSyncHandler};
+use crate::daemon::handler::chain_sync_v0::ChainSyncHandlerV0;
+use crate::daemon::launcher::DaemonContex;
+use crate::data::models::FailedLog;
+use crate::data::repo::error_repo::ErrorRepo;
+use crate::result::ClientResult;
+use alloy::rpc::types::Log;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+pub struct FailedLogRetryHandler {
+    interval: Duration,
+    batch_size: i64,
+    chain: Arc<ChainSyncHandlerV0>,
+    data_sync: Arc<DataSyncHandler>,
+    error_repo: Arc<ErrorRepo>,
+}
+
+impl FailedLogRetryHandler {
+
+    pub fn new(
+        interval: Duration,
+        batch_size: i64,
+        chain: Arc<ChainSyncHandlerV0>,
+        data_sync: Arc<DataSyncHandler>,
+        error_repo: Arc<ErrorRepo>,
+    ) -> Self {
+        Self { interval, batch_size, chain, data_sync, error_repo }
+    }
+
+    pub async fn handle(&self, ctx: Arc<DaemonContex>) {
+        loop {
+            if ctx.queue.is_shutdown() {
+                info!("[FAILED_LOG] Daemon queue is shutdown!");
+                break;
+            }
+
+            match self.retry_due().await {
+                Ok(0) => {}
+                Ok(count) => info!("[FAILED_LOG] Retried {} logs", count),
+                Err(e) => error!("[FAILED_LOG] Retry failed, next run in {:?}: {}", self.interval, e),
+            }
+
+            sleep(self.interval).await;
+        }
+    }
+
+    async fn retry_due(&self) -> ClientResult<usize> {
+        let entries = self.error_repo.find_due(self.batch_size)
+            .await?;
+
+        for entry in entries.iter() {
+            self.retry(entry).await?;
+        }
+
+        return Ok(entries.len());
+    }
+
+    // A failed attempt is recorded by the handler or here, which also moves `next_retry_at`.
+    // Writes of the replayed log are skipped where a later block already changed the same row
+    async fn retry(&self, entry: &FailedLog) -> ClientResult<()> {
+        let log = match serde_json::from_str::<Log>(&entry.log) {
+            Ok(log) => log,
+            Err(e) => {
+                warn!("[FAILED_LOG] Can't read stored log {}: {}", entry.id, e);
+                return self.error_repo.set_dead(entry.id, &e.to_string()).await;
+            }
+        };
+
+        if let Some(data) = self.chain.handle_log(&log).await {
+            let new_data = vec![BlockLogData { block_number: entry.block_number as u64, data }];
+            if let Err(e) = self.data_sync.sync_logs(&new_data).await {
+                return self.error_repo.insert_error_tx(&log, &e.to_string()).await;
+            }
+        }
+
+        if self.error_repo.resolve(entry.id, entry.attempts).await? {
+            info!("[FAILED_LOG] Log {} of {} resolved", entry.log_index, entry.tx_hash);
+        }
+
+        return Ok(());
+    }
+}
@@ -0,0 +1,162 @@
+use crate::env;
+use alloy::hex::ToHexExt;
+use alloy::primitives::B256;
+use alloy::rpc::types::Log;
+use client_ethscan::models::GetLogsParams;
+use codegen_contracts::ext::ToChecksum;
+use service_event::service::EventLogService;
+use std::collections::{BTreeSet, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{error, info};
+
+// Pages through the log source, a failed page is retried until it succeeds
+pub struct LogFetcher {
+    log_client: Arc<EventLogService>,
+    retry_timeout: Duration,
+    page_timeout: Duration,
+}
+
+impl LogFetcher {
+
+    pub fn new(log_client: Arc<EventLogService>, retry_timeout: Duration, page_timeout: Duration) -> Self {
+        Self { log_client, retry_timeout, page_timeout }
+    }
+
+    pub async fn fetch_source(&self, name: &str, from_block: u64, to_block: u64, address: Option<String>, topic0: B256, logs: &mut Vec<Log>) {
+        let offset = env::max_logs_per_request();
+        let mut params = GetLogsParams {
+            from_block,
+            to_block: Some(to_block),
+            addresses: address.into_iter().collect(),
+            offset: Some(offset),
+
+            topic0: Some(topic0.encode_hex_with_prefix()),
+            page: None,
+        };
+
+        self.fetch_logs(name, &mut params, offset, logs).await;
+    }
+
+    // One filter per chunk of addresses. EthScan can't take several, so it's queried by the topic
+    // and logs of other emitters are dropped here
+    pub async fn fetch_addresses(&self, name: &str, from_block: u64, to_block: u64, addresses: BTreeSet<String>, topic0: B256, logs: &mut Vec<Log>) {
+        if addresses.is_empty() {
+            return;
+        }
+
+        let per_request = match self.log_client.filters_many_addresses() {
+            true => env::log_addresses_per_request(),
+            false => addresses.len(),
+        };
+
+        let offset = env::max_logs_per_request();
+        let addresses = addresses.into_iter().collect::<Vec<String>>();
+        for chunk in addresses.chunks(per_request) {
+            let mut params = GetLogsParams {
+                from_block,
+                to_block: Some(to_block),
+                addresses: chunk.to_vec(),
+                offset: Some(offset),
+
+                topic0: Some(topic0.encode_hex_with_prefix()),
+                page: None,
+            };
+
+            let mut found = Vec::new();
+            self.fetch_logs(name, &mut params, offset, &mut found).await;
+
+            let chunk = chunk.iter().collect::<HashSet<&String>>();
+            logs.extend(found.into_iter().filter(|log| chunk.contains(&log.address().checksum())));
+        }
+    }
+
+    async fn fetch_logs(&self, name: &str, params: &mut GetLogsParams, offset: u32, logs: &mut Vec<Log>) {
+        let mut page = 1u32;
+        loop {
+            params.page = Some(page);
+
+            info!("[DAEMON_SYNC] Fetching {} logs (with topic) page {} (offset: {})", name, page, offset);
+            let response = match self.log_client.get_logs(params).await {
+                Ok(response) => response,
+                Err(err) => {
+                    error!("[DAEMON_SYNC] Error getting {} logs: {}", name, err);
+                    sleep(self.retry_timeout).await;
+                    continue;
+                }
+            };
+
+            let results_count = response.result.len();
+            info!("[DAEMON_SYNC] Got {} results for {} page {}", results_count, name, page);
+
+            // Use block numbers from EthScan to fetch actual logs via RPC
+            logs.extend(response.result);
+
+            if (results_count as u32) < offset {
+                info!("[DAEMON_SYNC] Reached end of results for {} (got {} < {})", name, results_count, offset);
+                break;
+            }
+
+            page += 1;
+            sleep(self.page_timeout).await;
+        }
+    }
+}
+
+#[tokio::test]
+async fn check_addresses_are_batched() {
+    use alloy::network::EthereumWallet;
+    use alloy::primitives::Address;
+    use alloy::signers::local::PrivateKeySigner;
+    use axum::extract::State;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use net_client::http::HttpProviderFactory;
+    use net_client::node::provider::Web3ProviderFactory;
+    use serde_json::{json, Value};
+    use service_event::service::EventLogClient;
+    use std::sync::Mutex;
+
+    // Address count of every `eth_getLogs` filter
+    type Filters = Arc<Mutex<Vec<usize>>>;
+
+    async fn rpc(State(filters): State<Filters>, Json(request): Json<Value>) -> Json<Value> {
+        if request["method"] == "eth_getLogs" {
+            let addresses = request["params"][0]["address"].as_array().map(|addresses| addresses.len()).unwrap_or(1);
+            filters.lock().unwrap().push(addresses);
+        }
+
+        return Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": [] }));
+    }
+
+    let filters = Filters::default();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let node_url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+    let node = Router::new()
+        .route("/", post(rpc))
+        .with_state(filters.clone());
+
+    tokio::spawn(async move { axum::serve(listener, node).await.unwrap() });
+
+    let client = HttpProviderFactory::http_client().unwrap();
+    let wallet = EthereumWallet::from(PrivateKeySigner::random());
+    let eth = Arc::new(Web3ProviderFactory::provider(node_url, 1, &client, wallet));
+    let fetcher = LogFetcher::new(
+        Arc::new(EventLogService::new(EventLogClient::Eth(eth))),
+        Duration::ZERO,
+        Duration::ZERO,
+    );
+
+    let apps = (1..=1_200u64)
+        .map(|i| Address::left_padding_from(&i.to_be_bytes()).checksum())
+        .collect::<BTreeSet<String>>();
+
+    let mut logs = Vec::new();
+    fetcher.fetch_addresses("OWNERSHIP", 1, 2, apps, B256::ZERO, &mut logs).await;
+    fetcher.fetch_addresses("PUBLISHER_APPS", 1, 2, BTreeSet::new(), B256::ZERO, &mut logs).await;
+
+    let per_request = env::log_addresses_per_request();
+    assert_eq!(*filters.lock().unwrap(), vec![per_request, per_request, 1_200 - 2 * per_request]);
+    assert!(logs.is_empty());
+}
@@ -1,4 +1,9 @@
 
 pub mod sync;
 pub mod chain_sync_v0;
-pub mod chain_sync_v1;
+pub mod log_fetcher;
+pub mod downloads_recount;
+pub mod trending;
+pub mod failed_log_retry;
+pub mod resync;
+pub mod change_outbox;
@@ -0,0 +1,163 @@
+use crate::daemon::cli::DaemonCommand;
+use crate::daemon::data::data_sync::{changed_addresses, BlockLogData, DataSyncHandler, LogResultData};
+use crate::daemon::data::object_factory::{DaemonResult, ObjectFactory};
+use crate::daemon::handler::chain_sync_v0::ChainSyncHandlerV0;
+use crate::data::models::AssetState;
+use crate::data::repo::artifact_repo::ArtifactRepo;
+use crate::data::repo::object_repo::ObjectRepo;
+use crate::result::ClientError;
+use crate::util::state_diff::{diff_states, StateChange};
+use alloy::primitives::Address;
+use codegen_contracts::ext::ToChecksum;
+use service_sc::obj::ScObjService;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+pub struct AssetDiff {
+    pub address: String,
+    pub changes: Vec<StateChange>,
+}
+
+impl Display for AssetDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.changes.is_empty() {
+            return write!(f, "{}: unchanged", self.address);
+        }
+
+        write!(f, "{}: {} changed", self.address, self.changes.len())?;
+        for change in self.changes.iter() {
+            write!(f, "\n  {}", change)?;
+        }
+
+        return Ok(());
+    }
+}
+
+// Repairs synced data in place, writes are the same upserts the sync does, so a run can be repeated
+pub struct ResyncHandler {
+    chain: Arc<ChainSyncHandlerV0>,
+    data_sync: Arc<DataSyncHandler>,
+    factory: Arc<ObjectFactory>,
+    obj_service: Arc<ScObjService>,
+    object_repo: Arc<ObjectRepo>,
+    art_repo: Arc<ArtifactRepo>,
+}
+
+impl ResyncHandler {
+
+    pub fn new(
+        chain: Arc<ChainSyncHandlerV0>,
+        data_sync: Arc<DataSyncHandler>,
+        factory: Arc<ObjectFactory>,
+        obj_service: Arc<ScObjService>,
+        object_repo: Arc<ObjectRepo>,
+        art_repo: Arc<ArtifactRepo>,
+    ) -> Self {
+        Self { chain, data_sync, factory, obj_service, object_repo, art_repo }
+    }
+
+    pub async fn handle(&self, command: DaemonCommand) -> DaemonResult<Vec<AssetDiff>> {
+        return match command {
+            DaemonCommand::Run => Ok(vec![]),
+            DaemonCommand::Resync { from_block, to_block } => self.resync_range(from_block, to_block).await,
+            DaemonCommand::Refresh { address } => self.refresh_asset(address).await,
+        };
+    }
+
+    // The sync checkpoint stays where it is, blocks past it are synced again by the daemon
+    pub async fn resync_range(&self, from_block: u64, to_block: u64) -> DaemonResult<Vec<AssetDiff>> {
+        let logs = self.chain.fetch_range(from_block, to_block)
+            .await?;
+
+        info!("[RESYNC] Handling {} logs from block {} to {}", logs.len(), from_block, to_block);
+        let mut new_data = Vec::with_capacity(logs.len());
+        for log in logs.iter() {
+            let Some(block_number) = log.block_number else {
+                warn!("[RESYNC] Skip log without block number: {:?}", log.transaction_hash);
+                continue;
+            };
+
+            if let Some(data) = self.chain.handle_log(log).await {
+                new_data.push(BlockLogData { block_number, data });
+            }
+        }
+
+        let mut addresses = changed_addresses(&new_data, &None)
+            .into_iter()
+            .collect::<BTreeSet<String>>();
+        for BlockLogData { data, .. } in new_data.iter() {
+            if let LogResultData::NewPublisherApp(Some(app), _) = data {
+                addresses.insert(app.asset_address.clone());
+            }
+        }
+        let addresses = addresses.into_iter().collect::<Vec<String>>();
+
+        let before = self.object_repo.find_states(&addresses)
+            .await?;
+
+        self.data_sync.sync_logs(&new_data)
+            .await?;
+
+        let after = self.object_repo.find_states(&addresses)
+            .await?;
+
+        return diff(before, after);
+    }
+
+    // Asset, builds and owner are fetched again, also for rows the sync never refetches
+    pub async fn refresh_asset(&self, address: Address) -> DaemonResult<Vec<AssetDiff>> {
+        let checksum = address.checksum();
+
+        let asset = self.factory.create_obj(address)
+            .await?;
+
+        let versions = self.art_repo.find_version_codes(&checksum)
+            .await?;
+
+        let mut artifacts = Vec::with_capacity(versions.len());
+        for version in versions {
+            match self.factory.create_artifact(address, version).await {
+                Ok(artifact) => artifacts.push(artifact),
+                Err(e) => warn!("[RESYNC] Skip build {} of {}: {}", version, checksum, e),
+            }
+        }
+
+        let owner = self.obj_service.get_owner(address)
+            .await?
+            .checksum();
+
+        let addresses = vec![checksum];
+        let before = self.object_repo.find_states(&addresses)
+            .await?;
+
+        self.data_sync.refresh_asset(&asset, &artifacts, &owner)
+            .await?;
+
+        let after = self.object_repo.find_states(&addresses)
+            .await?;
+
+        return diff(before, after);
+    }
+}
+
+fn diff(before: Vec<AssetState>, after: Vec<AssetState>) -> DaemonResult<Vec<AssetDiff>> {
+    let before = before.into_iter()
+        .map(|state| (state.address, state.state))
+        .collect::<BTreeMap<String, String>>();
+
+    let mut diffs = Vec::with_capacity(after.len());
+    for state in after {
+        let prev = before.get(&state.address)
+            .map(|prev| prev.as_str())
+            .unwrap_or("{}");
+
+        let changes = diff_states(prev, &state.state)
+            .map_err(ClientError::from)?;
+
+        diffs.push(AssetDiff { address: state.address, changes });
+    }
+
+    return Ok(diffs);
+}
@@ -0,0 +1,51 @@
+use crate::daemon::data::object_factory::ObjectFactory;
+use crate::data::id::TrackId;
+use crate::data::models::Publishing;
+use crate::data::repo::error_repo::ErrorRepo;
+use alloy::rpc::types::Log;
+use codegen_contracts::ext::ToChecksum;
+use service_sc::store::ScStoreService;
+use std::sync::Arc;
+use tracing::{error, info};
+
+pub struct AddToTrackHandlerV0 {
+    factory: Arc<ObjectFactory>,
+    error_repo: Arc<ErrorRepo>,
+}
+
+impl AddToTrackHandlerV0 {
+
+    pub fn new(
+        factory: Arc<ObjectFactory>,
+        error_repo: Arc<ErrorRepo>,
+    ) -> Self {
+        Self {
+            factory,
+            error_repo,
+        }
+    }
+
+    // Track membership is saved by `DataSyncHandler` together with the rest of the batch
+    pub async fn handle(&self, item: &Log) -> Option<Publishing> {
+        let log = ScStoreService::decode_add_to_track(item.as_ref());
+
+        let (target, track_id, version_code) = match log {
+            Ok(log) => (log.data.target, log.data.trackId, log.data.versionCode),
+            Err(e) => {
+                let _ = self.error_repo.insert_fatal_tx(item, &e.to_string()).await;
+
+                error!("[ADD_TO_TRACK] Failed to decode log: {}", e);
+                return None;
+            }
+        };
+
+        let track_id = TrackId::from(track_id.to::<i32>());
+        let version_code = version_code.to::<i64>();
+        info!("[ADD_TO_TRACK] Handling log: target - {} | track - {} | version - {}!", target.checksum(), track_id, version_code);
+
+        let publishing = self.factory
+            .create_publishing(target, track_id, version_code);
+
+        return Some(publishing);
+    }
+}
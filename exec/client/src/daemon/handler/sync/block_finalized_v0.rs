@@ -0,0 +1,207 @@
+use crate::daemon::data::object_factory::ObjectFactory;
+use crate::data::id::TrackId;
+use crate::data::models::{NewArtifact, NewAsset, NewBuildRequest, Publishing, RequestValidation};
+use crate::data::repo::artifact_repo::ArtifactRepo;
+use crate::data::repo::error_repo::ErrorRepo;
+use crate::data::repo::object_repo::ObjectRepo;
+use alloy::primitives::{Address, TxHash};
+use alloy::rpc::types::Log;
+use codegen_block::block::ValidationBlock;
+use codegen_block::status::ApkValidationStatus;
+use codegen_contracts::ext::ToChecksum;
+use prost::Message;
+use service_sc::store::ScStoreService;
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+pub type FinalizedBlockData = (
+    Vec<NewBuildRequest>,
+    Vec<RequestValidation>,
+    Vec<NewArtifact>,
+    Vec<NewAsset>,
+    Vec<Publishing>,
+);
+
+pub struct BlockFinalizedHandlerV0 {
+    factory: Arc<ObjectFactory>,
+    store_provider: Arc<ScStoreService>,
+    obj_repo: Arc<ObjectRepo>,
+    art_repo: Arc<ArtifactRepo>,
+    error_repo: Arc<ErrorRepo>,
+}
+
+impl BlockFinalizedHandlerV0 {
+
+    pub fn new(
+        factory: Arc<ObjectFactory>,
+        store_provider: Arc<ScStoreService>,
+        obj_repo: Arc<ObjectRepo>,
+        art_repo: Arc<ArtifactRepo>,
+        error_repo: Arc<ErrorRepo>,
+    ) -> Self {
+        Self {
+            factory,
+            store_provider,
+            obj_repo,
+            art_repo,
+            error_repo,
+        }
+    }
+
+    // Results of the block are saved by `DataSyncHandler` together with the rest of the batch
+    pub async fn handle(&self, item: &Log) -> Option<FinalizedBlockData> {
+        let (block_id, object_id) = match ScStoreService::decode_block_finalize(item.as_ref()) {
+            Ok(log) => (log.data.blockId, log.data.objectId),
+            Err(e) => {
+                let _ = self.error_repo.insert_fatal_tx(item, &e.to_string()).await;
+
+                error!("[BLOCK_FINALIZED] Failed to decode block finalize: {}", e);
+                return None;
+            }
+        };
+
+        // Block content is the input of the transaction referenced by `objectId`
+        let Ok(block_tx) = TxHash::try_from(object_id.as_ref()) else {
+            let _ = self.error_repo.insert_fatal_tx(item, "Object id is not a transaction hash").await;
+
+            error!("[BLOCK_FINALIZED] Failed to decode object id to TxHash.");
+            return None;
+        };
+
+        let block_data = match self.store_provider.get_block_data(block_tx).await {
+            Ok(Some(data)) => data,
+            Ok(None) => {
+                let _ = self.error_repo.insert_error_tx(item, "Block data not found").await;
+
+                error!("[BLOCK_FINALIZED] Block data not found for {}", block_tx);
+                return None;
+            }
+            Err(e) => {
+                let _ = self.error_repo.insert_error_tx(item, &e.to_string()).await;
+
+                error!("[BLOCK_FINALIZED] Failed to get block data: {}", e);
+                return None;
+            }
+        };
+
+        let block = match ValidationBlock::decode(block_data.as_ref()) {
+            Ok(block) => block,
+            Err(e) => {
+                let _ = self.error_repo.insert_fatal_tx(item, &e.to_string()).await;
+
+                error!("[BLOCK_FINALIZED] Failed to decode block: {}", e);
+                return None;
+            }
+        };
+
+        info!("[BLOCK_FINALIZED] Handling block {} with {} requests", block.id, block.requests.len());
+        return Some(self.handle_internal(item, block_id.to::<i64>(), block).await);
+    }
+
+    async fn handle_internal(&self, item: &Log, block_id: i64, block: ValidationBlock) -> FinalizedBlockData {
+        let mut requests = Vec::with_capacity(block.requests.len());
+        let mut validations = Vec::with_capacity(block.requests.len());
+        let mut publishings = vec![];
+        let mut obj_addresses = BTreeSet::new();
+        let mut artifact_refs = BTreeSet::new();
+
+        let success = ApkValidationStatus::Success.code() as i32;
+
+        for result in block.requests {
+            let Ok(obj_address) = Address::from_str(result.asset_address.as_str()) else {
+                error!("[BLOCK_FINALIZED] Failed to parse object address: {}", result.asset_address);
+                continue;
+            };
+
+            let address = obj_address.checksum();
+            let version = result.object_version;
+            let status = result.status as i32;
+
+            let mut build_request = self.factory.create_build_request(
+                result.request_id,
+                obj_address,
+                result.track_id as u8,
+                Some(status),
+                version,
+                result.owner_version,
+                item.block_timestamp,
+            );
+            build_request.block_id = Some(block_id);
+            build_request.finalized_at = build_request.created_at;
+            requests.push(build_request);
+
+            validations.push(RequestValidation {
+                request_id: result.request_id as i64,
+                asset_address: address.clone(),
+                version_code: version,
+                owner_version: result.owner_version as i64,
+                status,
+                file_hash: Some(result.file_hash).filter(|hash| !hash.is_empty()),
+            });
+
+            if version > 0 {
+                obj_addresses.insert(address.clone());
+            }
+
+            if result.artifact_protocol > 0 {
+                artifact_refs.insert((address, version));
+            }
+
+            // Failed builds stay out of tracks
+            if result.track_id > 0 && status == success {
+                let publishing = self.factory
+                    .create_publishing(obj_address, TrackId::from(result.track_id as i32), version);
+                publishings.push(publishing);
+            }
+        }
+
+        let missing_obj = self.obj_repo.find_obj_missing_addresses(obj_addresses.into_iter().collect())
+            .await;
+        let mut assets = Vec::with_capacity(missing_obj.len());
+        for address in missing_obj {
+            let Ok(obj_address) = Address::from_str(address.as_ref()) else {
+                warn!("[BLOCK_FINALIZED] Can't decode address {}", address);
+                continue;
+            };
+
+            match self.factory.create_obj(obj_address).await {
+                Ok(obj) => assets.push(obj),
+                Err(e) => {
+                    let _ = self.error_repo.insert_error_tx(item, &e.to_string()).await;
+                    error!("[BLOCK_FINALIZED] Can't fetch object data: {}, {}", address, e);
+                }
+            }
+        }
+
+        let missing_artifacts = self.art_repo.find_artifact_missing_refs(artifact_refs.into_iter().collect())
+            .await;
+        let mut artifacts = Vec::with_capacity(missing_artifacts.len());
+        for (address, version) in missing_artifacts {
+            let Ok(obj_address) = Address::from_str(address.as_ref()) else {
+                warn!("[BLOCK_FINALIZED] Can't decode address {}", address);
+                continue;
+            };
+
+            match self.factory.create_artifact(obj_address, version).await {
+                Ok(artifact) => artifacts.push(artifact),
+                Err(e) => {
+                    let _ = self.error_repo.insert_error_tx(item, &e.to_string()).await;
+                    error!("[BLOCK_FINALIZED] Can't fetch artifact data: {}, {}", address, e);
+                }
+            }
+        }
+
+        return (requests, validations, artifacts, assets, publishings);
+    }
+}
+
+#[tokio::test]
+async fn check_decode() {
+    let data = "08013AD60108011001180122423078303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303139353335382A2A3078303333323962623044333644323345416344623132354335333030414644393538303845396261393001380140014A06626C616B65335240463335434342364534374238393338394633383041324239423330424133343445373645383433313831463132354632333437364536384646423732434341435A0C08FC9A0310FCBA0318D1CA036001";
+
+    let result = ValidationBlock::decode(hex::decode(data).unwrap().as_slice());
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap().id, 1);
+}
@@ -1,7 +1,7 @@
 
-pub mod sync_finish;
 pub mod sync_finish_v0;
-pub mod block_finalized;
-pub mod new_req;
+pub mod block_finalized_v0;
 pub mod new_req_v0;
-pub mod add_to_track;
+pub mod add_to_track_v0;
+pub mod publisher_v0;
+pub mod ownership_v0;
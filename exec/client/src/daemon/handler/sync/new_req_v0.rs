@@ -6,7 +6,6 @@ use crate::data::repo::error_repo::ErrorRepo;
 use crate::data::repo::object_repo::ObjectRepo;
 use crate::data::repo::publishing_repo::PublishingRepo;
 use crate::data::repo::validation_repo::ValidationRepo;
-use alloy::hex::ToHexExt;
 use alloy::primitives::ruint::aliases::U256;
 use alloy::primitives::Address;
 use alloy::rpc::types::Log;
@@ -52,22 +51,20 @@ impl NewRequestHandlerV0 {
                 result.data.data,
             ),
             Err(e) => {
-                if let Some(tx_hash) = item.transaction_hash {
-                    let _ = self.error_repo.insert_fatal_tx(tx_hash.encode_hex_with_prefix())
-                        .await;
-                }
+                let _ = self.error_repo.insert_fatal_tx(item, &e.to_string()).await;
 
                 error!("[NEW_REQ_HANDLER] Can't decode app's event data: {}", e);
                 return (None, None, None, None);
             }
         };
 
-        return self.handle_internal(request_id, request_type.to(), item.block_timestamp, obj, data.as_ref())
+        return self.handle_internal(item, request_id, request_type.to(), item.block_timestamp, obj, data.as_ref())
             .await;
     }
 
     async fn handle_internal(
         &self,
+        item: &Log,
         request_id: u64,
         request_type: u8,
         request_time: Option<u64>,
@@ -103,6 +100,7 @@ impl NewRequestHandlerV0 {
                     }
                     Err(e) => {
                         error!("[NEW_REQ_HANDLER] Can't create artifact {}", e);
+                        let _ = self.error_repo.insert_error_tx(item, &e.to_string()).await;
                     }
                 }
 
@@ -127,6 +125,7 @@ impl NewRequestHandlerV0 {
                 }
                 Err(e) => {
                     error!("[NEW_REQ_HANDLER] Can't create object {}", e);
+                    let _ = self.error_repo.insert_error_tx(item, &e.to_string()).await;
                 }
             }
         }
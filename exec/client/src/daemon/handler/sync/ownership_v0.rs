@@ -0,0 +1,74 @@
+use crate::data::id::OwnershipChangeKind;
+use crate::data::models::NewOwnershipChange;
+use crate::data::repo::error_repo::ErrorRepo;
+use alloy::hex::ToHexExt;
+use alloy::rpc::types::Log;
+use chrono::DateTime;
+use codegen_contracts::ext::ToChecksum;
+use service_sc::obj::ScObjService;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+pub struct OwnershipHandlerV0 {
+    error_repo: Arc<ErrorRepo>,
+}
+
+impl OwnershipHandlerV0 {
+
+    pub fn new(error_repo: Arc<ErrorRepo>) -> Self {
+        Self { error_repo }
+    }
+
+    // Logs are only fetched for known apps, a transfer of an app missing in the db is still dropped on insert
+    pub async fn handle(&self, item: &Log) -> Option<NewOwnershipChange> {
+        let log = match ScObjService::decode_ownership_transferred(item.as_ref()) {
+            Ok(log) => log,
+            Err(e) => {
+                let _ = self.error_repo.insert_fatal_tx(item, &e.to_string()).await;
+
+                error!("[OWNERSHIP] Failed to decode ownership log: {}", e);
+                return None;
+            }
+        };
+
+        // Deploy of the app, its creation is recorded from `AppCreated`
+        if log.data.previousOwner.is_zero() {
+            return None;
+        }
+
+        let asset_address = item.address().checksum();
+        let prev_owner = log.data.previousOwner.checksum();
+        let new_owner = log.data.newOwner.checksum();
+        info!("[OWNERSHIP] Handling transfer: {} | {} -> {}", asset_address, prev_owner, new_owner);
+
+        return new_ownership_change(item, asset_address, Some(prev_owner), new_owner, OwnershipChangeKind::Transferred);
+    }
+}
+
+pub fn new_ownership_change(
+    item: &Log,
+    asset_address: String,
+    prev_owner: Option<String>,
+    new_owner: String,
+    kind: OwnershipChangeKind,
+) -> Option<NewOwnershipChange> {
+    let (Some(tx_hash), Some(log_index)) = (item.transaction_hash, item.log_index) else {
+        warn!("[OWNERSHIP] Skip owner change of {} without tx position", asset_address);
+        return None;
+    };
+
+    let changed_at = item.block_timestamp
+        .and_then(|time| DateTime::from_timestamp(time as i64, 0));
+
+    return Some(
+        NewOwnershipChange {
+            asset_address,
+            prev_owner,
+            new_owner,
+            kind,
+            tx_hash: tx_hash.encode_hex_with_prefix(),
+            log_index: log_index as i64,
+            changed_at,
+        }
+    );
+}
@@ -0,0 +1,81 @@
+use crate::daemon::handler::sync::ownership_v0::new_ownership_change;
+use crate::data::id::OwnershipChangeKind;
+use crate::data::models::{NewOwnershipChange, NewPublisher, NewPublisherApp};
+use crate::data::repo::error_repo::ErrorRepo;
+use alloy::rpc::types::Log;
+use codegen_contracts::ext::ToChecksum;
+use service_sc::publisher::ScPublisherService;
+use std::sync::Arc;
+use tracing::{error, info};
+
+pub struct PublisherHandlerV0 {
+    error_repo: Arc<ErrorRepo>,
+}
+
+impl PublisherHandlerV0 {
+
+    pub fn new(error_repo: Arc<ErrorRepo>) -> Self {
+        Self { error_repo }
+    }
+
+    pub async fn handle_account_created(&self, item: &Log) -> Option<NewPublisher> {
+        let log = match ScPublisherService::decode_account_created(item.as_ref()) {
+            Ok(log) => log,
+            Err(e) => {
+                self.save_fatal(item, &e.to_string()).await;
+                error!("[PUBLISHER] Failed to decode account log: {}", e);
+                return None;
+            }
+        };
+
+        let address = log.data.account.checksum();
+        info!("[PUBLISHER] Handling account: {} | owner - {}", address, log.data.owner.checksum());
+
+        return Some(
+            NewPublisher {
+                address,
+                owner: log.data.owner.checksum(),
+                name: log.data.name,
+                block_number: item.block_number.unwrap_or_default() as i64,
+            }
+        );
+    }
+
+    // The publisher account is the first owner of the app
+    pub async fn handle_app_created(&self, item: &Log) -> (Option<NewPublisherApp>, Option<NewOwnershipChange>) {
+        let log = match ScPublisherService::decode_app_created(item.as_ref()) {
+            Ok(log) => log,
+            Err(e) => {
+                self.save_fatal(item, &e.to_string()).await;
+                error!("[PUBLISHER] Failed to decode app log: {}", e);
+                return (None, None);
+            }
+        };
+
+        let publisher_address = item.address().checksum();
+        let asset_address = log.data.appAddress.checksum();
+        info!("[PUBLISHER] Handling app: {} | publisher - {}", asset_address, publisher_address);
+
+        let ownership = new_ownership_change(
+            item,
+            asset_address.clone(),
+            None,
+            publisher_address.clone(),
+            OwnershipChangeKind::Created,
+        );
+
+        let app = NewPublisherApp {
+            publisher_address,
+            asset_address,
+            package_name: log.data.id,
+            name: log.data.name,
+            block_number: item.block_number.unwrap_or_default() as i64,
+        };
+
+        return (Some(app), ownership);
+    }
+
+    async fn save_fatal(&self, item: &Log, error: &str) {
+        let _ = self.error_repo.insert_fatal_tx(item, error).await;
+    }
+}
@@ -4,7 +4,7 @@ use crate::data::repo::assetlink_repo::AssetlinkRepo;
 use crate::data::repo::error_repo::ErrorRepo;
 use crate::data::repo::object_repo::ObjectRepo;
 use alloy::hex::ToHexExt;
-use alloy::primitives::{Address, Bytes, LogData, TxHash, U256};
+use alloy::primitives::{Address, Bytes, LogData, U256};
 use alloy::rpc::types::Log;
 use core_std::trier::SyncTrier;
 use service_sc::assetlinks::{AssetlinkStatusCode, ScAssetLinkService};
@@ -50,23 +50,19 @@ impl SyncFinishedHandlerV0 {
         let (obj_address, status, owner_version) = match result {
             Ok(log) => (log.data.app, log.data.status, log.data.version),
             Err(e) => {
-                if let Some(tx_hash) = item.transaction_hash {
-                    let _ = self.error_repo.insert_fatal_tx(tx_hash.encode_hex_with_prefix())
-                        .await;
-                }
+                let _ = self.error_repo.insert_fatal_tx(item, &e.to_string()).await;
 
                 error!("[SYNC_FINISH_HANDLER] Failed to decode finalize log: {}", e);
                 return (None, None);
             }
         };
 
-       return self.handle_internal(item.transaction_hash, obj_address, status.to(), owner_version.to()).await;
+       return self.handle_internal(item, obj_address, status.to(), owner_version.to()).await;
     }
 
-    // TODO if filed we should save and try again
     async fn handle_internal(
         &self,
-        transaction_hash: Option<TxHash>,
+        item: &Log,
         obj_address: Address,
         status: i32,
         owner_version: i64,
@@ -76,36 +72,39 @@ impl SyncFinishedHandlerV0 {
             Ok(website) => website,
             Err(e) => {
                 error!("[SYNC_FINISH_HANDLER] Failed to get website: for asset {} with version {}, error: {}", object_addr, owner_version, e);
-                if let Some(tx_hash) = transaction_hash {
-                    let _ = self.error_repo.insert_error_tx(tx_hash.encode_hex_with_prefix())
-                        .await;
-                }
+                let _ = self.error_repo.insert_error_tx(item, &e.to_string()).await;
 
                 return (None, None);
             }
         };
         
         let proofs_res = self.app_provider.get_owner_proof_v0(obj_address, owner_version).await;
-        let owner_proofs = if let Ok(proofs) = proofs_res {
-            if let Some(proofs) = proofs { // TODO combine let
-                proofs 
-            } else { 
+        let owner_proofs = match proofs_res {
+            Ok(Some(proofs)) => proofs,
+            Ok(None) => {
                 error!("[SYNC_FINISH_HANDLER] Failed to get proofs for asset {} with version {}", object_addr, owner_version);
-                return (None, None);      
+                return (None, None);
+            }
+            Err(e) => {
+                error!("[SYNC_FINISH_HANDLER] Failed to get proofs for asset {} with version {}, error: {}", object_addr, owner_version, e);
+                let _ = self.error_repo.insert_error_tx(item, &e.to_string()).await;
+                return (None, None);
             }
-        } else {
-            error!("[SYNC_FINISH_HANDLER] Failed to get proofs for asset {} with version {}", object_addr, owner_version);
-            return (None, None);       
         };
 
         let result = self.verifier.verify_ownership_proofs_raw(
             obj_address, &owner_proofs.data.fingerprints, &owner_proofs.certs, &owner_proofs.proofs
         );
 
+        let fingerprints = owner_proofs.data.fingerprints.iter()
+            .map(|fingerprint| fingerprint.encode_hex())
+            .collect();
+
         let proof = ValidationProof {
             asset_address: object_addr.clone(),
             owner_version,
             status: result.code() as i32,
+            fingerprints,
         };
         
         let verification = AssetlinkSync {
@@ -0,0 +1,69 @@
+use crate::daemon::data::object_factory::DaemonResult;
+use crate::daemon::data::stat_repo::StatSyncHandler;
+use crate::daemon::data::trending::TrendingScore;
+use crate::daemon::launcher::DaemonContex;
+use crate::data::models::CacheEvent;
+use crate::data::repo::cache_repo::CacheRepo;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+pub struct TrendingHandler {
+    interval: Duration,
+    stat: Arc<StatSyncHandler>,
+    cache_repo: Arc<CacheRepo>,
+}
+
+impl TrendingHandler {
+
+    pub fn new(
+        interval: Duration,
+        stat: Arc<StatSyncHandler>,
+        cache_repo: Arc<CacheRepo>,
+    ) -> Self {
+        Self { interval, stat, cache_repo }
+    }
+
+    pub async fn handle(&self, ctx: Arc<DaemonContex>) {
+        loop {
+            if ctx.queue.is_shutdown() {
+                info!("[TRENDING] Daemon queue is shutdown!");
+                break;
+            }
+
+            match self.refresh().await {
+                Ok(count) => {
+                    info!("[TRENDING] Scores refreshed for {} assets", count);
+                    self.publish_changes().await;
+                }
+                Err(e) => error!("[TRENDING] Refresh failed, retry in {:?}: {}", self.interval, e),
+            }
+
+            sleep(self.interval).await;
+        }
+    }
+
+    async fn refresh(&self) -> DaemonResult<u64> {
+        let counts = self.stat.get_trending_counts(Utc::now())
+            .await?;
+
+        let scores = counts.iter()
+            .map(TrendingScore::from_counts)
+            .filter(|score| score.score > 0.0)
+            .collect::<Vec<TrendingScore>>();
+
+        let count = self.stat.replace_trending_scores(&scores)
+            .await?;
+
+        return Ok(count);
+    }
+
+    // Trending chart is ordered by score
+    async fn publish_changes(&self) {
+        if let Err(e) = self.cache_repo.publish_event(&CacheEvent::ChartChanged).await {
+            warn!("[TRENDING] Can't publish cache event: {}", e);
+        }
+    }
+}
@@ -1,4 +1,8 @@
 use crate::daemon::handler::chain_sync_v0::ChainSyncHandlerV0;
+use crate::daemon::handler::change_outbox::ChangeOutboxHandler;
+use crate::daemon::handler::downloads_recount::DownloadsRecountHandler;
+use crate::daemon::handler::failed_log_retry::FailedLogRetryHandler;
+use crate::daemon::handler::trending::TrendingHandler;
 use async_trait::async_trait;
 use core_actor::{ActionQueue, ActionQueueError, Context, EventHandler, UniqueEvent};
 use derive_more::Display;
@@ -15,6 +19,15 @@ pub enum DaemonAction {
     #[display("DownloadsRecount")]
     DownloadsRecount,
 
+    #[display("TrendingRefresh")]
+    TrendingRefresh,
+
+    #[display("FailedLogRetry")]
+    FailedLogRetry,
+
+    #[display("ChangeOutbox")]
+    ChangeOutbox,
+
     #[display("Shutdown")]
     Shutdown,
 }
@@ -27,6 +40,9 @@ impl UniqueEvent<u64> for DaemonAction {
             DaemonAction::ChainSync => 1,
             DaemonAction::DownloadsRecount => 2,
             DaemonAction::Shutdown => 3,
+            DaemonAction::TrendingRefresh => 4,
+            DaemonAction::FailedLogRetry => 5,
+            DaemonAction::ChangeOutbox => 6,
         }
     }
 
@@ -35,6 +51,9 @@ impl UniqueEvent<u64> for DaemonAction {
             DaemonAction::Launch => Some(0),
             DaemonAction::ChainSync => Some(0),
             DaemonAction::DownloadsRecount => Some(0),
+            DaemonAction::TrendingRefresh => Some(0),
+            DaemonAction::FailedLogRetry => Some(0),
+            DaemonAction::ChangeOutbox => Some(0),
             DaemonAction::Shutdown => Some(0),
         }
     }
@@ -46,11 +65,21 @@ pub type DaemonContex = Context<u64, DaemonAction>;
 #[derive(Clone)]
 pub struct DaemonEventHandler {
     chain: Arc<ChainSyncHandlerV0>,
+    recount: Arc<DownloadsRecountHandler>,
+    trending: Arc<TrendingHandler>,
+    failed_log_retry: Arc<FailedLogRetryHandler>,
+    change_outbox: Arc<ChangeOutboxHandler>,
 }
 
 impl DaemonEventHandler {
-    pub fn new(chain: Arc<ChainSyncHandlerV0>) -> Self {
-        Self { chain }
+    pub fn new(
+        chain: Arc<ChainSyncHandlerV0>,
+        recount: Arc<DownloadsRecountHandler>,
+        trending: Arc<TrendingHandler>,
+        failed_log_retry: Arc<FailedLogRetryHandler>,
+        change_outbox: Arc<ChangeOutboxHandler>,
+    ) -> Self {
+        Self { chain, recount, trending, failed_log_retry, change_outbox }
     }
 }
 
@@ -64,13 +93,35 @@ impl EventHandler<u64, DaemonAction> for DaemonEventHandler {
 
                 ctx.queue.push_parallel(DaemonAction::DownloadsRecount)
                     .await;
+
+                ctx.queue.push_parallel(DaemonAction::TrendingRefresh)
+                    .await;
+
+                ctx.queue.push_parallel(DaemonAction::FailedLogRetry)
+                    .await;
+
+                ctx.queue.push_parallel(DaemonAction::ChangeOutbox)
+                    .await;
             }
             DaemonAction::ChainSync => {
                 self.chain.handle(ctx.clone())
                     .await;
             }
             DaemonAction::DownloadsRecount => {
-                
+                self.recount.handle(ctx.clone())
+                    .await;
+            }
+            DaemonAction::TrendingRefresh => {
+                self.trending.handle(ctx.clone())
+                    .await;
+            }
+            DaemonAction::FailedLogRetry => {
+                self.failed_log_retry.handle(ctx.clone())
+                    .await;
+            }
+            DaemonAction::ChangeOutbox => {
+                self.change_outbox.handle(ctx.clone())
+                    .await;
             }
             DaemonAction::Shutdown => {
                 ctx.queue.async_shutdown()
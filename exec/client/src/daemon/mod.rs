@@ -1,6 +1,7 @@
 pub mod launcher;
 pub mod handler;
 pub mod data;
+pub mod cli;
 
 
 
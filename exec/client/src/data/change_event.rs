@@ -0,0 +1,159 @@
+use crate::data::id::{ChangeEventKind, Visibility};
+use crate::data::models::{NewAsset, NewBuildRequest, NewChangeEvent, NewOwnershipChange, NewPublisherApp};
+use chrono::{DateTime, Utc};
+use codegen_contracts::ext::ToChecksum;
+use codegen_change::change::change_event::Payload;
+use codegen_change::change::{AssetCreated, AssetVisibilityChanged, BuildValidated, ChangeEvent, OwnerTransferred};
+use prost::Message;
+
+// Ids of chain changes come from the chain itself, a replayed batch or a resync produces the same ids
+pub fn asset_created(app: &NewPublisherApp, block_number: Option<i64>) -> NewChangeEvent {
+    let payload = Payload::AssetCreated(AssetCreated {
+        asset_address: app.asset_address.clone(),
+        publisher_address: app.publisher_address.clone(),
+        package_name: app.package_name.clone(),
+        name: app.name.clone(),
+    });
+
+    return new_event(
+        format!("asset_created:{}", app.asset_address),
+        ChangeEventKind::AssetCreated,
+        &app.asset_address,
+        block_number,
+        payload,
+    );
+}
+
+// Apps without a publisher factory first show up with their asset row, the id is shared with the one above
+pub fn asset_listed(asset: &NewAsset, owner: &str, block_number: Option<i64>) -> NewChangeEvent {
+    let app = NewPublisherApp {
+        publisher_address: owner.to_string(),
+        asset_address: asset.address.checksum(),
+        package_name: asset.id.clone(),
+        name: asset.name.clone(),
+        block_number: block_number.unwrap_or_default(),
+    };
+
+    return asset_created(&app, block_number);
+}
+
+pub fn build_validated(request: &NewBuildRequest, block_number: Option<i64>) -> NewChangeEvent {
+    let payload = Payload::BuildValidated(BuildValidated {
+        asset_address: request.asset_address.clone(),
+        request_id: request.id,
+        version_code: request.version_code,
+        track_id: request.track_id.clone().into(),
+        block_id: request.block_id.unwrap_or_default(),
+    });
+
+    return new_event(
+        format!("build_validated:{}", request.id),
+        ChangeEventKind::BuildValidated,
+        &request.asset_address,
+        block_number,
+        payload,
+    );
+}
+
+pub fn owner_transferred(change: &NewOwnershipChange, block_number: Option<i64>) -> NewChangeEvent {
+    let payload = Payload::OwnerTransferred(OwnerTransferred {
+        asset_address: change.asset_address.clone(),
+        prev_owner: change.prev_owner.clone().unwrap_or_default(),
+        new_owner: change.new_owner.clone(),
+        tx_hash: change.tx_hash.clone(),
+        log_index: change.log_index,
+    });
+
+    return new_event(
+        format!("owner_transferred:{}:{}", change.tx_hash, change.log_index),
+        ChangeEventKind::OwnerTransferred,
+        &change.asset_address,
+        block_number,
+        payload,
+    );
+}
+
+// Moderation has no chain position, the change time keeps ids of repeated changes apart
+pub fn visibility_changed(
+    asset_id: i64,
+    address: &str,
+    visibility: Visibility,
+    reason: Option<&str>,
+    changed_at: DateTime<Utc>,
+) -> NewChangeEvent {
+    let payload = Payload::VisibilityChanged(AssetVisibilityChanged {
+        asset_address: address.to_string(),
+        asset_id,
+        visibility: visibility.into(),
+        reason: reason.unwrap_or_default().to_string(),
+    });
+
+    return new_event(
+        format!("visibility_changed:{}:{}", asset_id, changed_at.timestamp_micros()),
+        ChangeEventKind::VisibilityChanged,
+        address,
+        None,
+        payload,
+    );
+}
+
+fn new_event(
+    event_id: String,
+    kind: ChangeEventKind,
+    asset_address: &str,
+    block_number: Option<i64>,
+    payload: Payload,
+) -> NewChangeEvent {
+    let event = ChangeEvent {
+        id: event_id.clone(),
+        block_number: block_number.unwrap_or_default(),
+        created_at: Utc::now().timestamp_millis(),
+        payload: Some(payload),
+    };
+
+    return NewChangeEvent {
+        event_id,
+        kind,
+        asset_address: asset_address.to_string(),
+        payload: event.encode_to_vec(),
+        block_number,
+    };
+}
+
+#[test]
+fn check_change_event_ids() {
+    use crate::data::id::OwnershipChangeKind;
+
+    let change = NewOwnershipChange {
+        asset_address: "0xA".to_string(),
+        prev_owner: Some("0xB".to_string()),
+        new_owner: "0xC".to_string(),
+        kind: OwnershipChangeKind::Transferred,
+        tx_hash: "0xD".to_string(),
+        log_index: 3,
+        changed_at: None,
+    };
+
+    let first = owner_transferred(&change, Some(10));
+    let replayed = owner_transferred(&change, Some(10));
+    assert_eq!(first.event_id, "owner_transferred:0xD:3");
+    assert_eq!(first.event_id, replayed.event_id);
+    assert_eq!(first.kind, ChangeEventKind::OwnerTransferred);
+
+    let event = ChangeEvent::decode(first.payload.as_slice()).unwrap();
+    assert_eq!(event.id, first.event_id);
+    assert_eq!(event.block_number, 10);
+    assert_eq!(event.payload, Some(Payload::OwnerTransferred(OwnerTransferred {
+        asset_address: "0xA".to_string(),
+        prev_owner: "0xB".to_string(),
+        new_owner: "0xC".to_string(),
+        tx_hash: "0xD".to_string(),
+        log_index: 3,
+    })));
+
+    let changed_at = Utc::now();
+    let hidden = visibility_changed(7, "0xA", Visibility::Hidden, Some("spam"), changed_at);
+    let shown = visibility_changed(7, "0xA", Visibility::Visible, None, changed_at + chrono::Duration::seconds(1));
+    assert_ne!(hidden.event_id, shown.event_id);
+    assert_eq!(hidden.block_number, None);
+}
@@ -1,4 +1,8 @@
-use crate::data::models::{Artifact, BuildRequest, ValidationProof};
+use crate::data::id::{FeedLayout, ObjTypeId, PlatformId, TrackId};
+use crate::data::models::{Artifact, Asset, BuildRequest, Publisher, RatingSummary, RichAsset, UpdateCandidate, ValidationProof};
+use chrono::DateTime;
+use std::collections::BTreeSet;
+use crate::net::headers::ApiNamedVersion;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,3 +22,417 @@ pub struct AndroidPublishingResponse {
     pub published: Vec<DtoPublishing>,
     pub reviewing: Vec<BuildRequest>,
 }
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetDetails<T> {
+    #[serde(flatten)]
+    pub asset: T,
+    pub rating_summary: RatingSummary,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingDto {
+    pub average: f32,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationDto {
+    pub ownership: bool,
+    pub build: bool,
+    pub os: bool,
+}
+
+// Asset shape since api v2: named platform/type, nested rating and no internal flags
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetV2 {
+    pub id: i64,
+    pub name: String,
+    pub package_name: String,
+    pub address: String,
+
+    pub website: Option<String>,
+    pub logo: Option<String>,
+    pub description: Option<String>,
+
+    pub category_id: i32,
+    pub platform: PlatformId,
+    #[serde(rename = "type")]
+    pub type_id: ObjTypeId,
+
+    pub is_os_verified: bool,
+
+    pub rating: RatingDto,
+    pub price: i64,
+    pub downloads: i64,
+}
+
+impl From<Asset> for AssetV2 {
+    fn from(asset: Asset) -> Self {
+        return AssetV2 {
+            id: asset.id,
+            name: asset.name,
+            package_name: asset.package_name,
+            address: asset.address,
+            website: asset.website,
+            logo: asset.logo,
+            description: asset.description,
+            category_id: asset.category_id,
+            platform: PlatformId::from(asset.platform_id),
+            type_id: ObjTypeId::from(asset.type_id),
+            is_os_verified: asset.is_os_verified,
+            rating: RatingDto { average: asset.rating, count: asset.rating_count },
+            price: asset.price,
+            downloads: asset.downloads,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RichAssetV2 {
+    pub id: i64,
+    pub name: String,
+    pub package_name: String,
+    pub address: String,
+
+    pub website: Option<String>,
+    pub logo: Option<String>,
+    pub description: Option<String>,
+
+    pub category_id: i32,
+    pub platform: PlatformId,
+    #[serde(rename = "type")]
+    pub type_id: ObjTypeId,
+
+    pub verification: VerificationDto,
+
+    pub rating: RatingDto,
+    pub price: i64,
+    pub downloads: i64,
+}
+
+impl From<RichAsset> for RichAssetV2 {
+    fn from(asset: RichAsset) -> Self {
+        return RichAssetV2 {
+            id: asset.id,
+            name: asset.name,
+            package_name: asset.package_name,
+            address: asset.address,
+            website: asset.website,
+            logo: asset.logo,
+            description: asset.description,
+            category_id: asset.category_id,
+            platform: PlatformId::from(asset.platform_id),
+            type_id: ObjTypeId::from(asset.type_id),
+            verification: VerificationDto {
+                ownership: asset.is_ownership_verified,
+                build: asset.is_build_verified,
+                os: asset.is_os_verified,
+            },
+            rating: RatingDto { average: asset.rating, count: asset.rating_count },
+            price: asset.price,
+            downloads: asset.downloads,
+        }
+    }
+}
+
+// Serializes an asset in the shape of the negotiated api version
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum AssetDto {
+    V1(Asset),
+    V2(AssetV2),
+}
+
+impl AssetDto {
+    pub fn versioned(asset: Asset, version: u32) -> Self {
+        if version.is_protocol_zero() {
+            return AssetDto::V1(asset);
+        }
+
+        return AssetDto::V2(AssetV2::from(asset));
+    }
+
+    pub fn versioned_list(assets: Vec<Asset>, version: u32) -> Vec<Self> {
+        return assets.into_iter()
+            .map(|asset| AssetDto::versioned(asset, version))
+            .collect();
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum RichAssetDto {
+    V1(RichAsset),
+    V2(RichAssetV2),
+}
+
+impl RichAssetDto {
+    pub fn versioned(asset: RichAsset, version: u32) -> Self {
+        if version.is_protocol_zero() {
+            return RichAssetDto::V1(asset);
+        }
+
+        return RichAssetDto::V2(RichAssetV2::from(asset));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedTitle {
+    // Localization key resolved by clients
+    #[serde(rename = "type")]
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedSectionDto {
+    #[serde(rename = "type")]
+    pub layout: FeedLayout,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<FeedTitle>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub covers: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<AssetDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<AssetDto>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedDto {
+    pub sections: Vec<FeedSectionDto>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableUpdate {
+    pub asset_id: i64,
+    pub address: String,
+    pub package_name: String,
+    pub track: TrackId,
+    pub artifact: Artifact,
+    // False when the signing certificate of the build isn't synced yet
+    pub signature_verified: bool,
+}
+
+// Newer build is signed by another certificate, so it can't be installed over the app
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureMismatch {
+    pub asset_id: i64,
+    pub address: String,
+    pub package_name: String,
+    pub version_code: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateCheckDto {
+    pub updates: Vec<AvailableUpdate>,
+    pub mismatched: Vec<SignatureMismatch>,
+}
+
+impl From<Vec<UpdateCandidate>> for UpdateCheckDto {
+    fn from(candidates: Vec<UpdateCandidate>) -> Self {
+        let mut updates = vec![];
+        let mut mismatched = vec![];
+
+        for candidate in candidates {
+            if candidate.is_signature_match != Some(false) {
+                updates.push(AvailableUpdate {
+                    asset_id: candidate.asset_id,
+                    address: candidate.address,
+                    package_name: candidate.package_name,
+                    track: candidate.track,
+                    artifact: candidate.artifact,
+                    signature_verified: candidate.is_signature_match.is_some(),
+                });
+            } else {
+                mismatched.push(SignatureMismatch {
+                    asset_id: candidate.asset_id,
+                    address: candidate.address,
+                    package_name: candidate.package_name,
+                    version_code: candidate.artifact.version_code,
+                });
+            }
+        }
+
+        return Self { updates, mismatched };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublisherStatsDto {
+    pub apps: i64,
+    pub downloads: i64,
+    pub rating: RatingDto,
+}
+
+impl PublisherStatsDto {
+    // Average is weighted by the number of ratings, so a single 5 star app doesn't dominate
+    pub fn from_apps(apps: &[Asset]) -> Self {
+        let count: i64 = apps.iter().map(|app| app.rating_count).sum();
+        let total: f64 = apps.iter().map(|app| app.rating as f64 * app.rating_count as f64).sum();
+        let average = if count > 0 { (total / count as f64) as f32 } else { 0.0 };
+
+        return PublisherStatsDto {
+            apps: apps.len() as i64,
+            downloads: apps.iter().map(|app| app.downloads).sum(),
+            rating: RatingDto { average, count },
+        };
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublisherDto {
+    pub address: String,
+    pub owner: String,
+    pub name: String,
+    pub created_at: DateTime<chrono::Utc>,
+    // Domains verified by assetlinks of the listed apps
+    pub domains: Vec<String>,
+    pub stats: PublisherStatsDto,
+    pub apps: Vec<AssetDto>,
+}
+
+impl PublisherDto {
+    pub fn versioned(publisher: Publisher, apps: Vec<Asset>, version: u32) -> Self {
+        let domains = apps.iter()
+            .filter_map(|app| app.website.clone())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect();
+
+        return PublisherDto {
+            address: publisher.address,
+            owner: publisher.owner,
+            name: publisher.name,
+            created_at: publisher.created_at,
+            domains,
+            stats: PublisherStatsDto::from_apps(&apps),
+            apps: AssetDto::versioned_list(apps, version),
+        };
+    }
+}
+
+#[test]
+fn check_publisher_stats() {
+    let app = |rating: f32, rating_count: i64, downloads: i64| Asset {
+        id: 1,
+        name: "App".to_string(),
+        package_name: "com.app".to_string(),
+        address: "0x1".to_string(),
+        website: None,
+        logo: None,
+        description: None,
+        category_id: 1,
+        platform_id: 1,
+        type_id: 1,
+        is_os_verified: false,
+        is_hidden: false,
+        rating,
+        rating_count,
+        price: 0,
+        downloads,
+    };
+
+    let stats = PublisherStatsDto::from_apps(&[app(5.0, 1, 10), app(3.0, 3, 20), app(0.0, 0, 5)]);
+    assert_eq!(stats.apps, 3);
+    assert_eq!(stats.downloads, 35);
+    assert_eq!(stats.rating, RatingDto { average: 3.5, count: 4 });
+
+    let stats = PublisherStatsDto::from_apps(&[]);
+    assert_eq!(stats.rating, RatingDto { average: 0.0, count: 0 });
+}
+
+#[test]
+fn check_versioned_asset_shapes() {
+    use serde_json::json;
+
+    let asset = Asset {
+        id: 7,
+        name: "App".to_string(),
+        package_name: "com.app".to_string(),
+        address: "0x7".to_string(),
+        website: Some("app.com".to_string()),
+        logo: None,
+        description: None,
+        category_id: 3,
+        platform_id: 1,
+        type_id: 2,
+        is_os_verified: true,
+        is_hidden: false,
+        rating: 4.5,
+        rating_count: 2,
+        price: 0,
+        downloads: 10,
+    };
+
+    let v1 = serde_json::to_value(AssetDto::versioned(asset.clone(), 1)).unwrap();
+    assert_eq!(v1, json!({
+        "id": 7, "name": "App", "packageName": "com.app", "address": "0x7",
+        "website": "app.com", "logo": null, "description": null,
+        "categoryId": 3, "platformId": 1, "typeId": 2,
+        "isOsVerified": true, "isHidden": false,
+        "rating": 4.5, "ratingCount": 2, "price": 0, "downloads": 10
+    }));
+
+    let v2 = serde_json::to_value(AssetDto::versioned(asset, 2)).unwrap();
+    assert_eq!(v2, json!({
+        "id": 7, "name": "App", "packageName": "com.app", "address": "0x7",
+        "website": "app.com", "logo": null, "description": null,
+        "categoryId": 3, "platform": "android", "type": "game",
+        "isOsVerified": true,
+        "rating": { "average": 4.5, "count": 2 }, "price": 0, "downloads": 10
+    }));
+
+    let rich = RichAsset {
+        id: 7,
+        name: "App".to_string(),
+        package_name: "com.app".to_string(),
+        address: "0x7".to_string(),
+        website: None,
+        logo: Some("logo.png".to_string()),
+        description: Some("About".to_string()),
+        category_id: 3,
+        platform_id: 1,
+        type_id: 1,
+        is_ownership_verified: true,
+        is_build_verified: false,
+        is_os_verified: true,
+        rating: 0.0,
+        rating_count: 0,
+        price: 5,
+        downloads: 0,
+    };
+
+    let v1 = serde_json::to_value(RichAssetDto::versioned(rich.clone(), 1)).unwrap();
+    assert_eq!(v1, json!({
+        "id": 7, "name": "App", "packageName": "com.app", "address": "0x7",
+        "website": null, "logo": "logo.png", "description": "About",
+        "categoryId": 3, "platformId": 1, "typeId": 1,
+        "isOwnershipVerified": true, "isBuildVerified": false, "isOsVerified": true,
+        "rating": 0.0, "ratingCount": 0, "price": 5, "downloads": 0
+    }));
+
+    let v2 = serde_json::to_value(RichAssetDto::versioned(rich, 2)).unwrap();
+    assert_eq!(v2, json!({
+        "id": 7, "name": "App", "packageName": "com.app", "address": "0x7",
+        "website": null, "logo": "logo.png", "description": "About",
+        "categoryId": 3, "platform": "android", "type": "app",
+        "verification": { "ownership": true, "build": false, "os": true },
+        "rating": { "average": 0.0, "count": 0 }, "price": 5, "downloads": 0
+    }));
+}
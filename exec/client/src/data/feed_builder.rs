@@ -0,0 +1,186 @@
+use crate::data::dto::{AssetDto, FeedDto, FeedSectionDto, FeedTitle};
+use crate::data::id::{ChartSort, FeedLayout, FeedSource, ObjTypeId, PlatformId};
+use crate::data::models::{Asset, FeedSection};
+use crate::data::repo::feed_repo::FeedRepo;
+use crate::data::repo::object_repo::ObjectRepo;
+use crate::result::ClientResult;
+use std::sync::Arc;
+
+// Composes the home feed from `feed_section` definitions, only listed published assets get in
+pub struct FeedBuilder {
+    feed_repo: Arc<FeedRepo>,
+    object_repo: Arc<ObjectRepo>,
+}
+
+impl FeedBuilder {
+
+    pub fn new(feed_repo: Arc<FeedRepo>, object_repo: Arc<ObjectRepo>) -> Self {
+        Self { feed_repo, object_repo }
+    }
+
+    pub async fn build(&self, type_id: &ObjTypeId, platform: PlatformId, version: u32) -> ClientResult<FeedDto> {
+        let definitions = self.feed_repo.find_sections(type_id)
+            .await?;
+
+        let mut sections = Vec::with_capacity(definitions.len());
+        for definition in definitions {
+            let assets = self.find_assets(&definition, type_id, platform)
+                .await?;
+
+            if let Some(section) = compose_section(definition, assets, version) {
+                sections.push(section);
+            }
+        }
+
+        return Ok(FeedDto { sections });
+    }
+
+    async fn find_assets(
+        &self,
+        section: &FeedSection,
+        type_id: &ObjTypeId,
+        platform: PlatformId,
+    ) -> ClientResult<Vec<Asset>> {
+        let platform_id: i32 = platform.into();
+        let limit = section.size as i64;
+
+        let assets = match FeedSource::from(section.source) {
+            FeedSource::None => vec![],
+            FeedSource::Editorial => {
+                self.object_repo
+                    .find_listed_by_ids(platform_id, section.asset_ids.as_slice(), limit)
+                    .await?
+            }
+            FeedSource::NewReleases => {
+                self.object_repo
+                    .find_new_releases(platform_id, type_id, limit)
+                    .await?
+            }
+            FeedSource::RecentlyUpdated => {
+                self.object_repo
+                    .find_recently_updated(platform_id, type_id, limit)
+                    .await?
+            }
+            FeedSource::TopChart => {
+                self.object_repo
+                    .chart_by_app_type(platform_id, Some(type_id.clone()), ChartSort::Downloads, None, limit, 0)
+                    .await?
+                    .into_iter()
+                    .map(Asset::from)
+                    .collect()
+            }
+            FeedSource::TopCategory => {
+                let Some(category_id) = section.category_ids.first() else {
+                    return Ok(vec![]);
+                };
+
+                self.object_repo
+                    .chart_by_category(platform_id, *category_id, ChartSort::Downloads, None, limit, 0)
+                    .await?
+                    .into_iter()
+                    .map(Asset::from)
+                    .collect()
+            }
+        };
+
+        return Ok(assets);
+    }
+}
+
+// Asset sections without assets are dropped, so the feed never shows empty rails
+fn compose_section(section: FeedSection, assets: Vec<Asset>, version: u32) -> Option<FeedSectionDto> {
+    let layout = FeedLayout::from(section.layout);
+    if layout == FeedLayout::Unspecified {
+        return None;
+    }
+
+    if layout.has_assets() && assets.is_empty() {
+        return None;
+    }
+
+    let category_id = match FeedSource::from(section.source) {
+        FeedSource::TopCategory => section.category_ids.first().copied(),
+        _ => None,
+    };
+
+    let title = section.title_key
+        .map(|key| FeedTitle { key, category_id });
+
+    let mut objects = AssetDto::versioned_list(assets, version);
+    let target = match layout {
+        FeedLayout::Highlight => Some(objects.remove(0)),
+        _ => None,
+    };
+
+    if !layout.has_assets() {
+        objects.clear();
+    }
+
+    let categories = match layout {
+        FeedLayout::Categories => section.category_ids,
+        _ => vec![],
+    };
+
+    return Some(FeedSectionDto {
+        layout,
+        title,
+        covers: section.covers,
+        objects: if target.is_some() { vec![] } else { objects },
+        target,
+        categories,
+    });
+}
+
+#[test]
+fn check_compose_section() {
+    let section = |layout: FeedLayout, source: FeedSource| FeedSection {
+        id: 1,
+        type_id: 1,
+        position: 0,
+        layout: layout.into(),
+        source: source.into(),
+        title_key: Some("best_in_category".to_string()),
+        covers: vec![],
+        asset_ids: vec![],
+        category_ids: vec![8],
+        size: 10,
+    };
+
+    let asset = |id: i64| Asset {
+        id,
+        name: format!("App {}", id),
+        package_name: format!("org.app{}", id),
+        address: format!("0x{}", id),
+        website: None,
+        logo: None,
+        description: None,
+        category_id: 8,
+        platform_id: 1,
+        type_id: 1,
+        is_os_verified: false,
+        is_hidden: false,
+        rating: 0.0,
+        rating_count: 0,
+        price: 0,
+        downloads: 0,
+    };
+
+    let empty = compose_section(section(FeedLayout::HList, FeedSource::TopCategory), vec![], 1);
+    assert!(empty.is_none());
+
+    let list = compose_section(section(FeedLayout::HList, FeedSource::TopCategory), vec![asset(1), asset(2)], 1).unwrap();
+    let json = serde_json::to_value(&list).unwrap();
+    assert_eq!(json["type"], "h_list");
+    assert_eq!(json["title"], serde_json::json!({ "type": "best_in_category", "categoryId": 8 }));
+    assert_eq!(json["objects"].as_array().unwrap().len(), 2);
+    assert!(json.get("categories").is_none());
+
+    let highlight = compose_section(section(FeedLayout::Highlight, FeedSource::Editorial), vec![asset(3), asset(4)], 1).unwrap();
+    let json = serde_json::to_value(&highlight).unwrap();
+    assert_eq!(json["target"]["id"], 3);
+    assert!(json.get("objects").is_none());
+
+    let categories = compose_section(section(FeedLayout::Categories, FeedSource::None), vec![], 1).unwrap();
+    let json = serde_json::to_value(&categories).unwrap();
+    assert_eq!(json["categories"], serde_json::json!([8]));
+}
@@ -126,6 +126,7 @@ impl From<i32> for CategoryId {
     }
 }
 
+// Ordered from the most stable one, a track also serves builds of every more stable track
 #[derive(Debug, Clone, Display, Deserialize, Serialize, Type)]
 #[repr(i32)]
 pub enum TrackId {
@@ -141,6 +142,9 @@ pub enum TrackId {
     #[display("alpha")]
     #[serde(rename = "alpha")]
     Alpha = 3,
+    #[display("internal")]
+    #[serde(rename = "internal")]
+    Internal = 4,
 }
 
 impl Into<i32> for TrackId {
@@ -155,6 +159,7 @@ impl From<i32> for TrackId {
             1 => Self::Release,
             2 => Self::Beta,
             3 => Self::Alpha,
+            4 => Self::Internal,
             _ => Self::Unspecified,
         }
     }
@@ -227,7 +232,7 @@ impl From<i32> for PlatformId {
 }
 
 
-#[derive(Debug, Clone, Display, Deserialize, Serialize, Type)]
+#[derive(Debug, Clone, PartialEq, Eq, Display, Deserialize, Serialize, Type)]
 #[repr(i32)]
 pub enum ObjTypeId {
     #[display("unspecified")]
@@ -292,4 +297,425 @@ impl From<u8> for ReqTypeId {
             _ => Self::Unspecified,
         }
     }
-}
\ No newline at end of file
+}
+// Sent as its number, unknown ones become `Unspecified`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Deserialize, Serialize)]
+#[serde(from = "i32", into = "i32")]
+#[repr(i32)]
+pub enum ReportCategory {
+    #[display("unspecified")]
+    Unspecified = 0,
+    #[display("trafficking_narcotics")]
+    TraffickingNarcotics = 1,
+    #[display("trafficking_counterfeit")]
+    TraffickingCounterfeit = 2,
+    #[display("trafficking_stolen_goods")]
+    TraffickingStolenGoods = 3,
+    #[display("cybercrime_malware")]
+    CybercrimeMalware = 4,
+    #[display("cybercrime_hacking")]
+    CybercrimeHacking = 5,
+    #[display("cybercrime_carding")]
+    CybercrimeCarding = 6,
+    #[display("financial_crime_laundering")]
+    FinancialCrimeLaundering = 7,
+    #[display("financial_crime_fraud")]
+    FinancialCrimeFraud = 8,
+    #[display("financial_crime_stolen_data")]
+    FinancialCrimeStolenData = 9,
+    #[display("exploitation_csam")]
+    ExploitationCsam = 10,
+    #[display("exploitation_human_trafficking")]
+    ExploitationHumanTrafficking = 11,
+    #[display("exploitation_violence_for_hire")]
+    ExploitationViolenceForHire = 12,
+    #[display("exploitation_terrorism")]
+    ExploitationTerrorism = 13,
+    #[display("privacy_doxing")]
+    PrivacyDoxing = 14,
+    #[display("privacy_surveillance")]
+    PrivacySurveillance = 15,
+}
+
+impl Into<i32> for ReportCategory {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<i32> for ReportCategory {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::TraffickingNarcotics,
+            2 => Self::TraffickingCounterfeit,
+            3 => Self::TraffickingStolenGoods,
+            4 => Self::CybercrimeMalware,
+            5 => Self::CybercrimeHacking,
+            6 => Self::CybercrimeCarding,
+            7 => Self::FinancialCrimeLaundering,
+            8 => Self::FinancialCrimeFraud,
+            9 => Self::FinancialCrimeStolenData,
+            10 => Self::ExploitationCsam,
+            11 => Self::ExploitationHumanTrafficking,
+            12 => Self::ExploitationViolenceForHire,
+            13 => Self::ExploitationTerrorism,
+            14 => Self::PrivacyDoxing,
+            15 => Self::PrivacySurveillance,
+            _ => Self::Unspecified,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Deserialize, Serialize)]
+#[repr(i32)]
+pub enum ReportStatus {
+    #[display("open")]
+    #[serde(rename = "open")]
+    Open = 0,
+    #[display("triaged")]
+    #[serde(rename = "triaged")]
+    Triaged = 1,
+    #[display("actioned")]
+    #[serde(rename = "actioned")]
+    Actioned = 2,
+    #[display("dismissed")]
+    #[serde(rename = "dismissed")]
+    Dismissed = 3,
+}
+
+impl ReportStatus {
+    pub fn is_pending(&self) -> bool {
+        return *self == ReportStatus::Open || *self == ReportStatus::Triaged;
+    }
+
+    // Open reports are triaged or closed, triaged ones are closed, closed ones stay as they are
+    pub fn can_move_to(&self, next: &ReportStatus) -> bool {
+        return match (self, next) {
+            (ReportStatus::Open, ReportStatus::Triaged | ReportStatus::Actioned | ReportStatus::Dismissed) => true,
+            (ReportStatus::Triaged, ReportStatus::Actioned | ReportStatus::Dismissed) => true,
+            _ => false,
+        };
+    }
+}
+
+impl Into<i32> for ReportStatus {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<i32> for ReportStatus {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Triaged,
+            2 => Self::Actioned,
+            3 => Self::Dismissed,
+            _ => Self::Open,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display, Deserialize, Serialize)]
+pub enum ChartSort {
+    #[default]
+    #[display("downloads")]
+    #[serde(rename = "downloads")]
+    Downloads,
+    #[display("rating")]
+    #[serde(rename = "rating")]
+    Rating,
+    #[display("trending")]
+    #[serde(rename = "trending")]
+    Trending,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Deserialize, Serialize)]
+#[repr(i32)]
+pub enum Visibility {
+    #[display("visible")]
+    #[serde(rename = "visible")]
+    Visible = 0,
+    #[display("hidden")]
+    #[serde(rename = "hidden")]
+    Hidden = 1,
+    #[display("blocked")]
+    #[serde(rename = "blocked")]
+    Blocked = 2,
+}
+
+impl Into<i32> for Visibility {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<i32> for Visibility {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Visible,
+            2 => Self::Blocked,
+            _ => Self::Hidden,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Deserialize, Serialize)]
+#[repr(i32)]
+pub enum FeedLayout {
+    #[display("unspecified")]
+    #[serde(rename = "unspecified")]
+    Unspecified = 0,
+    #[display("banner")]
+    #[serde(rename = "banner")]
+    Banner = 1,
+    #[display("h_list")]
+    #[serde(rename = "h_list")]
+    HList = 2,
+    #[display("v_list")]
+    #[serde(rename = "v_list")]
+    VList = 3,
+    #[display("highlight")]
+    #[serde(rename = "highlight")]
+    Highlight = 4,
+    #[display("categories")]
+    #[serde(rename = "categories")]
+    Categories = 5,
+    #[display("header")]
+    #[serde(rename = "header")]
+    Header = 6,
+}
+
+impl FeedLayout {
+    // Sections of these layouts are dropped when their source has no assets
+    pub fn has_assets(&self) -> bool {
+        return matches!(self, Self::Banner | Self::HList | Self::VList | Self::Highlight);
+    }
+}
+
+impl Into<i32> for FeedLayout {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<i32> for FeedLayout {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Banner,
+            2 => Self::HList,
+            3 => Self::VList,
+            4 => Self::Highlight,
+            5 => Self::Categories,
+            6 => Self::Header,
+            _ => Self::Unspecified,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Deserialize, Serialize)]
+#[repr(i32)]
+pub enum FeedSource {
+    #[display("none")]
+    #[serde(rename = "none")]
+    None = 0,
+    // Curated `asset_ids` in the given order
+    #[display("editorial")]
+    #[serde(rename = "editorial")]
+    Editorial = 1,
+    #[display("new_releases")]
+    #[serde(rename = "new_releases")]
+    NewReleases = 2,
+    #[display("recently_updated")]
+    #[serde(rename = "recently_updated")]
+    RecentlyUpdated = 3,
+    #[display("top_chart")]
+    #[serde(rename = "top_chart")]
+    TopChart = 4,
+    // Top of the first of `category_ids`
+    #[display("top_category")]
+    #[serde(rename = "top_category")]
+    TopCategory = 5,
+}
+
+impl Into<i32> for FeedSource {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<i32> for FeedSource {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Editorial,
+            2 => Self::NewReleases,
+            3 => Self::RecentlyUpdated,
+            4 => Self::TopChart,
+            5 => Self::TopCategory,
+            _ => Self::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Deserialize, Serialize)]
+#[repr(i32)]
+pub enum AdminRole {
+    #[display("unspecified")]
+    #[serde(rename = "unspecified")]
+    Unspecified = 0,
+    #[display("moderator")]
+    #[serde(rename = "moderator")]
+    Moderator = 1,
+    #[display("curator")]
+    #[serde(rename = "curator")]
+    Curator = 2,
+    #[display("admin")]
+    #[serde(rename = "admin")]
+    Admin = 3,
+}
+
+impl AdminRole {
+    pub fn allows(&self, required: AdminRole) -> bool {
+        if *self == AdminRole::Unspecified {
+            return false
+        }
+
+        return *self == AdminRole::Admin || *self == required;
+    }
+}
+
+impl Into<i32> for AdminRole {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<i32> for AdminRole {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Moderator,
+            2 => Self::Curator,
+            3 => Self::Admin,
+            _ => Self::Unspecified,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Deserialize, Serialize)]
+#[repr(i32)]
+pub enum FailedLogKind {
+    // Log can't be decoded, retrying only helps after a handler fix
+    #[display("fatal")]
+    #[serde(rename = "fatal")]
+    Fatal = 0,
+    // Data behind the log couldn't be fetched or stored
+    #[display("error")]
+    #[serde(rename = "error")]
+    Error = 1,
+}
+
+impl Into<i32> for FailedLogKind {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<i32> for FailedLogKind {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Fatal,
+            _ => Self::Error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Deserialize, Serialize)]
+#[repr(i32)]
+pub enum FailedLogStatus {
+    #[display("pending")]
+    #[serde(rename = "pending")]
+    Pending = 0,
+    #[display("resolved")]
+    #[serde(rename = "resolved")]
+    Resolved = 1,
+    // Out of attempts, only re-driven by an operator
+    #[display("dead")]
+    #[serde(rename = "dead")]
+    Dead = 2,
+}
+
+impl Into<i32> for FailedLogStatus {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<i32> for FailedLogStatus {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Resolved,
+            2 => Self::Dead,
+            _ => Self::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Deserialize, Serialize)]
+#[repr(i32)]
+pub enum OwnershipChangeKind {
+    // App deployed by a publisher account, the account is its first owner
+    #[display("created")]
+    #[serde(rename = "created")]
+    Created = 0,
+    #[display("transferred")]
+    #[serde(rename = "transferred")]
+    Transferred = 1,
+}
+
+impl Into<i32> for OwnershipChangeKind {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<i32> for OwnershipChangeKind {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Created,
+            _ => Self::Transferred,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Deserialize, Serialize)]
+#[repr(i32)]
+pub enum ChangeEventKind {
+    #[display("asset_created")]
+    #[serde(rename = "asset_created")]
+    AssetCreated = 0,
+    #[display("build_validated")]
+    #[serde(rename = "build_validated")]
+    BuildValidated = 1,
+    #[display("visibility_changed")]
+    #[serde(rename = "visibility_changed")]
+    VisibilityChanged = 2,
+    #[display("owner_transferred")]
+    #[serde(rename = "owner_transferred")]
+    OwnerTransferred = 3,
+}
+
+impl Into<i32> for ChangeEventKind {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<i32> for ChangeEventKind {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::AssetCreated,
+            1 => Self::BuildValidated,
+            2 => Self::VisibilityChanged,
+            _ => Self::OwnerTransferred,
+        }
+    }
+}
@@ -2,3 +2,5 @@ pub mod repo;
 pub mod models;
 pub mod dto;
 pub mod id;
+pub mod feed_builder;
+pub mod change_event;
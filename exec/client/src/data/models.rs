@@ -1,4 +1,4 @@
-use crate::data::id::{CategoryId, ObjTypeId, PlatformId, ReqTypeId, TrackId};
+use crate::data::id::{AdminRole, CategoryId, ChangeEventKind, FeedLayout, FeedSource, ObjTypeId, OwnershipChangeKind, PlatformId, ReportCategory, ReqTypeId, TrackId};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::hash::{Hash, Hasher};
@@ -68,6 +68,7 @@ pub struct RichAsset {
     pub is_os_verified: bool,
 
     pub rating: f32,
+    pub rating_count: i64,
     pub price: i64,
     pub downloads: i64,
 }
@@ -92,6 +93,7 @@ pub struct Asset {
     pub is_hidden: bool,
 
     pub rating: f32,
+    pub rating_count: i64,
     pub price: i64,
     pub downloads: i64,
 }
@@ -113,12 +115,110 @@ impl Hash for Asset {
 
         // Hash the bits of the f32
         self.rating.to_bits().hash(state);
+        self.rating_count.hash(state);
 
         self.price.hash(state);
         self.downloads.hash(state);
     }
 }
 
+// Chart row, `chart_position` is the curated position or `i32::MAX` for not pinned assets
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct ChartAsset {
+    pub id: i64,
+    pub name: String,
+    pub package_name: String,
+    pub address: String,
+
+    pub website: Option<String>,
+    pub logo: Option<String>,
+    pub description: Option<String>,
+
+    pub category_id: i32,
+    pub platform_id: i32,
+    pub type_id: i32,
+
+    pub is_os_verified: bool,
+    pub is_hidden: bool,
+
+    pub rating: f32,
+    pub rating_count: i64,
+    pub price: i64,
+    pub downloads: i64,
+
+    pub chart_position: i32,
+    pub trending_score: f32,
+}
+
+impl From<ChartAsset> for Asset {
+    fn from(asset: ChartAsset) -> Self {
+        return Asset {
+            id: asset.id,
+            name: asset.name,
+            package_name: asset.package_name,
+            address: asset.address,
+            website: asset.website,
+            logo: asset.logo,
+            description: asset.description,
+            category_id: asset.category_id,
+            platform_id: asset.platform_id,
+            type_id: asset.type_id,
+            is_os_verified: asset.is_os_verified,
+            is_hidden: asset.is_hidden,
+            rating: asset.rating,
+            rating_count: asset.rating_count,
+            price: asset.price,
+            downloads: asset.downloads,
+        }
+    }
+}
+
+// Keyset of the last chart row, `rating` and `score` are 0 unless the chart is sorted by them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartCursor {
+    pub position: i32,
+    pub rating: f32,
+    #[serde(default)]
+    pub score: f32,
+    pub downloads: i64,
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchCursor {
+    pub downloads: i64,
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewCursor {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionCursor {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OwnershipCursor {
+    pub block_number: i64,
+    pub log_index: i64,
+}
+
+// Synced rows of one asset as json, compared before and after a resync
+#[derive(Debug, Clone, FromRow)]
+pub struct AssetState {
+    pub address: String,
+    pub state: String,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct AssetVisibility {
+    pub visibility: i32,
+    pub visibility_reason: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NewAsset {
@@ -153,6 +253,18 @@ pub struct ValidationProof {
     pub asset_address: String,
     pub owner_version: i64,
     pub status: i32,
+    pub fingerprints: Vec<String>,
+}
+
+// Result of one request in a finalized validation block
+#[derive(Debug, Clone)]
+pub struct RequestValidation {
+    pub request_id: i64,
+    pub asset_address: String,
+    pub version_code: i64,
+    pub owner_version: i64,
+    pub status: i32,
+    pub file_hash: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -173,6 +285,8 @@ pub struct NewBuildRequest {
     pub version_code: i64,
     pub owner_version: u64,
     pub created_at: Option<DateTime<chrono::Utc>>,
+    pub block_id: Option<i64>,
+    pub finalized_at: Option<DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -204,6 +318,17 @@ pub struct NewReview {
     pub user_id: String,
     pub rating: i32,
     pub text: Option<String>,
+    pub signature: String,
+    pub signed_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingSummary {
+    pub average: f32,
+    pub count: i64,
+    // Number of reviews per rating, index 0 is for 1 star
+    pub histogram: Vec<i64>,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, FromRow, Serialize, Deserialize)]
@@ -215,18 +340,31 @@ pub struct Report {
     pub subcategory_id: i32,
     pub email: String,
     pub description: Option<String>,
+    pub status: i32,
+    pub note: Option<String>,
+    pub created_at: DateTime<chrono::Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NewReport {
     pub asset_address: String,
-    pub category_id: i32,
+    pub category_id: ReportCategory,
     pub subcategory_id: i32,
     pub email: String,
     pub description: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlaggedAsset {
+    pub id: i64,
+    pub name: String,
+    pub address: String,
+    pub visibility: i32,
+    pub pending_reports: i64,
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, FromRow, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Artifact {
@@ -240,6 +378,68 @@ pub struct Artifact {
     pub checksum: String,
 }
 
+// One build request of the asset, artifact fields are empty until the build is uploaded
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetVersion {
+    pub id: i64,
+    pub track_id: TrackId,
+    pub version_code: i64,
+    pub version_name: Option<String>,
+    pub checksum: Option<String>,
+    pub size: Option<i64>,
+    pub status: Option<i32>,
+    pub proof_status: Option<i32>,
+    pub block_id: Option<i64>,
+    pub created_at: DateTime<chrono::Utc>,
+    pub finalized_at: Option<DateTime<chrono::Utc>>,
+}
+
+// Owner change of a listed asset, `prev_owner` is empty for the created app
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnershipChange {
+    pub id: i64,
+    pub kind: OwnershipChangeKind,
+    pub prev_owner: Option<String>,
+    pub new_owner: String,
+    pub tx_hash: String,
+    pub block_number: i64,
+    #[serde(skip)]
+    pub log_index: i64,
+    pub changed_at: Option<DateTime<chrono::Utc>>,
+}
+
+// Installed app as reported by the store client, `fingerprint` is normalized
+#[derive(Debug, Clone)]
+pub struct InstalledApp {
+    pub package_name: Option<String>,
+    pub address: Option<String>,
+    pub version_code: i64,
+    pub fingerprint: String,
+}
+
+// Newest build above the installed version, `index` points back to the installed app
+#[derive(Debug, Clone)]
+pub struct UpdateCandidate {
+    pub index: i64,
+    pub asset_id: i64,
+    pub address: String,
+    pub package_name: String,
+    pub track: TrackId,
+    // None when the proof has no fingerprints
+    pub is_signature_match: Option<bool>,
+    pub artifact: Artifact,
+}
+
+// Build served for the requested track, `track` is where it is actually published
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackArtifact {
+    pub track: TrackId,
+    pub artifact: Artifact,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NewArtifact {
@@ -252,6 +452,156 @@ pub struct NewArtifact {
     pub checksum: String,
 }
 
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub id: i64,
+    pub actor: String,
+    pub role: i32,
+    pub action: String,
+    pub target: String,
+    pub payload: Option<String>,
+    pub created_at: DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAuditEntry {
+    pub actor: String,
+    pub role: AdminRole,
+    pub action: String,
+    pub target: String,
+    pub payload: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedLog {
+    pub id: i64,
+    pub tx_hash: String,
+    pub log_index: i64,
+    pub block_number: i64,
+    pub topic0: Option<String>,
+    pub log: String,
+    pub kind: i32,
+    pub error: String,
+    pub status: i32,
+    pub attempts: i32,
+    pub next_retry_at: DateTime<chrono::Utc>,
+    pub created_at: DateTime<chrono::Utc>,
+    pub updated_at: DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChartOrder {
+    pub platform_id: i32,
+    pub category_id: i32,
+    pub asset_id: i64,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedSection {
+    pub id: i64,
+    pub type_id: i32,
+    pub position: i32,
+    pub layout: i32,
+    pub source: i32,
+    pub title_key: Option<String>,
+    pub covers: Vec<String>,
+    pub asset_ids: Vec<i64>,
+    pub category_ids: Vec<i32>,
+    pub size: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewFeedSection {
+    pub layout: FeedLayout,
+    #[serde(default = "default_feed_source")]
+    pub source: FeedSource,
+    pub title_key: Option<String>,
+    #[serde(default)]
+    pub covers: Vec<String>,
+    #[serde(default)]
+    pub asset_ids: Vec<i64>,
+    #[serde(default)]
+    pub category_ids: Vec<i32>,
+    #[serde(default = "default_feed_section_size")]
+    pub size: i32,
+}
+
+fn default_feed_source() -> FeedSource { FeedSource::None }
+fn default_feed_section_size() -> i32 { 10 }
+
+#[derive(Debug, Clone)]
+pub struct NewPublisher {
+    pub address: String,
+    pub owner: String,
+    pub name: String,
+    pub block_number: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewPublisherApp {
+    pub publisher_address: String,
+    pub asset_address: String,
+    pub package_name: String,
+    pub name: String,
+    pub block_number: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewOwnershipChange {
+    pub asset_address: String,
+    pub prev_owner: Option<String>,
+    pub new_owner: String,
+    pub kind: OwnershipChangeKind,
+    pub tx_hash: String,
+    pub log_index: i64,
+    pub changed_at: Option<DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Publisher {
+    pub id: i64,
+    pub address: String,
+    pub owner: String,
+    pub name: String,
+    pub created_at: DateTime<chrono::Utc>,
+}
+
+// Published by the daemon and admin handlers after commit, every api instance purges affected etags
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CacheEvent {
+    AssetChanged { addresses: Vec<String> },
+    CategoryChanged,
+    FeedChanged { type_id: ObjTypeId },
+    ChartChanged,
+}
+
+// Encoded `change.ChangeEvent`, written to the outbox in the transaction of the change
+#[derive(Debug, Clone)]
+pub struct NewChangeEvent {
+    pub event_id: String,
+    pub kind: ChangeEventKind,
+    pub asset_address: String,
+    pub payload: Vec<u8>,
+    pub block_number: Option<i64>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ChangeOutboxEntry {
+    pub id: i64,
+    pub event_id: String,
+    pub asset_address: String,
+    pub payload: Vec<u8>,
+    pub attempts: i32,
+}
+
 // FUTURE
 
 #[derive(Debug, Clone, Hash, PartialEq, FromRow, Serialize, Deserialize)]
@@ -271,24 +621,3 @@ pub struct NewAchievement {
     pub value: Option<String>,
     pub asset_id: i64,
 }
-
-#[derive(Serialize, Deserialize, Debug)]
-struct Feed {
-    sections: Vec<Section>,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(tag = "type")]
-enum Section {
-    #[serde(rename = "banner")]
-    Banner { assets: Vec<Asset> },
-
-    #[serde(rename = "h_list")]
-    HList { assets: Vec<Asset> },
-
-    #[serde(rename = "v_list")]
-    VList { assets: Vec<Asset> },
-
-    #[serde(rename = "highlight")]
-    Highlight { target: Asset },
-}
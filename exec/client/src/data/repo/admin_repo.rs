@@ -0,0 +1,158 @@
+use crate::data::models::{AuditEntry, ChartOrder, NewAuditEntry};
+use crate::result::ClientResult;
+use db_psql::client::PgClient;
+use sqlx::{PgPool, Postgres, Transaction};
+
+#[derive(Clone)]
+pub struct AdminRepo {
+    client: PgClient,
+}
+
+impl AdminRepo {
+
+    pub fn new(client: PgClient) -> Self {
+        Self { client }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        self.client.pool()
+    }
+
+    pub async fn start(
+        &self
+    ) -> ClientResult<Transaction<'static, Postgres>> {
+        let transaction = self.client.start()
+            .await?;
+
+        return Ok(transaction);
+    }
+
+    pub async fn find_key(&self, key_hash: &str) -> ClientResult<Option<(String, i32)>> {
+        let result = sqlx::query!(
+            r#"
+            SELECT name, role FROM admin_key
+            WHERE key_hash = $1 AND is_active = true
+            "#,
+            key_hash
+        )
+            .fetch_optional(self.pool())
+            .await?;
+
+        return Ok(result.map(|row| (row.name, row.role)))
+    }
+
+    pub async fn find_wallet_role(&self, address: &str) -> ClientResult<Option<i32>> {
+        let result = sqlx::query_scalar!(
+            r#"
+            SELECT role FROM admin_wallet
+            WHERE address = $1 AND is_active = true
+            "#,
+            address
+        )
+            .fetch_optional(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    pub async fn insert_audit(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entry: &NewAuditEntry,
+    ) -> ClientResult<()> {
+        let role: i32 = entry.role.into();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO audit_log (actor, role, action, target, payload)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            entry.actor,
+            role,
+            entry.action,
+            entry.target,
+            entry.payload
+        )
+            .execute(&mut **tx)
+            .await?;
+
+        return Ok(())
+    }
+
+    pub async fn find_audit(&self, limit: i64, offset: i64) -> ClientResult<Vec<AuditEntry>> {
+        let result = sqlx::query_as!(
+            AuditEntry,
+            r#"
+            SELECT id, actor, role, action, target, payload, created_at
+            FROM audit_log
+            ORDER BY id DESC
+            LIMIT $1 OFFSET $2
+            "#,
+            limit,
+            offset
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    pub async fn get_chart_order(
+        &self,
+        platform_id: i32,
+        category_id: i32,
+    ) -> ClientResult<Vec<ChartOrder>> {
+        let result = sqlx::query_as!(
+            ChartOrder,
+            r#"
+            SELECT platform_id, category_id, asset_id, position
+            FROM chart_order
+            WHERE platform_id = $1 AND category_id = $2
+            ORDER BY position
+            "#,
+            platform_id,
+            category_id
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    // Replaces the pinned ordering of a chart, position follows the order of `asset_ids`
+    pub async fn set_chart_order(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        platform_id: i32,
+        category_id: i32,
+        asset_ids: &[i64],
+    ) -> ClientResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM chart_order
+            WHERE platform_id = $1 AND category_id = $2
+            "#,
+            platform_id,
+            category_id
+        )
+            .execute(&mut **tx)
+            .await?;
+
+        for (position, asset_id) in asset_ids.iter().enumerate() {
+            sqlx::query!(
+                r#"
+                INSERT INTO chart_order (platform_id, category_id, asset_id, position)
+                VALUES ($1, $2, $3, $4)
+                "#,
+                platform_id,
+                category_id,
+                asset_id,
+                position as i32
+            )
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        return Ok(())
+    }
+}
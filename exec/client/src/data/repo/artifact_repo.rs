@@ -1,8 +1,9 @@
 use bytes::Bytes;
-use crate::data::models::{Artifact, NewArtifact};
+use crate::data::id::TrackId;
+use crate::data::models::{Artifact, InstalledApp, NewArtifact, TrackArtifact, UpdateCandidate};
 use crate::result::{ClientError, ClientResult};
 use db_psql::client::PgClient;
-use sqlx::{FromRow, PgPool};
+use sqlx::{FromRow, PgExecutor, PgPool};
 use tracing::error;
 
 #[derive(Clone)]
@@ -67,17 +68,29 @@ impl ArtifactRepo {
         }
     }
 
-    pub async fn insert_artifact(&self, data: &NewArtifact) -> ClientResult<()> {
+    pub async fn insert_artifact<'e, E: PgExecutor<'e>>(
+        &self,
+        executor: E,
+        data: &NewArtifact,
+        block_number: Option<i64>,
+    ) -> ClientResult<()> {
         sqlx::query_as!(
             Artifact,
             r#"
             INSERT INTO artifact (
-                ref_id, asset_address, protocol_id, size, version_name, version_code, checksum
+                ref_id, asset_address, protocol_id, size, version_name, version_code, checksum, block_number
             )
             
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             
-            ON CONFLICT (asset_address, version_code) DO NOTHING
+            -- Refetched builds replace what was stored, the first synced block stays
+            ON CONFLICT (asset_address, version_code) DO UPDATE SET
+                ref_id = EXCLUDED.ref_id,
+                protocol_id = EXCLUDED.protocol_id,
+                size = EXCLUDED.size,
+                version_name = EXCLUDED.version_name,
+                checksum = EXCLUDED.checksum,
+                block_number = COALESCE(artifact.block_number, EXCLUDED.block_number)
             "#,
             data.object_ref,
             data.asset_address,
@@ -86,13 +99,31 @@ impl ArtifactRepo {
             data.version_name,
             data.version_code,
             data.checksum,
+            block_number,
         )
-            .execute(self.pool())
+            .execute(executor)
             .await?;
 
         Ok(())
     }
 
+    // Builds of the asset known from requests or artifacts
+    pub async fn find_version_codes(&self, address: &str) -> ClientResult<Vec<i64>> {
+        let result = sqlx::query_scalar!(
+            r#"
+            SELECT version_code AS "version_code!" FROM build_request WHERE asset_address = $1 AND version_code > 0
+            UNION
+            SELECT version_code AS "version_code!" FROM artifact WHERE asset_address = $1
+            ORDER BY 1
+            "#,
+            address
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result);
+    }
+
     pub async fn find_by_obj_track(&self, obj_id: i64, track_id: i32) -> ClientResult<Option<Artifact>> {
         let result = sqlx::query_as!(
             Artifact,
@@ -105,6 +136,44 @@ impl ArtifactRepo {
 
             WHERE p.track_id = $2
             AND artifact.asset_address = o.address
+            AND artifact.version_code = p.version_code
+            AND o.visibility = 0
+            "#,
+            obj_id,
+            track_id
+        )
+            .fetch_optional(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    // Newest validated build over the track and every more stable one, so the release is a fallback
+    pub async fn find_latest_by_track(&self, obj_id: i64, track_id: &TrackId) -> ClientResult<Option<TrackArtifact>> {
+        let track_id: i32 = track_id.clone().into();
+        let result = sqlx::query!(
+            r#"
+            SELECT
+                artifact.id, ref_id, artifact.asset_address, protocol_id, size, version_name,
+                artifact.version_code, artifact.checksum, p.track_id
+
+            FROM artifact
+            INNER JOIN obj o ON o.id = $1
+            INNER JOIN publishing p ON o.address = p.asset_address AND p.is_active
+
+            WHERE p.track_id BETWEEN 1 AND $2
+            AND artifact.asset_address = o.address
+            AND artifact.version_code = p.version_code
+            AND o.visibility = 0
+            AND EXISTS (
+                SELECT 1 FROM build_request br
+                WHERE br.asset_address = p.asset_address
+                AND br.version_code = p.version_code
+                AND br.status = 1
+            )
+
+            ORDER BY p.version_code DESC, p.track_id ASC
+            LIMIT 1
             "#,
             obj_id,
             track_id
@@ -112,6 +181,97 @@ impl ArtifactRepo {
             .fetch_optional(self.pool())
             .await?;
 
+        let result = result.map(|row| TrackArtifact {
+            track: TrackId::from(row.track_id),
+            artifact: Artifact {
+                id: row.id,
+                ref_id: row.ref_id,
+                asset_address: row.asset_address,
+                protocol_id: row.protocol_id,
+                size: row.size,
+                version_name: row.version_name,
+                version_code: row.version_code,
+                checksum: row.checksum,
+            },
+        });
+
+        return Ok(result)
+    }
+
+    // One query for the whole device, a matching signature wins over a higher version of a clone.
+    // Proofs without fingerprints can't be compared, their match is unknown
+    pub async fn find_updates(&self, apps: &[InstalledApp], track_id: &TrackId) -> ClientResult<Vec<UpdateCandidate>> {
+        let track_id: i32 = track_id.clone().into();
+        let package_names: Vec<String> = apps.iter()
+            .map(|app| app.package_name.clone().unwrap_or_default())
+            .collect();
+        let addresses: Vec<String> = apps.iter()
+            .map(|app| app.address.clone().unwrap_or_default())
+            .collect();
+        let version_codes: Vec<i64> = apps.iter()
+            .map(|app| app.version_code)
+            .collect();
+        let fingerprints: Vec<String> = apps.iter()
+            .map(|app| app.fingerprint.clone())
+            .collect();
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT ON (input.idx)
+                input.idx AS "idx!",
+                obj.id AS asset_id,
+                obj.address,
+                obj.package_name,
+                p.track_id,
+                signature.is_match AS is_signature_match,
+                artifact.id, ref_id, protocol_id, size, version_name, artifact.version_code, artifact.checksum
+
+            FROM UNNEST($1::TEXT[], $2::TEXT[], $3::BIGINT[], $4::TEXT[])
+                WITH ORDINALITY AS input(package_name, address, version_code, fingerprint, idx)
+            INNER JOIN obj ON (obj.package_name = input.package_name OR obj.address = input.address) AND obj.visibility = 0
+            INNER JOIN publishing p ON p.asset_address = obj.address AND p.is_active AND p.track_id BETWEEN 1 AND $5
+            INNER JOIN artifact ON artifact.asset_address = p.asset_address AND artifact.version_code = p.version_code
+            INNER JOIN build_request br ON br.asset_address = p.asset_address AND br.version_code = p.version_code AND br.status = 1
+            INNER JOIN validation_proof proof ON proof.asset_address = br.asset_address AND proof.owner_version = br.owner_version AND proof.status = 1 AND proof.request_id IS NULL
+            CROSS JOIN LATERAL (
+                SELECT CASE WHEN cardinality(proof.fingerprints) = 0 THEN NULL
+                    ELSE input.fingerprint = ANY(proof.fingerprints) END AS is_match
+            ) signature
+
+            WHERE p.version_code > input.version_code
+
+            ORDER BY input.idx, signature.is_match IS TRUE DESC, signature.is_match IS NULL DESC, p.version_code DESC, p.track_id ASC
+            "#,
+            &package_names,
+            &addresses,
+            &version_codes,
+            &fingerprints,
+            track_id
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        let result = rows.into_iter()
+            .map(|row| UpdateCandidate {
+                index: row.idx - 1,
+                asset_id: row.asset_id,
+                address: row.address.clone(),
+                package_name: row.package_name,
+                track: TrackId::from(row.track_id),
+                is_signature_match: row.is_signature_match,
+                artifact: Artifact {
+                    id: row.id,
+                    ref_id: row.ref_id,
+                    asset_address: row.address,
+                    protocol_id: row.protocol_id,
+                    size: row.size,
+                    version_name: row.version_name,
+                    version_code: row.version_code,
+                    checksum: row.checksum,
+                },
+            })
+            .collect();
+
         return Ok(result)
     }
 
@@ -1,7 +1,7 @@
-use crate::data::models::{AssetlinkSync, ValidationProof};
+use crate::data::models::{AssetlinkSync, RequestValidation, ValidationProof};
 use crate::result::ClientResult;
 use db_psql::client::PgClient;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 #[derive(Clone)]
 pub struct AssetlinkRepo {
@@ -22,8 +22,8 @@ impl AssetlinkRepo {
         let result = sqlx::query_as!(
             ValidationProof,
             r#"
-            SELECT asset_address, owner_version, status FROM validation_proof
-            WHERE asset_address = $1
+            SELECT asset_address, owner_version, status, fingerprints FROM validation_proof
+            WHERE asset_address = $1 AND request_id IS NULL
             ORDER BY owner_version
             DESC
             LIMIT 1;
@@ -36,44 +36,95 @@ impl AssetlinkRepo {
         return Ok(result);
     }
 
-    pub async fn insert_assetlink_status(&self, data: &AssetlinkSync) -> ClientResult<()> {
+    pub async fn insert_assetlink_status<'e, E: PgExecutor<'e>>(
+        &self,
+        executor: E,
+        data: &AssetlinkSync,
+        block_number: Option<i64>,
+    ) -> ClientResult<()> {
         let result = sqlx::query_as!(
             AssetlinkSync,
             r#"
             INSERT INTO assetlink_sync (
-                asset_address, domain, owner_version, status
+                asset_address, domain, owner_version, status, block_number
             )
-            VALUES ($1, $2, $3, $4)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (asset_address, owner_version, domain, status) DO NOTHING
             "#,
             &data.asset_address,
             &data.domain,
             data.owner_version,
-            data.status
+            data.status,
+            block_number
         )
-            .execute(self.pool())
+            .execute(executor)
             .await?;
 
         Ok(())
     }
 
-    pub async fn insert_validation_proof(&self, data: &ValidationProof) -> ClientResult<()> {
+    // Proofs saved before fingerprints were synced get them on a resync of their block
+    pub async fn insert_validation_proof<'e, E: PgExecutor<'e>>(
+        &self,
+        executor: E,
+        data: &ValidationProof,
+        block_number: Option<i64>,
+    ) -> ClientResult<()> {
         let result = sqlx::query_as!(
             ValidationProof,
             r#"
             INSERT INTO validation_proof (
-                asset_address, owner_version, status
+                asset_address, owner_version, status, fingerprints, block_number
             )
 
-            VALUES ($1, $2, $3)
-            ON CONFLICT DO NOTHING;
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (asset_address, owner_version) WHERE request_id IS NULL DO UPDATE SET
+                fingerprints = EXCLUDED.fingerprints;
             "#,
             &data.asset_address,
             data.owner_version,
-            data.status
+            data.status,
+            &data.fingerprints,
+            block_number
         )
-            .execute(self.pool())
+            .execute(executor)
             .await?;
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    // Stored next to ownership proofs, finalization of the same request again replaces its result
+    // unless it comes from an older block, like a retried log
+    pub async fn insert_request_validation<'e, E: PgExecutor<'e>>(
+        &self,
+        executor: E,
+        data: &RequestValidation,
+        block_number: Option<i64>,
+    ) -> ClientResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO validation_proof (
+                asset_address, owner_version, status, request_id, version_code, file_hash, block_number
+            )
+
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (request_id) WHERE request_id IS NOT NULL DO UPDATE SET
+                status = EXCLUDED.status,
+                file_hash = EXCLUDED.file_hash,
+                block_number = EXCLUDED.block_number
+            WHERE (validation_proof.block_number > EXCLUDED.block_number) IS NOT TRUE
+            "#,
+            &data.asset_address,
+            data.owner_version,
+            data.status,
+            data.request_id,
+            data.version_code,
+            data.file_hash,
+            block_number
+        )
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+}
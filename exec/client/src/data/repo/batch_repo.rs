@@ -1,6 +1,7 @@
 use crate::result::ClientResult;
 use db_psql::client::PgClient;
-use sqlx::{Database, Decode, PgPool, Type};
+use sqlx::{Database, Decode, PgExecutor, PgPool, Type};
+use std::collections::BTreeSet;
 
 #[derive(Type, Clone)]
 #[repr(i32)]
@@ -15,6 +16,11 @@ pub struct TransactionBatch {
     pub status: TransactionStatus,
 }
 
+pub struct SyncBlock {
+    pub block_number: i64,
+    pub block_hash: String,
+}
+
 pub struct BatchRepo {
     client: PgClient
 }
@@ -30,7 +36,7 @@ impl BatchRepo {
         self.client.pool()
     }
 
-    pub async fn save_batch(&self, batch: TransactionBatch) -> ClientResult<()> {
+    pub async fn save_batch<'e, E: PgExecutor<'e>>(&self, executor: E, batch: TransactionBatch) -> ClientResult<()> {
         let result = sqlx::query_as!(
             TransactionBatch,
             r#"
@@ -44,7 +50,7 @@ impl BatchRepo {
             batch.to_block_number,
             batch.status as i32
         )
-            .execute(self.pool())
+            .execute(executor)
             .await?;
 
         Ok(())
@@ -68,4 +74,212 @@ impl BatchRepo {
         
         Ok(result)
     }
+    pub async fn save_block_hashes<'e, E: PgExecutor<'e>>(&self, executor: E, blocks: &[SyncBlock]) -> ClientResult<()> {
+        let numbers: Vec<i64> = blocks.iter().map(|block| block.block_number).collect();
+        let hashes: Vec<String> = blocks.iter().map(|block| block.block_hash.clone()).collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO sync_block (block_number, block_hash)
+            SELECT * FROM UNNEST($1::BIGINT[], $2::VARCHAR[])
+            ON CONFLICT (block_number) DO UPDATE SET
+                block_hash = EXCLUDED.block_hash
+            "#,
+            &numbers,
+            &hashes
+        )
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    // Newest first, the fork point is searched from the sync head down
+    pub async fn get_block_hashes(&self, limit: i64) -> ClientResult<Vec<SyncBlock>> {
+        let result = sqlx::query_as!(
+            SyncBlock,
+            r#"
+            SELECT block_number, block_hash
+            FROM sync_block
+            ORDER BY block_number DESC
+            LIMIT $1
+            "#,
+            limit
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        Ok(result)
+    }
+
+    // Batch ranges are [from, to), so the last batch that ends at or before `block_number` is kept intact
+    pub async fn get_batch_end_before(&self, block_number: i64) -> ClientResult<Option<i64>> {
+        let result = sqlx::query_scalar!(
+            r#"
+            SELECT MAX(to_block_number)
+            FROM transactions_batch
+            WHERE to_block_number <= $1
+            "#,
+            block_number
+        )
+            .fetch_one(self.pool())
+            .await?;
+
+        Ok(result)
+    }
+
+    // Removes everything synced from `block_number` on and rewinds the batches to it.
+    // Returns addresses of the touched assets, api caches of them are stale
+    pub async fn rollback(&self, block_number: i64) -> ClientResult<Vec<String>> {
+        let mut tx = self.client.start()
+            .await?;
+
+        let mut addresses = BTreeSet::new();
+
+        let tracks = sqlx::query_scalar!(
+            r#"
+            WITH first_change AS (
+                SELECT DISTINCT ON (asset_address, track_id)
+                    asset_address, track_id, prev_version_code, prev_is_active
+                FROM publishing_change
+                WHERE block_number >= $1
+                ORDER BY asset_address, track_id, id
+            ), restored AS (
+                UPDATE publishing SET
+                    version_code = first_change.prev_version_code,
+                    is_active = COALESCE(first_change.prev_is_active, false)
+                FROM first_change
+                WHERE publishing.asset_address = first_change.asset_address
+                AND publishing.track_id = first_change.track_id
+                AND first_change.prev_version_code IS NOT NULL
+                RETURNING publishing.asset_address
+            ), removed AS (
+                DELETE FROM publishing
+                USING first_change
+                WHERE publishing.asset_address = first_change.asset_address
+                AND publishing.track_id = first_change.track_id
+                AND first_change.prev_version_code IS NULL
+                RETURNING publishing.asset_address
+            )
+            SELECT asset_address AS "asset_address!" FROM restored
+            UNION
+            SELECT asset_address AS "asset_address!" FROM removed
+            "#,
+            block_number
+        )
+            .fetch_all(&mut *tx)
+            .await?;
+        addresses.extend(tracks);
+
+        sqlx::query!("DELETE FROM publishing_change WHERE block_number >= $1", block_number)
+            .execute(&mut *tx)
+            .await?;
+
+        // Requests created before the fork lose the result of their orphaned finalized block
+        let finalized = sqlx::query_scalar!(
+            r#"
+            UPDATE build_request SET status = NULL, block_id = NULL, finalized_at = NULL
+            FROM validation_proof proof
+            WHERE proof.request_id = build_request.id AND proof.block_number >= $1
+            RETURNING build_request.asset_address
+            "#,
+            block_number
+        )
+            .fetch_all(&mut *tx)
+            .await?;
+        addresses.extend(finalized);
+
+        let requests = sqlx::query_scalar!(
+            "DELETE FROM build_request WHERE block_number >= $1 RETURNING asset_address",
+            block_number
+        )
+            .fetch_all(&mut *tx)
+            .await?;
+        addresses.extend(requests);
+
+        let artifacts = sqlx::query_scalar!(
+            "DELETE FROM artifact WHERE block_number >= $1 RETURNING asset_address",
+            block_number
+        )
+            .fetch_all(&mut *tx)
+            .await?;
+        addresses.extend(artifacts);
+
+        let syncs = sqlx::query_scalar!(
+            "DELETE FROM assetlink_sync WHERE block_number >= $1 RETURNING asset_address",
+            block_number
+        )
+            .fetch_all(&mut *tx)
+            .await?;
+        addresses.extend(syncs);
+
+        let proofs = sqlx::query_scalar!(
+            "DELETE FROM validation_proof WHERE block_number >= $1 RETURNING asset_address",
+            block_number
+        )
+            .fetch_all(&mut *tx)
+            .await?;
+        addresses.extend(proofs);
+
+        sqlx::query!("DELETE FROM publisher_app WHERE block_number >= $1", block_number)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!("DELETE FROM publisher WHERE block_number >= $1", block_number)
+            .execute(&mut *tx)
+            .await?;
+
+        let owners = sqlx::query_scalar!(
+            "DELETE FROM ownership_history WHERE block_number >= $1 RETURNING asset_address",
+            block_number
+        )
+            .fetch_all(&mut *tx)
+            .await?;
+
+        // Owner falls back to the last change before the fork
+        sqlx::query!(
+            r#"
+            UPDATE obj SET owner = COALESCE(
+                (SELECT new_owner FROM ownership_history WHERE asset_address = obj.address ORDER BY block_number DESC, log_index DESC LIMIT 1),
+                (SELECT publisher_address FROM publisher_app WHERE asset_address = obj.address)
+            )
+            WHERE address = ANY($1)
+            "#,
+            &owners
+        )
+            .execute(&mut *tx)
+            .await?;
+        addresses.extend(owners);
+
+        let objects = sqlx::query_scalar!(
+            "DELETE FROM obj WHERE block_number >= $1 RETURNING address",
+            block_number
+        )
+            .fetch_all(&mut *tx)
+            .await?;
+        addresses.extend(objects);
+
+        sqlx::query!("DELETE FROM sync_block WHERE block_number >= $1", block_number)
+            .execute(&mut *tx)
+            .await?;
+
+        // Orphaned logs are gone from the chain, the resync records their replacements
+        sqlx::query!("DELETE FROM failed_log WHERE block_number >= $1", block_number)
+            .execute(&mut *tx)
+            .await?;
+
+        // Events of orphaned blocks that kafka hasn't got yet are dropped, published ones are already out
+        sqlx::query!("DELETE FROM change_outbox WHERE block_number >= $1 AND published_at IS NULL", block_number)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!("DELETE FROM transactions_batch WHERE to_block_number > $1", block_number)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit()
+            .await?;
+
+        Ok(addresses.into_iter().collect())
+    }
 }
\ No newline at end of file
@@ -1,13 +1,17 @@
 use base64::prelude::BASE64_URL_SAFE_NO_PAD;
 use base64::Engine;
+use crate::data::models::CacheEvent;
 use db_redis::cache::{KeyValueResult, RedisCache};
+use std::future::Future;
 use std::sync::Arc;
+use tracing::warn;
 
 pub struct CacheRepo {
     cache: Arc<RedisCache>
 }
 
 const ETAG_TTL: u64 = 24 * 60 * 60; // TODO to config
+const CACHE_EVENTS_CHANNEL: &str = "events:cache";
 
 impl CacheRepo {
 
@@ -54,4 +58,56 @@ impl CacheRepo {
             .await
             .unwrap_or(None)
     }
+
+    pub async fn set_content(&self, key: &str, content: &str) -> KeyValueResult<()> {
+        return self.cache.set_str(key, content, None)
+            .await
+    }
+
+    pub async fn set_content_with_ttl(&self, key: &str, content: &str, ttl: u64) -> KeyValueResult<()> {
+        return self.cache.set_str(key, content, Some(ttl))
+            .await
+    }
+
+    pub async fn delete(&self, key: &str) -> KeyValueResult<bool> {
+        return self.cache.delete(key)
+            .await
+    }
+
+    pub async fn delete_pattern(&self, pattern: &str) -> KeyValueResult<u64> {
+        return self.cache.delete_pattern(pattern)
+            .await
+    }
+
+    // False when the nonce was already used by the wallet within `ttl`
+    pub async fn claim_admin_nonce(&self, address: &str, nonce: &str, ttl: u64) -> KeyValueResult<bool> {
+        return self.cache.set_nx(format!("admin_nonce:{}:{}", address, nonce).as_str(), "1", ttl)
+            .await
+    }
+
+    pub async fn publish_event(&self, event: &CacheEvent) -> KeyValueResult<()> {
+        let message = serde_json::to_string(event)?;
+
+        return self.cache.publish(CACHE_EVENTS_CHANNEL, message.as_str())
+            .await
+    }
+
+    pub async fn listen_events<F, Fut>(&self, mut handler: F) -> KeyValueResult<()>
+    where
+        F: FnMut(CacheEvent) -> Fut,
+        Fut: Future<Output=()> {
+        return self.cache.listen(CACHE_EVENTS_CHANNEL, |message| {
+            let event = serde_json::from_str::<CacheEvent>(message.as_str());
+            if let Err(e) = &event {
+                warn!("Skip malformed cache event {}: {}", message, e);
+            }
+
+            let future = event.ok().map(|event| handler(event));
+            async move {
+                if let Some(future) = future {
+                    future.await;
+                }
+            }
+        }).await
+    }
 }
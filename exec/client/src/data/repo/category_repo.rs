@@ -1,4 +1,4 @@
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use db_psql::client::PgClient;
 use crate::data::models::{Category, NewCategory};
 use crate::result::ClientResult;
@@ -18,23 +18,46 @@ impl CategoryRepo {
         self.client.pool()
     }
 
-    pub async fn put_all(&self, categories: Vec<NewCategory>) -> ClientResult<()> {
-        // sqlx::query_as!(
-        //     Category,
-        //     r#"
-        //     INSERT INTO category (id, name, type_id)
-        //     VALUES ($1, $2, $3)
-        //     "#,
-        //     new_category.id
-        //     new_category.name
-        //     new_category.type_id
-        // )
-        //     .fetch_one(self.pool())
-        //     .await?
+    pub async fn put_all(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        categories: &Vec<NewCategory>,
+    ) -> ClientResult<()> {
+        for category in categories {
+            sqlx::query!(
+                r#"
+                INSERT INTO category (id, name, type_id)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (id) DO UPDATE SET
+                    name = EXCLUDED.name,
+                    type_id = EXCLUDED.type_id
+                "#,
+                category.id,
+                category.name,
+                category.type_id
+            )
+                .execute(&mut **tx)
+                .await?;
+        }
 
         return Ok(())
     }
 
+    pub async fn delete(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        category_id: i32,
+    ) -> ClientResult<u64> {
+        let result = sqlx::query!(
+            "DELETE FROM category WHERE id = $1",
+            category_id
+        )
+            .execute(&mut **tx)
+            .await?;
+
+        return Ok(result.rows_affected())
+    }
+
     pub async fn get_all(&self) -> ClientResult<Vec<Category>> {
         let result = sqlx::query_as!(
             Category,
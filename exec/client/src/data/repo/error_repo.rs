@@ -1,6 +1,12 @@
-use sqlx::PgPool;
+use crate::data::id::{FailedLogKind, FailedLogStatus};
+use crate::data::models::FailedLog;
+use crate::env;
+use crate::result::ClientResult;
+use alloy::hex::ToHexExt;
+use alloy::rpc::types::Log;
 use db_psql::client::PgClient;
-use net_client::node::result::EthResult;
+use sqlx::{PgPool, Postgres, Transaction};
+use tracing::warn;
 
 #[derive(Clone)]
 pub struct ErrorRepo {
@@ -17,11 +23,230 @@ impl ErrorRepo {
         self.client.pool()
     }
 
-    pub async fn insert_fatal_tx(&self, hash: String) -> EthResult<()> {
+    pub async fn insert_fatal_tx(&self, item: &Log, error: &str) -> ClientResult<()> {
+        return self.insert_failed_log(item, FailedLogKind::Fatal, error).await
+    }
+
+    pub async fn insert_error_tx(&self, item: &Log, error: &str) -> ClientResult<()> {
+        return self.insert_failed_log(item, FailedLogKind::Error, error).await
+    }
+
+    // A log failing again counts as another attempt, the delay doubles until the entry is dead
+    async fn insert_failed_log(&self, item: &Log, kind: FailedLogKind, error: &str) -> ClientResult<()> {
+        let (Some(tx_hash), Some(log_index), Some(block_number)) =
+            (item.transaction_hash, item.log_index, item.block_number) else {
+            warn!("[FAILED_LOG] Log without transaction data can't be stored: {}", error);
+            return Ok(())
+        };
+
+        let log = serde_json::to_string(item)?;
+        let topic0 = item.topic0().map(|topic| topic.encode_hex_with_prefix());
+        let kind: i32 = kind.into();
+        let dead: i32 = FailedLogStatus::Dead.into();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO failed_log (
+                tx_hash, log_index, block_number, topic0, log, kind, error, next_retry_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, CURRENT_TIMESTAMP + make_interval(secs => $8))
+            ON CONFLICT (tx_hash, log_index) DO UPDATE SET
+                log = EXCLUDED.log,
+                kind = EXCLUDED.kind,
+                error = EXCLUDED.error,
+                attempts = failed_log.attempts + 1,
+                status = CASE WHEN failed_log.attempts + 1 >= $9 THEN $10 ELSE 0 END,
+                next_retry_at = CURRENT_TIMESTAMP
+                    + make_interval(secs => LEAST($8 * POWER(2, failed_log.attempts), $11)),
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+            tx_hash.encode_hex_with_prefix(),
+            log_index as i64,
+            block_number as i64,
+            topic0,
+            log,
+            kind,
+            error,
+            env::failed_log_retry_base_sec(),
+            env::failed_log_max_attempts(),
+            dead,
+            env::failed_log_retry_max_sec()
+        )
+            .execute(self.pool())
+            .await?;
+
         return Ok(())
     }
-    
-    pub async fn insert_error_tx(&self, hash: String) -> EthResult<()> {
+
+    pub async fn find_due(&self, limit: i64) -> ClientResult<Vec<FailedLog>> {
+        let result = sqlx::query_as!(
+            FailedLog,
+            r#"
+            SELECT id, tx_hash, log_index, block_number, topic0, log, kind, error, status, attempts,
+                next_retry_at, created_at, updated_at
+            FROM failed_log
+            WHERE status = 0 AND next_retry_at <= CURRENT_TIMESTAMP
+            ORDER BY block_number, log_index
+            LIMIT $1
+            "#,
+            limit
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    // Skipped if the retry recorded a new failure, `attempts` is the value the retry started with
+    pub async fn resolve(&self, id: i64, attempts: i32) -> ClientResult<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE failed_log SET status = 1, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND attempts = $2 AND status = 0
+            "#,
+            id,
+            attempts
+        )
+            .execute(self.pool())
+            .await?;
+
+        return Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_dead(&self, id: i64, error: &str) -> ClientResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE failed_log SET status = 2, error = $2, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            "#,
+            id,
+            error
+        )
+            .execute(self.pool())
+            .await?;
+
         return Ok(())
     }
+
+    pub async fn find_by_id(&self, id: i64) -> ClientResult<Option<FailedLog>> {
+        let result = sqlx::query_as!(
+            FailedLog,
+            r#"
+            SELECT id, tx_hash, log_index, block_number, topic0, log, kind, error, status, attempts,
+                next_retry_at, created_at, updated_at
+            FROM failed_log
+            WHERE id = $1
+            "#,
+            id
+        )
+            .fetch_optional(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    pub async fn find_by_status(
+        &self,
+        status: FailedLogStatus,
+        limit: i64,
+        offset: i64,
+    ) -> ClientResult<Vec<FailedLog>> {
+        let status: i32 = status.into();
+
+        let result = sqlx::query_as!(
+            FailedLog,
+            r#"
+            SELECT id, tx_hash, log_index, block_number, topic0, log, kind, error, status, attempts,
+                next_retry_at, created_at, updated_at
+            FROM failed_log
+            WHERE status = $1
+            ORDER BY id DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            status,
+            limit,
+            offset
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    // Resolved entries are never re-driven, replaying them could override newer state
+    pub async fn redrive(&self, tx: &mut Transaction<'_, Postgres>, id: i64) -> ClientResult<Option<FailedLog>> {
+        let result = sqlx::query_as!(
+            FailedLog,
+            r#"
+            UPDATE failed_log
+            SET status = 0, attempts = 0, next_retry_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND status <> 1
+            RETURNING id, tx_hash, log_index, block_number, topic0, log, kind, error, status, attempts,
+                next_retry_at, created_at, updated_at
+            "#,
+            id
+        )
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        return Ok(result)
+    }
+}
+
+// Walks an entry from the first failure to dead and back through a re-drive
+#[tokio::test]
+#[ignore = "needs a migrated DATABASE_URL"]
+async fn check_failed_log_lifecycle() {
+    use alloy::primitives::B256;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let repo = ErrorRepo::new(PgClient::connect(env::psql_url().as_ref()).await.unwrap());
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    let tx_hash = B256::left_padding_from(&nanos.to_be_bytes());
+    let item = Log {
+        transaction_hash: Some(tx_hash),
+        log_index: Some(3),
+        block_number: Some(1 << 60),
+        ..Default::default()
+    };
+
+    repo.insert_fatal_tx(&item, "decode").await.unwrap();
+    let entry = find(&repo, FailedLogStatus::Pending, tx_hash).await.unwrap();
+    assert_eq!(entry.attempts, 1);
+    assert_eq!(entry.kind, FailedLogKind::Fatal as i32);
+    assert!(entry.next_retry_at > entry.created_at);
+    assert_eq!(serde_json::from_str::<Log>(&entry.log).unwrap(), item);
+
+    for _ in 1..env::failed_log_max_attempts() {
+        repo.insert_error_tx(&item, "lookup").await.unwrap();
+    }
+    let entry = find(&repo, FailedLogStatus::Dead, tx_hash).await.unwrap();
+    assert_eq!(entry.attempts, env::failed_log_max_attempts());
+    assert_eq!(entry.error, "lookup");
+
+    let mut tx = repo.pool().begin().await.unwrap();
+    let entry = repo.redrive(&mut tx, entry.id).await.unwrap().unwrap();
+    tx.commit().await.unwrap();
+    assert_eq!(entry.status, FailedLogStatus::Pending as i32);
+    assert!(repo.find_due(1000).await.unwrap().iter().any(|due| due.id == entry.id));
+
+    // Failure recorded during the retry wins over its resolve
+    repo.insert_error_tx(&item, "lookup").await.unwrap();
+    assert!(!repo.resolve(entry.id, entry.attempts).await.unwrap());
+    assert!(repo.resolve(entry.id, entry.attempts + 1).await.unwrap());
+
+    let mut tx = repo.pool().begin().await.unwrap();
+    assert!(repo.redrive(&mut tx, entry.id).await.unwrap().is_none());
+    drop(tx);
+
+    sqlx::query!("DELETE FROM failed_log WHERE id = $1", entry.id)
+        .execute(repo.pool())
+        .await
+        .unwrap();
+
+    async fn find(repo: &ErrorRepo, status: FailedLogStatus, tx_hash: alloy::primitives::B256) -> Option<FailedLog> {
+        let entries = repo.find_by_status(status, 100, 0).await.unwrap();
+        return entries.into_iter().find(|entry| entry.tx_hash == tx_hash.encode_hex_with_prefix());
+    }
 }
@@ -0,0 +1,80 @@
+use crate::data::id::ObjTypeId;
+use crate::data::models::{FeedSection, NewFeedSection};
+use crate::result::ClientResult;
+use db_psql::client::PgClient;
+use sqlx::{PgPool, Postgres, Transaction};
+
+#[derive(Clone)]
+pub struct FeedRepo {
+    client: PgClient,
+}
+
+impl FeedRepo {
+
+    pub fn new(client: PgClient) -> Self {
+        Self { client }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        self.client.pool()
+    }
+
+    pub async fn find_sections(&self, type_id: &ObjTypeId) -> ClientResult<Vec<FeedSection>> {
+        let type_id: i32 = type_id.clone().into();
+        let result = sqlx::query_as!(
+            FeedSection,
+            r#"
+            SELECT id, type_id, position, layout, source, title_key, covers, asset_ids, category_ids, size
+            FROM feed_section
+            WHERE type_id = $1
+            ORDER BY position ASC
+            "#,
+            type_id
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    // Sections are positioned in the given order
+    pub async fn replace_sections(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        type_id: &ObjTypeId,
+        sections: &Vec<NewFeedSection>,
+    ) -> ClientResult<()> {
+        let type_id: i32 = type_id.clone().into();
+
+        sqlx::query!("DELETE FROM feed_section WHERE type_id = $1", type_id)
+            .execute(&mut **tx)
+            .await?;
+
+        for (position, section) in sections.iter().enumerate() {
+            let layout: i32 = section.layout.into();
+            let source: i32 = section.source.into();
+
+            sqlx::query!(
+                r#"
+                INSERT INTO feed_section (
+                    type_id, position, layout, source, title_key, covers, asset_ids, category_ids, size
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#,
+                type_id,
+                position as i32,
+                layout,
+                source,
+                section.title_key,
+                &section.covers,
+                &section.asset_ids,
+                &section.category_ids,
+                section.size
+            )
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        return Ok(())
+    }
+}
@@ -11,3 +11,8 @@ pub mod assetlink_repo;
 pub mod validation_repo;
 pub mod error_repo;
 pub mod batch_repo;
+pub mod admin_repo;
+pub mod feed_repo;
+pub mod publisher_repo;
+pub mod ownership_repo;
+pub mod outbox_repo;
@@ -1,15 +1,16 @@
-use crate::data::models::{NewAsset, Asset, RichAsset};
+use crate::data::models::{NewAsset, Asset, AssetState, AssetVisibility, ChartAsset, ChartCursor, RichAsset};
 use crate::result::ClientResult;
 use core_std::empty::Empty;
 use db_psql::client::PgClient;
-use sqlx::{PgPool, Postgres, Transaction};
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
 use std::ops::Deref;
 use hex::ToHex;
 use tracing::{error, log};
 use codegen_contracts::ext::ToChecksum;
 use core_std::hexer;
 use service_graph::client::AppAsset;
-use crate::data::id::{ObjTypeId, CategoryId, PlatformId};
+use crate::data::id::{ChartSort, ObjTypeId, CategoryId, PlatformId, TrackId, Visibility};
+use crate::env::chart_min_rating_count;
 
 #[derive(Clone)]
 pub struct ObjectRepo {
@@ -35,10 +36,13 @@ impl ObjectRepo {
         return Ok(transaction);
     }
 
+    // Asset is available on the track when its build is published there or on a more stable track
     pub async fn find_by_id(
         &self,
-        id: i64
+        id: i64,
+        track_id: &TrackId,
     ) -> ClientResult<Option<Asset>> {
+        let track_id: i32 = track_id.clone().into();
         let result = sqlx::query_as!(
             Asset,
             r#"
@@ -46,22 +50,25 @@ impl ObjectRepo {
                 name, package_name, address, logo, description,
                 type_id, category_id, platform_id,
                 is_os_verified, is_hidden,
-                price, obj.id, rating, downloads, assetlink_sync.domain as website
+                price, obj.id, rating, rating_count, downloads, assetlink_sync.domain as website
             FROM obj
                 
-            INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id = 1
+            INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id BETWEEN 1 AND $2
             INNER JOIN assetlink_sync ON assetlink_sync.asset_address = obj.address AND assetlink_sync.status = 1
             INNER JOIN build_request ON build_request.asset_address = obj.address AND build_request.status = 1
-            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1
+            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1 AND validation_proof.request_id IS NULL
 
             WHERE build_request.owner_version = assetlink_sync.owner_version
             AND build_request.owner_version = validation_proof.owner_version
             AND build_request.version_code = publishing.version_code
+            AND obj.visibility = 0
             AND obj.id = $1
-            
+            -- The newest build among the allowed tracks, the least stable track on a tie
+            ORDER BY publishing.version_code DESC, publishing.track_id DESC
             LIMIT 1
             "#,
-            id
+            id,
+            track_id
         )
             .fetch_optional(self.pool())
             .await?;
@@ -73,36 +80,69 @@ impl ObjectRepo {
         &self,
         platform_id: i32,
         category_id: i32,
+        sort: ChartSort,
+        cursor: Option<ChartCursor>,
         limit: i64,
         offset: i64,
-    ) -> ClientResult<Vec<Asset>> {
+    ) -> ClientResult<Vec<ChartAsset>> {
+        let by_rating = sort == ChartSort::Rating;
+        let by_trending = sort == ChartSort::Trending;
+
         let result = sqlx::query_as!(
-            Asset,
+            ChartAsset,
             r#"
             SELECT
                 name, package_name, address, logo, description,
-                type_id, category_id, platform_id,
+                obj.type_id, obj.category_id, obj.platform_id,
                 is_os_verified, is_hidden,
-                price, obj.id, rating, downloads, assetlink_sync.domain as website
+                price, obj.id, rating, rating_count, downloads, assetlink_sync.domain as website,
+                COALESCE(chart_order.position, 2147483647) AS "chart_position!",
+                COALESCE(trending_score.score, 0) AS "trending_score!"
             FROM obj
             INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id = 1
             INNER JOIN assetlink_sync ON assetlink_sync.asset_address = obj.address AND assetlink_sync.status = 1
             INNER JOIN build_request ON build_request.asset_address = obj.address AND build_request.status = 1
-            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1
+            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1 AND validation_proof.request_id IS NULL
+            LEFT JOIN chart_order ON chart_order.asset_id = obj.id
+                AND chart_order.platform_id = obj.platform_id
+                AND chart_order.category_id = obj.category_id
+            LEFT JOIN trending_score ON trending_score.asset_id = obj.id
              
             WHERE build_request.owner_version = assetlink_sync.owner_version
             AND build_request.owner_version = validation_proof.owner_version
             AND build_request.version_code = publishing.version_code
-            AND platform_id = $1
-            AND category_id = $2
-
-            ORDER BY downloads DESC
-            LIMIT $3 OFFSET $4
+            AND obj.visibility = 0
+            AND obj.platform_id = $1
+            AND obj.category_id = $2
+            AND ($3 = false OR obj.rating_count >= $4)
+            -- Keyset of the order below, descending parts are negated to compare as a single row
+            AND ($5::INT IS NULL OR (
+                COALESCE(chart_order.position, 2147483647),
+                -(CASE WHEN $3 THEN obj.rating ELSE 0 END),
+                -(CASE WHEN $11 THEN COALESCE(trending_score.score, 0) ELSE 0 END),
+                -downloads,
+                -obj.id
+            ) > ($5, -$6::REAL, -$12::REAL, -$7::BIGINT, -$8::BIGINT))
+
+            ORDER BY COALESCE(chart_order.position, 2147483647) ASC,
+                CASE WHEN $3 THEN obj.rating ELSE 0 END DESC,
+                CASE WHEN $11 THEN COALESCE(trending_score.score, 0) ELSE 0 END DESC,
+                downloads DESC,
+                obj.id DESC
+            LIMIT $9 OFFSET $10
             "#,
             platform_id,
             category_id,
+            by_rating,
+            chart_min_rating_count(),
+            cursor.as_ref().map(|cursor| cursor.position),
+            cursor.as_ref().map(|cursor| cursor.rating),
+            cursor.as_ref().map(|cursor| cursor.downloads),
+            cursor.as_ref().map(|cursor| cursor.id),
             limit,
-            offset
+            offset,
+            by_trending,
+            cursor.as_ref().map(|cursor| cursor.score),
         )
             .fetch_all(self.pool())
             .await?;
@@ -113,37 +153,71 @@ impl ObjectRepo {
     pub async fn chart_by_app_type(
         &self,
         platform_id: i32,
-        type_id: Option<ObjTypeId>, // TODO split on app/game when it will be many apps
+        type_id: Option<ObjTypeId>,
+        sort: ChartSort,
+        cursor: Option<ChartCursor>,
         limit: i64,
         offset: i64,
-    ) -> ClientResult<Vec<Asset>> {
+    ) -> ClientResult<Vec<ChartAsset>> {
+        let type_id: Option<i32> = type_id.map(|type_id| type_id.into());
+        let by_rating = sort == ChartSort::Rating;
+        let by_trending = sort == ChartSort::Trending;
+
         let result = sqlx::query_as!(
-            Asset,
+            ChartAsset,
             r#"
             SELECT
                 name, package_name, address, logo, description,
-                type_id, category_id, platform_id,
+                obj.type_id, obj.category_id, obj.platform_id,
                 is_os_verified, is_hidden,
-                price, obj.id, rating, downloads, assetlink_sync.domain as website
+                price, obj.id, rating, rating_count, downloads, assetlink_sync.domain as website,
+                COALESCE(chart_order.position, 2147483647) AS "chart_position!",
+                COALESCE(trending_score.score, 0) AS "trending_score!"
             FROM obj
             INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id = 1
             INNER JOIN assetlink_sync ON assetlink_sync.asset_address = obj.address AND assetlink_sync.status = 1
             INNER JOIN build_request ON build_request.asset_address = obj.address AND build_request.status = 1
-            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1
+            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1 AND validation_proof.request_id IS NULL
+            LEFT JOIN chart_order ON chart_order.asset_id = obj.id
+                AND chart_order.platform_id = obj.platform_id
+                AND chart_order.category_id = 0
+            LEFT JOIN trending_score ON trending_score.asset_id = obj.id
              
             WHERE build_request.owner_version = assetlink_sync.owner_version
             AND build_request.owner_version = validation_proof.owner_version
             AND build_request.version_code = publishing.version_code
-            AND platform_id = $1
---             AND type_id = $2
+            AND obj.visibility = 0
+            AND obj.platform_id = $1
+            AND ($12::INT IS NULL OR obj.type_id = $12)
+            AND ($2 = false OR obj.rating_count >= $3)
+            -- Keyset of the order below, descending parts are negated to compare as a single row
+            AND ($4::INT IS NULL OR (
+                COALESCE(chart_order.position, 2147483647),
+                -(CASE WHEN $2 THEN obj.rating ELSE 0 END),
+                -(CASE WHEN $10 THEN COALESCE(trending_score.score, 0) ELSE 0 END),
+                -downloads,
+                -obj.id
+            ) > ($4, -$5::REAL, -$11::REAL, -$6::BIGINT, -$7::BIGINT))
             
-            ORDER BY downloads DESC
-            LIMIT $2 OFFSET $3
+            ORDER BY COALESCE(chart_order.position, 2147483647) ASC,
+                CASE WHEN $2 THEN obj.rating ELSE 0 END DESC,
+                CASE WHEN $10 THEN COALESCE(trending_score.score, 0) ELSE 0 END DESC,
+                downloads DESC,
+                obj.id DESC
+            LIMIT $8 OFFSET $9
             "#,
             platform_id,
-            // type_id,
+            by_rating,
+            chart_min_rating_count(),
+            cursor.as_ref().map(|cursor| cursor.position),
+            cursor.as_ref().map(|cursor| cursor.rating),
+            cursor.as_ref().map(|cursor| cursor.downloads),
+            cursor.as_ref().map(|cursor| cursor.id),
             limit,
-            offset
+            offset,
+            by_trending,
+            cursor.as_ref().map(|cursor| cursor.score),
+            type_id,
         )
             .fetch_all(self.pool())
             .await?;
@@ -151,6 +225,131 @@ impl ObjectRepo {
         return Ok(result)
     }
     
+    // Listed assets of the curated list, in the order of `ids`
+    pub async fn find_listed_by_ids(
+        &self,
+        platform_id: i32,
+        ids: &[i64],
+        limit: i64,
+    ) -> ClientResult<Vec<Asset>> {
+        let result = sqlx::query_as!(
+            Asset,
+            r#"
+            SELECT
+                name, package_name, address, logo, description,
+                obj.type_id, obj.category_id, obj.platform_id,
+                is_os_verified, is_hidden,
+                price, obj.id, rating, rating_count, downloads, assetlink_sync.domain as website
+            FROM obj
+            INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id = 1
+            INNER JOIN assetlink_sync ON assetlink_sync.asset_address = obj.address AND assetlink_sync.status = 1
+            INNER JOIN build_request ON build_request.asset_address = obj.address AND build_request.status = 1
+            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1 AND validation_proof.request_id IS NULL
+
+            WHERE build_request.owner_version = assetlink_sync.owner_version
+            AND build_request.owner_version = validation_proof.owner_version
+            AND build_request.version_code = publishing.version_code
+            AND obj.visibility = 0
+            AND obj.platform_id = $1
+            AND obj.id = ANY($2)
+
+            ORDER BY array_position($2, obj.id)
+            LIMIT $3
+            "#,
+            platform_id,
+            ids,
+            limit
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    // First published to production recently
+    pub async fn find_new_releases(
+        &self,
+        platform_id: i32,
+        type_id: &ObjTypeId,
+        limit: i64,
+    ) -> ClientResult<Vec<Asset>> {
+        let type_id: i32 = type_id.clone().into();
+        let result = sqlx::query_as!(
+            Asset,
+            r#"
+            SELECT
+                name, package_name, address, logo, description,
+                obj.type_id, obj.category_id, obj.platform_id,
+                is_os_verified, is_hidden,
+                price, obj.id, rating, rating_count, downloads, assetlink_sync.domain as website
+            FROM obj
+            INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id = 1
+            INNER JOIN assetlink_sync ON assetlink_sync.asset_address = obj.address AND assetlink_sync.status = 1
+            INNER JOIN build_request ON build_request.asset_address = obj.address AND build_request.status = 1
+            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1 AND validation_proof.request_id IS NULL
+
+            WHERE build_request.owner_version = assetlink_sync.owner_version
+            AND build_request.owner_version = validation_proof.owner_version
+            AND build_request.version_code = publishing.version_code
+            AND obj.visibility = 0
+            AND obj.platform_id = $1
+            AND obj.type_id = $2
+
+            ORDER BY publishing.created_at DESC, obj.id DESC
+            LIMIT $3
+            "#,
+            platform_id,
+            type_id,
+            limit
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    // New production version published recently
+    pub async fn find_recently_updated(
+        &self,
+        platform_id: i32,
+        type_id: &ObjTypeId,
+        limit: i64,
+    ) -> ClientResult<Vec<Asset>> {
+        let type_id: i32 = type_id.clone().into();
+        let result = sqlx::query_as!(
+            Asset,
+            r#"
+            SELECT
+                name, package_name, address, logo, description,
+                obj.type_id, obj.category_id, obj.platform_id,
+                is_os_verified, is_hidden,
+                price, obj.id, rating, rating_count, downloads, assetlink_sync.domain as website
+            FROM obj
+            INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id = 1
+            INNER JOIN assetlink_sync ON assetlink_sync.asset_address = obj.address AND assetlink_sync.status = 1
+            INNER JOIN build_request ON build_request.asset_address = obj.address AND build_request.status = 1
+            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1 AND validation_proof.request_id IS NULL
+
+            WHERE build_request.owner_version = assetlink_sync.owner_version
+            AND build_request.owner_version = validation_proof.owner_version
+            AND build_request.version_code = publishing.version_code
+            AND obj.visibility = 0
+            AND obj.platform_id = $1
+            AND obj.type_id = $2
+
+            ORDER BY publishing.updated_at DESC, obj.id DESC
+            LIMIT $3
+            "#,
+            platform_id,
+            type_id,
+            limit
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
     pub async fn has_by_address(
         &self,
         upper_address: &str,
@@ -172,14 +371,16 @@ impl ObjectRepo {
     pub async fn find_by_address(
         &self,
         address: &str,
+        track_id: &TrackId,
     ) -> ClientResult<Option<RichAsset>> {
+        let track_id: i32 = track_id.clone().into();
         let result = sqlx::query_as!(
             RichAsset,
             r#"
             SELECT
                 obj.id, name, package_name, address, logo, description,
                 type_id, category_id, platform_id,
-                price, rating, downloads, assetlink_sync.domain as website,
+                price, rating, rating_count, downloads, assetlink_sync.domain as website,
                 
                 is_os_verified,
                 COALESCE(assetlink_sync.status = 1, false) AS "is_ownership_verified!: bool",
@@ -187,20 +388,22 @@ impl ObjectRepo {
                 
             FROM obj
                 
-            INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id = 1
+            INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id BETWEEN 1 AND $2
             INNER JOIN assetlink_sync ON assetlink_sync.asset_address = obj.address AND assetlink_sync.status = 1
             INNER JOIN build_request ON build_request.asset_address = obj.address AND build_request.status = 1
-            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1
+            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1 AND validation_proof.request_id IS NULL
             
             WHERE build_request.version_code = publishing.version_code
             AND build_request.owner_version = assetlink_sync.owner_version
             AND build_request.owner_version = validation_proof.owner_version
+            AND obj.visibility = 0
             AND address = $1
-            ORDER BY obj.created_at DESC
+            ORDER BY publishing.version_code DESC, publishing.track_id DESC
             
             LIMIT 1
             "#,
-            address
+            address,
+            track_id
         )
             .fetch_optional(self.pool())
             .await?;
@@ -209,21 +412,33 @@ impl ObjectRepo {
     }
 
     // TODO v2 remove copy
-    pub async fn insert_or_update(&self, data: &NewAsset) -> ClientResult<()> {
+    // `block_number` of the creating event is kept on update, it's only used to roll back reorged blocks.
+    // Gives the owner of a newly created row, an updated one gives `None`
+    pub async fn insert_or_update<'e, E: PgExecutor<'e>>(
+        &self,
+        executor: E,
+        data: &NewAsset,
+        block_number: Option<i64>,
+    ) -> ClientResult<Option<String>> {
         let type_id: i32 = data.type_id.clone().into();
         let category_id: i32 = data.category_id.clone().into();
         let platform_id: i32 = data.platform_id.clone().into();
         
-        let object = sqlx::query_as!(
-            Object,
+        let created = sqlx::query_scalar!(
             r#"
             INSERT INTO obj (
                 name, package_name, address, logo, description,
                 type_id, category_id, platform_id,
-                is_os_verified, is_hidden, price
+                is_os_verified, is_hidden, price, block_number, owner
             )
             
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            VALUES ($1, $2, $3::VARCHAR, $4, $5, $6, $7, $8, $9, $10, $11, $12,
+                -- Asset rows appear with the first build, long after the app changed hands
+                COALESCE(
+                    (SELECT new_owner FROM ownership_history WHERE asset_address = $3 ORDER BY block_number DESC, log_index DESC LIMIT 1),
+                    (SELECT publisher_address FROM publisher_app WHERE asset_address = $3)
+                )
+            )
             
             ON CONFLICT (address) DO UPDATE SET
                 name = EXCLUDED.name,
@@ -233,8 +448,9 @@ impl ObjectRepo {
                 category_id = EXCLUDED.category_id,
                 platform_id = EXCLUDED.platform_id,
                 is_os_verified = EXCLUDED.is_os_verified,
-                is_hidden = EXCLUDED.is_hidden,
                 price = EXCLUDED.price
+
+            RETURNING CASE WHEN xmax = 0 THEN COALESCE(owner, '') END AS created
             "#,
             data.name,
             data.id,
@@ -246,12 +462,80 @@ impl ObjectRepo {
             platform_id,
             data.is_os_verified,
             data.is_hidden,
-            data.price
+            data.price,
+            block_number
         )
-            .execute(self.pool())
+            .fetch_one(executor)
             .await?;
 
-        Ok(())
+        return Ok(created);
+    }
+
+    // Owner read from the app contract, it replaces the one derived from the history
+    pub async fn update_owner<'e, E: PgExecutor<'e>>(&self, executor: E, address: &str, owner: &str) -> ClientResult<()> {
+        sqlx::query!(
+            "UPDATE obj SET owner = $2 WHERE address = $1",
+            address,
+            owner
+        )
+            .execute(executor)
+            .await?;
+
+        return Ok(());
+    }
+
+    // Everything synced for the assets, missing rows are left out of the json
+    pub async fn find_states(&self, addresses: &[String]) -> ClientResult<Vec<AssetState>> {
+        let result = sqlx::query_as!(
+            AssetState,
+            r#"
+            SELECT
+                target.address AS "address!",
+                jsonb_strip_nulls(jsonb_build_object(
+                    'obj', (
+                        SELECT jsonb_build_object(
+                            'name', name, 'packageName', package_name, 'logo', logo, 'description', description,
+                            'typeId', type_id, 'categoryId', category_id, 'platformId', platform_id,
+                            'isOsVerified', is_os_verified, 'price', price, 'owner', owner
+                        )
+                        FROM obj WHERE address = target.address
+                    ),
+                    'tracks', (
+                        SELECT jsonb_object_agg(track_id, jsonb_build_object('versionCode', version_code, 'isActive', is_active))
+                        FROM publishing WHERE asset_address = target.address
+                    ),
+                    'requests', (
+                        SELECT jsonb_object_agg(id, jsonb_build_object('status', status, 'blockId', block_id))
+                        FROM build_request WHERE asset_address = target.address
+                    ),
+                    'artifacts', (
+                        SELECT jsonb_object_agg(version_code, jsonb_build_object(
+                            'refId', ref_id, 'protocolId', protocol_id, 'size', size, 'versionName', version_name, 'checksum', checksum
+                        ))
+                        FROM artifact WHERE asset_address = target.address
+                    ),
+                    'assetlinks', (
+                        SELECT jsonb_object_agg(owner_version || ':' || domain || ':' || status, true)
+                        FROM assetlink_sync WHERE asset_address = target.address
+                    ),
+                    'proofs', (
+                        SELECT jsonb_object_agg(COALESCE('request:' || request_id, 'owner:' || owner_version), status)
+                        FROM validation_proof WHERE asset_address = target.address
+                    ),
+                    'owners', (
+                        SELECT jsonb_object_agg(block_number || ':' || log_index, new_owner)
+                        FROM ownership_history WHERE asset_address = target.address
+                    )
+                ))::TEXT AS "state!"
+
+            FROM UNNEST($1::VARCHAR[]) AS target(address)
+            "#,
+            addresses
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result);
     }
 
     pub async fn update_from_graph(&self, data: &NewAsset) -> ClientResult<u64> {
@@ -279,8 +563,9 @@ impl ObjectRepo {
         Ok(result.rows_affected())
     }
 
-    pub async fn update_app_graph(
+    pub async fn update_app_graph<'e, E: PgExecutor<'e>>(
         &self,
+        executor: E,
         app: &AppAsset,
     ) -> ClientResult<()>  {
         let category = CategoryId::from(app.categoryId);
@@ -304,7 +589,7 @@ impl ObjectRepo {
             Into::<i32>::into(platform),
             app.id,
         )
-            .execute(self.pool())
+            .execute(executor)
             .await?;
 
         return Ok(())
@@ -350,6 +635,62 @@ impl ObjectRepo {
     }
     
 
+    pub async fn set_visibility(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: i64,
+        visibility: Visibility,
+        reason: Option<String>,
+    ) -> ClientResult<Option<String>> {
+        let is_hidden = visibility != Visibility::Visible;
+        let visibility: i32 = visibility.into();
+
+        let address = sqlx::query_scalar!(
+            r#"
+            UPDATE obj
+            SET
+                visibility = $1,
+                visibility_reason = $2,
+                is_hidden = $3,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $4
+            RETURNING address
+            "#,
+            visibility,
+            reason,
+            is_hidden,
+            id
+        )
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        return Ok(address)
+    }
+
+    pub async fn find_visibility_by_id(&self, id: i64) -> ClientResult<Option<AssetVisibility>> {
+        let result = sqlx::query_as!(
+            AssetVisibility,
+            "SELECT visibility, visibility_reason FROM obj WHERE id = $1",
+            id
+        )
+            .fetch_optional(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    pub async fn find_visibility_by_address(&self, address: &str) -> ClientResult<Option<AssetVisibility>> {
+        let result = sqlx::query_as!(
+            AssetVisibility,
+            "SELECT visibility, visibility_reason FROM obj WHERE address = $1",
+            address
+        )
+            .fetch_optional(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
     pub async fn delete(&self, del_id: i64) -> ClientResult<u64> {
         let result = sqlx::query!(
             r#"
@@ -393,3 +734,130 @@ impl ObjectRepo {
         }
     }
 }
+
+#[tokio::test]
+#[ignore = "needs a migrated DATABASE_URL"]
+async fn check_feed_sources_keep_type() {
+    use crate::env;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let repo = ObjectRepo::new(PgClient::connect(env::psql_url().as_ref()).await.unwrap());
+
+    // Rows are committed, a platform no client asks for keeps them out of real feeds
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    let platform_id = 1_000_000 + (nanos % 1_000_000) as i32;
+    let app = format!("0x{:040x}", nanos);
+    let game = format!("0x{:040x}", nanos + 1);
+    let assets = [(app.as_str(), ObjTypeId::App), (game.as_str(), ObjTypeId::Game)];
+
+    for (address, type_id) in assets.iter() {
+        let type_id: i32 = type_id.clone().into();
+        sqlx::query("INSERT INTO obj (name, package_name, address, category_id, platform_id, type_id, is_hidden) VALUES ('Feed', 'com.feed.' || $1, $1, 0, $2, $3, false)")
+            .bind(address)
+            .bind(platform_id)
+            .bind(type_id)
+            .execute(repo.pool())
+            .await
+            .unwrap();
+
+        // Published, linked and validated first version
+        let statements = [
+            "INSERT INTO publishing (asset_address, track_id, version_code, is_active) VALUES ($1, 1, 1, true)",
+            "INSERT INTO assetlink_sync (asset_address, domain, owner_version, status) VALUES ($1, 'feed.test', 1, 1)",
+            "INSERT INTO build_request (request_type_id, track_id, asset_address, version_code, owner_version, status) VALUES (1, 1, $1, 1, 1, 1)",
+            "INSERT INTO validation_proof (asset_address, owner_version, status) VALUES ($1, 1, 1)",
+        ];
+        for statement in statements {
+            sqlx::query(statement)
+                .bind(address)
+                .execute(repo.pool())
+                .await
+                .unwrap();
+        }
+    }
+
+    let addresses = |assets: Vec<Asset>| assets.into_iter().map(|asset| asset.address).collect::<Vec<_>>();
+    let releases = repo.find_new_releases(platform_id, &ObjTypeId::Game, 10).await;
+    let updated = repo.find_recently_updated(platform_id, &ObjTypeId::App, 10).await;
+    let chart = repo.chart_by_app_type(platform_id, Some(ObjTypeId::Game), ChartSort::Downloads, None, 10, 0).await;
+    let all = repo.chart_by_app_type(platform_id, None, ChartSort::Downloads, None, 10, 0).await;
+
+    for table in ["validation_proof", "build_request", "assetlink_sync", "publishing", "obj"] {
+        let column = if table == "obj" { "address" } else { "asset_address" };
+        sqlx::query(format!("DELETE FROM {} WHERE {} = ANY($1)", table, column).as_str())
+            .bind([app.clone(), game.clone()])
+            .execute(repo.pool())
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(addresses(releases.unwrap()), vec![game.clone()]);
+    assert_eq!(addresses(updated.unwrap()), vec![app.clone()]);
+    assert_eq!(chart.unwrap().into_iter().map(|asset| asset.address).collect::<Vec<_>>(), vec![game.clone()]);
+    assert_eq!(all.unwrap().len(), 2);
+}
+
+// A beta client sees the newest build of both tracks, a release one only the release build
+#[tokio::test]
+#[ignore = "needs a migrated DATABASE_URL"]
+async fn check_track_picks_newest_build() {
+    use crate::env;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let repo = ObjectRepo::new(PgClient::connect(env::psql_url().as_ref()).await.unwrap());
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    let platform_id = 1_000_000 + (nanos % 1_000_000) as i32;
+    let address = format!("0x{:040x}", nanos);
+    let id = sqlx::query_scalar::<_, i64>("INSERT INTO obj (name, package_name, address, category_id, platform_id, type_id, is_hidden) VALUES ('Track', 'com.track.' || $1, $1, 0, $2, 1, false) RETURNING id")
+        .bind(&address)
+        .bind(platform_id)
+        .fetch_one(repo.pool())
+        .await
+        .unwrap();
+
+    // Release has version 1 of the first owner version, beta has version 2 of the second one
+    let statements = [
+        "INSERT INTO publishing (asset_address, track_id, version_code, is_active) VALUES ($1, 1, 1, true)",
+        "INSERT INTO publishing (asset_address, track_id, version_code, is_active) VALUES ($1, 2, 2, true)",
+        "INSERT INTO assetlink_sync (asset_address, domain, owner_version, status) VALUES ($1, 'release.test', 1, 1)",
+        "INSERT INTO assetlink_sync (asset_address, domain, owner_version, status) VALUES ($1, 'beta.test', 2, 1)",
+        "INSERT INTO build_request (request_type_id, track_id, asset_address, version_code, owner_version, status) VALUES (1, 1, $1, 1, 1, 1)",
+        "INSERT INTO build_request (request_type_id, track_id, asset_address, version_code, owner_version, status) VALUES (1, 2, $1, 2, 2, 1)",
+        "INSERT INTO validation_proof (asset_address, owner_version, status) VALUES ($1, 1, 1)",
+        "INSERT INTO validation_proof (asset_address, owner_version, status) VALUES ($1, 2, 1)",
+    ];
+    for statement in statements {
+        sqlx::query(statement)
+            .bind(&address)
+            .execute(repo.pool())
+            .await
+            .unwrap();
+    }
+
+    let mut websites = vec![];
+    for track_id in [TrackId::Release, TrackId::Beta, TrackId::Alpha] {
+        let by_id = repo.find_by_id(id, &track_id).await;
+        let by_address = repo.find_by_address(&address, &track_id).await;
+        websites.push((
+            by_id.map(|asset| asset.and_then(|asset| asset.website)),
+            by_address.map(|asset| asset.and_then(|asset| asset.website)),
+        ));
+    }
+
+    for table in ["validation_proof", "build_request", "assetlink_sync", "publishing", "obj"] {
+        let column = if table == "obj" { "address" } else { "asset_address" };
+        sqlx::query(format!("DELETE FROM {} WHERE {} = $1", table, column).as_str())
+            .bind(&address)
+            .execute(repo.pool())
+            .await
+            .unwrap();
+    }
+
+    let website = |domain: &str| Some(domain.to_string());
+    for (i, expected) in ["release.test", "beta.test", "beta.test"].into_iter().enumerate() {
+        let (by_id, by_address) = &websites[i];
+        assert_eq!(by_id.as_ref().unwrap(), &website(expected));
+        assert_eq!(by_address.as_ref().unwrap(), &website(expected));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::data::models::{ChangeOutboxEntry, NewChangeEvent};
+use crate::result::ClientResult;
+use db_psql::client::PgClient;
+use sqlx::{PgExecutor, PgPool};
+
+#[derive(Clone)]
+pub struct OutboxRepo {
+    client: PgClient,
+}
+
+impl OutboxRepo {
+
+    pub fn new(client: PgClient) -> Self {
+        Self { client }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        self.client.pool()
+    }
+
+    // Called in the transaction of the change, an event already in the outbox is kept as it is
+    pub async fn insert_event<'e, E: PgExecutor<'e>>(&self, executor: E, event: &NewChangeEvent) -> ClientResult<bool> {
+        let kind: i32 = event.kind.into();
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO change_outbox (event_id, kind, asset_address, payload, block_number)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (event_id) DO NOTHING
+            "#,
+            event.event_id,
+            kind,
+            event.asset_address,
+            event.payload,
+            event.block_number
+        )
+            .execute(executor)
+            .await?;
+
+        return Ok(result.rows_affected() > 0);
+    }
+
+    // Oldest first, so events of an asset reach kafka in commit order
+    pub async fn find_pending(&self, limit: i64) -> ClientResult<Vec<ChangeOutboxEntry>> {
+        let result = sqlx::query_as!(
+            ChangeOutboxEntry,
+            r#"
+            SELECT id, event_id, asset_address, payload, attempts
+            FROM change_outbox
+            WHERE published_at IS NULL
+            ORDER BY id
+            LIMIT $1
+            "#,
+            limit
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result);
+    }
+
+    pub async fn set_published(&self, id: i64) -> ClientResult<()> {
+        sqlx::query!(
+            "UPDATE change_outbox SET published_at = CURRENT_TIMESTAMP, last_error = NULL WHERE id = $1",
+            id
+        )
+            .execute(self.pool())
+            .await?;
+
+        return Ok(())
+    }
+
+    pub async fn set_failed(&self, id: i64, error: &str) -> ClientResult<()> {
+        sqlx::query!(
+            "UPDATE change_outbox SET attempts = attempts + 1, last_error = $2 WHERE id = $1",
+            id,
+            error
+        )
+            .execute(self.pool())
+            .await?;
+
+        return Ok(())
+    }
+
+    // Published events are only kept for inspection, unpublished ones are never removed here
+    pub async fn delete_published(&self, older_than_sec: i64) -> ClientResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM change_outbox
+            WHERE published_at IS NOT NULL
+            AND published_at < CURRENT_TIMESTAMP - make_interval(secs => $1::BIGINT)
+            "#,
+            older_than_sec
+        )
+            .execute(self.pool())
+            .await?;
+
+        return Ok(result.rows_affected());
+    }
+}
@@ -0,0 +1,196 @@
+use crate::data::id::OwnershipChangeKind;
+use crate::data::models::{NewOwnershipChange, OwnershipChange, OwnershipCursor};
+use crate::result::ClientResult;
+use db_psql::client::PgClient;
+use sqlx::{PgPool, Postgres, Transaction};
+
+#[derive(Clone)]
+pub struct OwnershipRepo {
+    client: PgClient,
+}
+
+impl OwnershipRepo {
+
+    pub fn new(client: PgClient) -> Self {
+        Self { client }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        self.client.pool()
+    }
+
+    // Only changes of known apps are kept, `obj.owner` follows the newest change on chain,
+    // so a retried old log doesn't override a later transfer
+    pub async fn insert_change(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        change: &NewOwnershipChange,
+        block_number: i64,
+    ) -> ClientResult<bool> {
+        let kind: i32 = change.kind.into();
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO ownership_history (
+                asset_address, prev_owner, new_owner, kind, tx_hash, log_index, block_number, changed_at
+            )
+            SELECT $1::VARCHAR, $2, $3, $4, $5, $6, $7, $8
+            WHERE EXISTS (SELECT 1 FROM publisher_app WHERE asset_address = $1)
+            OR EXISTS (SELECT 1 FROM obj WHERE address = $1)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+            change.asset_address,
+            change.prev_owner,
+            change.new_owner,
+            kind,
+            change.tx_hash,
+            change.log_index,
+            block_number,
+            change.changed_at
+        )
+            .execute(&mut **tx)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE obj SET owner = latest.new_owner
+            FROM (
+                SELECT new_owner FROM ownership_history
+                WHERE asset_address = $1
+                ORDER BY block_number DESC, log_index DESC
+                LIMIT 1
+            ) AS latest
+            WHERE obj.address = $1 AND obj.owner IS DISTINCT FROM latest.new_owner
+            "#,
+            change.asset_address
+        )
+            .execute(&mut **tx)
+            .await?;
+
+        return Ok(true);
+    }
+
+    // Contracts whose `OwnershipTransferred` logs the sync queries
+    pub async fn find_app_addresses(&self) -> ClientResult<Vec<String>> {
+        let result = sqlx::query_scalar!(
+            r#"
+            SELECT asset_address AS "address!" FROM publisher_app
+            UNION
+            SELECT address AS "address!" FROM obj
+            "#
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result);
+    }
+
+    // Owner history of a listed asset, newest change first
+    pub async fn find_history(
+        &self,
+        asset_id: i64,
+        cursor: Option<OwnershipCursor>,
+        limit: i64,
+        offset: i64,
+    ) -> ClientResult<Vec<OwnershipChange>> {
+        let (block_number, log_index) = match cursor {
+            Some(cursor) => (Some(cursor.block_number), Some(cursor.log_index)),
+            None => (None, None),
+        };
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                history.id,
+                history.kind,
+                history.prev_owner,
+                history.new_owner,
+                history.tx_hash,
+                history.block_number,
+                history.log_index,
+                history.changed_at
+
+            FROM obj
+            INNER JOIN ownership_history history ON history.asset_address = obj.address
+
+            WHERE obj.id = $1
+            AND obj.visibility = 0
+            AND ($2::BIGINT IS NULL OR (history.block_number, history.log_index) < ($2, $3::BIGINT))
+            ORDER BY history.block_number DESC, history.log_index DESC
+            LIMIT $4 OFFSET $5
+            "#,
+            asset_id,
+            block_number,
+            log_index,
+            limit,
+            offset
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        let history = rows.into_iter()
+            .map(|row| OwnershipChange {
+                id: row.id,
+                kind: OwnershipChangeKind::from(row.kind),
+                prev_owner: row.prev_owner,
+                new_owner: row.new_owner,
+                tx_hash: row.tx_hash,
+                block_number: row.block_number,
+                log_index: row.log_index,
+                changed_at: row.changed_at,
+            })
+            .collect();
+
+        return Ok(history)
+    }
+}
+
+// Nothing is committed
+#[tokio::test]
+#[ignore = "needs a migrated DATABASE_URL"]
+async fn check_owner_follows_newest_change() {
+    use crate::env;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let repo = OwnershipRepo::new(PgClient::connect(env::psql_url().as_ref()).await.unwrap());
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    let asset = format!("0x{:040x}", nanos);
+    let change = |kind: OwnershipChangeKind, owner: &str, log_index: i64| NewOwnershipChange {
+        asset_address: asset.clone(),
+        prev_owner: None,
+        new_owner: owner.to_string(),
+        kind,
+        tx_hash: format!("0x{:064x}", nanos),
+        log_index,
+        changed_at: None,
+    };
+
+    let mut tx = repo.client.start().await.unwrap();
+
+    // Unknown contracts emit `OwnershipTransferred` too
+    assert!(!repo.insert_change(&mut tx, &change(OwnershipChangeKind::Transferred, "0xA", 0), 10).await.unwrap());
+
+    sqlx::query("INSERT INTO obj (name, package_name, address, category_id, platform_id, type_id) VALUES ('Owner', 'com.owner.test', $1, 0, 0, 0)")
+        .bind(&asset)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    assert!(repo.insert_change(&mut tx, &change(OwnershipChangeKind::Created, "0xA", 1), 10).await.unwrap());
+    assert!(repo.insert_change(&mut tx, &change(OwnershipChangeKind::Transferred, "0xB", 2), 30).await.unwrap());
+    // Retried log of an earlier block
+    assert!(repo.insert_change(&mut tx, &change(OwnershipChangeKind::Transferred, "0xC", 3), 20).await.unwrap());
+    // Replay of a saved log
+    assert!(!repo.insert_change(&mut tx, &change(OwnershipChangeKind::Transferred, "0xD", 2), 30).await.unwrap());
+
+    let owner = sqlx::query_scalar::<_, Option<String>>("SELECT owner FROM obj WHERE address = $1")
+        .bind(&asset)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(owner.as_deref(), Some("0xB"));
+}
@@ -0,0 +1,117 @@
+use crate::data::models::{Asset, NewPublisher, NewPublisherApp, Publisher};
+use crate::result::ClientResult;
+use db_psql::client::PgClient;
+use sqlx::{PgExecutor, PgPool};
+
+#[derive(Clone)]
+pub struct PublisherRepo {
+    client: PgClient,
+}
+
+impl PublisherRepo {
+
+    pub fn new(client: PgClient) -> Self {
+        Self { client }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        self.client.pool()
+    }
+
+    pub async fn insert_publisher<'e, E: PgExecutor<'e>>(&self, executor: E, data: &NewPublisher) -> ClientResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO publisher (address, owner, name, block_number)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (address) DO NOTHING
+            "#,
+            data.address,
+            data.owner,
+            data.name,
+            data.block_number,
+        )
+            .execute(executor)
+            .await?;
+
+        return Ok(());
+    }
+
+    // Anyone can emit `AppCreated`, so only apps of accounts deployed by the factory are kept
+    pub async fn insert_app<'e, E: PgExecutor<'e>>(&self, executor: E, data: &NewPublisherApp) -> ClientResult<bool> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO publisher_app (publisher_address, asset_address, package_name, name, block_number)
+            SELECT $1::VARCHAR, $2, $3, $4, $5
+            WHERE EXISTS (SELECT 1 FROM publisher WHERE address = $1)
+            ON CONFLICT (asset_address) DO NOTHING
+            "#,
+            data.publisher_address,
+            data.asset_address,
+            data.package_name,
+            data.name,
+            data.block_number,
+        )
+            .execute(executor)
+            .await?;
+
+        return Ok(result.rows_affected() > 0);
+    }
+
+    // Emitters of `AppCreated`, the sync only queries logs of known accounts
+    pub async fn find_addresses(&self) -> ClientResult<Vec<String>> {
+        let result = sqlx::query_scalar!("SELECT address FROM publisher ORDER BY id")
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result);
+    }
+
+    pub async fn find_by_address(&self, address: &str) -> ClientResult<Option<Publisher>> {
+        let result = sqlx::query_as!(
+            Publisher,
+            r#"
+            SELECT id, address, owner, name, created_at
+            FROM publisher
+            WHERE address = $1
+            "#,
+            address
+        )
+            .fetch_optional(self.pool())
+            .await?;
+
+        return Ok(result);
+    }
+
+    // Same listing rules as charts, apps without a verified release build are not shown
+    pub async fn find_apps(&self, address: &str) -> ClientResult<Vec<Asset>> {
+        let result = sqlx::query_as!(
+            Asset,
+            r#"
+            SELECT
+                obj.name, obj.package_name, address, logo, description,
+                obj.type_id, obj.category_id, obj.platform_id,
+                is_os_verified, is_hidden,
+                price, obj.id, rating, rating_count, downloads, assetlink_sync.domain as website
+            FROM publisher_app
+            INNER JOIN obj ON obj.address = publisher_app.asset_address
+            INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id = 1
+            INNER JOIN assetlink_sync ON assetlink_sync.asset_address = obj.address AND assetlink_sync.status = 1
+            INNER JOIN build_request ON build_request.asset_address = obj.address AND build_request.status = 1
+            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1 AND validation_proof.request_id IS NULL
+
+            WHERE publisher_app.publisher_address = $1
+            AND build_request.owner_version = assetlink_sync.owner_version
+            AND build_request.owner_version = validation_proof.owner_version
+            AND build_request.version_code = publishing.version_code
+            AND obj.visibility = 0
+
+            ORDER BY obj.downloads DESC, obj.id DESC
+            "#,
+            address
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result);
+    }
+}
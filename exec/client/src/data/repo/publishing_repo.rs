@@ -6,7 +6,7 @@ use alloy::primitives::Address;
 use codegen_contracts::ext::ToChecksum;
 use core_std::hexer;
 use db_psql::client::PgClient;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 #[derive(Clone)]
 pub struct PublishingRepo {
@@ -23,14 +23,32 @@ impl PublishingRepo {
         self.client.pool()
     }
 
-    pub async fn insert_or_update(
-        &self, 
-        publishing: &Publishing
+    // Synced changes with `block_number` journal the previous track state, so a reorg can restore it
+    pub async fn insert_or_update<'e, E: PgExecutor<'e>>(
+        &self,
+        executor: E,
+        publishing: &Publishing,
+        block_number: Option<i64>,
     ) -> ClientResult<()> {
         let track_id: i32 = publishing.track_id.clone().into();
         let row = sqlx::query_as!(
             Publishing,
             r#"
+            WITH newer AS (
+                -- A retried log must not override a change synced from a later block
+                SELECT 1 FROM publishing_change
+                WHERE asset_address = $1 AND track_id = $2 AND block_number > $5
+            ), prev AS (
+                SELECT version_code, is_active
+                FROM publishing
+                WHERE asset_address = $1 AND track_id = $2
+            ), change AS (
+                INSERT INTO publishing_change (asset_address, track_id, prev_version_code, prev_is_active, block_number)
+                SELECT $1, $2, prev.version_code, prev.is_active, $5
+                FROM (SELECT 1) AS one
+                LEFT JOIN prev ON true
+                WHERE $5::BIGINT IS NOT NULL AND NOT EXISTS (SELECT 1 FROM newer)
+            )
             INSERT INTO publishing (
                 asset_address,
                 track_id,
@@ -42,14 +60,20 @@ impl PublishingRepo {
             
             ON CONFLICT (asset_address, track_id) DO UPDATE SET
                 version_code = EXCLUDED.version_code,
-                is_active = EXCLUDED.is_active
+                is_active = EXCLUDED.is_active,
+                updated_at = CASE WHEN publishing.version_code <> EXCLUDED.version_code
+                    THEN CURRENT_TIMESTAMP
+                    ELSE publishing.updated_at
+                END
+            WHERE NOT EXISTS (SELECT 1 FROM newer)
             "#,
             publishing.asset_address.checksum(),
             track_id,
             publishing.version_code,
-            publishing.is_active
+            publishing.is_active,
+            block_number
         )
-            .execute(self.pool())
+            .execute(executor)
             .await?;
 
         return Ok(())
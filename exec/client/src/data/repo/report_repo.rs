@@ -1,9 +1,14 @@
 use alloy::primitives::Address;
-use crate::data::models::{NewReport, Report};
-use crate::result::ClientResult;
+use crate::data::id::ReportStatus;
+use crate::data::models::{FlaggedAsset, NewReport, Report};
+use crate::env::{report_flag_threshold, report_limit_per_hour};
+use crate::result::{ClientError, ClientResult};
 use codegen_contracts::ext::ToChecksum;
 use db_psql::client::PgClient;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
+
+// Class of the advisory locks taken by `create`
+const REPORT_LOCK: i32 = 1;
 
 #[derive(Clone)]
 pub struct ReportRepo {
@@ -20,29 +25,218 @@ impl ReportRepo {
         self.client.pool()
     }
 
-    pub async fn create(&self, new_report: NewReport) -> ClientResult<()> {
+    pub async fn start(
+        &self
+    ) -> ClientResult<Transaction<'static, Postgres>> {
+        let transaction = self.client.start()
+            .await?;
+
+        return Ok(transaction);
+    }
+
+    // Throttled per email and ip, pending duplicates are rejected by `idx_reports_pending_dedup`.
+    // Returns true when the asset got flagged by this report.
+    pub async fn create(&self, new_report: NewReport, ip: &str) -> ClientResult<bool> {
+        let address = new_report.asset_address.checksum();
+        let category_id: i32 = new_report.category_id.into();
+        let mut tx = self.start().await?;
+
+        // Concurrent reports of the same email or ip wait here, so the count below can't be stale
         sqlx::query!(
             r#"
-            INSERT INTO report (asset_address, email, category_id, subcategory_id, description)
-            VALUES ($1, $2, $3, $4, $5)
+            SELECT pg_advisory_xact_lock($1, hashtext(key))
+            FROM UNNEST(ARRAY[$2, $3]::TEXT[]) AS key
+            ORDER BY hashtext(key)
+            "#,
+            REPORT_LOCK,
+            new_report.email,
+            ip
+        )
+            .fetch_all(&mut *tx)
+            .await?;
+
+        let result = sqlx::query_scalar!(
+            r#"
+            INSERT INTO report (asset_address, email, category_id, subcategory_id, description, ip)
+            SELECT $1::VARCHAR, $2::VARCHAR, $3::INT, $4::INT, $5::TEXT, $6::VARCHAR
+            WHERE (
+                SELECT COUNT(*) FROM report
+                WHERE (email = $2 OR ip = $6)
+                AND created_at > NOW() - INTERVAL '1 hour'
+            ) < $7::BIGINT
+            RETURNING id
             "#,
-            new_report.asset_address.checksum(),
+            address,
             new_report.email,
-            new_report.category_id,
+            category_id,
             new_report.subcategory_id,
-            new_report.description
+            new_report.description,
+            ip,
+            report_limit_per_hour()
         )
-            .execute(self.pool())
+            .fetch_optional(&mut *tx)
+            .await;
+
+        match result {
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                return Err(ClientError::Conflict("Report was already submitted".to_string()));
+            }
+            Err(e) => return Err(e.into()),
+            Ok(None) => {
+                return Err(ClientError::RateLimited("Too many reports, try again later".to_string()));
+            }
+            Ok(Some(_)) => {}
+        }
+
+        let reporters = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(DISTINCT email) AS "count!" FROM report
+            WHERE asset_address = $1 AND status IN (0, 1)
+            "#,
+            address
+        )
+            .fetch_one(&mut *tx)
             .await?;
 
-        return Ok(())
+        let mut flagged = false;
+        if reporters >= report_flag_threshold() {
+            let result = sqlx::query!(
+                r#"
+                UPDATE obj SET is_flagged = true, updated_at = CURRENT_TIMESTAMP
+                WHERE address = $1 AND is_flagged = false
+                "#,
+                address
+            )
+                .execute(&mut *tx)
+                .await?;
+
+            flagged = result.rows_affected() > 0;
+        }
+
+        tx.commit().await?;
+        return Ok(flagged)
+    }
+
+    // Flag is kept while the asset has pending reports
+    pub async fn set_status(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        report_id: i64,
+        status: ReportStatus,
+        note: Option<String>,
+    ) -> ClientResult<Option<Report>> {
+        // Locked until the caller commits, so concurrent moderators see each other's transition
+        let current = sqlx::query_scalar!("SELECT status FROM report WHERE id = $1 FOR UPDATE", report_id)
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        let Some(current) = current.map(ReportStatus::from) else {
+            return Ok(None);
+        };
+
+        if !current.can_move_to(&status) {
+            return Err(ClientError::Conflict(format!("Report can't move from {} to {}", current, status)));
+        }
+
+        let status: i32 = status.into();
+
+        let result = sqlx::query_as!(
+            Report,
+            r#"
+            UPDATE report SET status = $2, note = $3, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            RETURNING id, asset_address, email, category_id, subcategory_id, description, status, note, created_at
+            "#,
+            report_id,
+            status,
+            note
+        )
+            .fetch_optional(&mut **tx)
+            .await;
+
+        let result = match result {
+            Ok(result) => result,
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                return Err(ClientError::Conflict("Same report is already pending".to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let Some(report) = result else {
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE obj SET is_flagged = false, updated_at = CURRENT_TIMESTAMP
+            WHERE address = $1 AND is_flagged = true
+            AND NOT EXISTS (
+                SELECT 1 FROM report WHERE report.asset_address = obj.address AND report.status IN (0, 1)
+            )
+            "#,
+            report.asset_address
+        )
+            .execute(&mut **tx)
+            .await?;
+
+        return Ok(Some(report))
+    }
+
+    pub async fn find_by_status(
+        &self,
+        status: ReportStatus,
+        limit: i64,
+        offset: i64,
+    ) -> ClientResult<Vec<Report>> {
+        let status: i32 = status.into();
+
+        let result = sqlx::query_as!(
+            Report,
+            r#"
+            SELECT id, asset_address, email, category_id, subcategory_id, description, status, note, created_at
+            FROM report
+            WHERE status = $1
+            ORDER BY id DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            status,
+            limit,
+            offset
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    pub async fn find_flagged(&self, limit: i64, offset: i64) -> ClientResult<Vec<FlaggedAsset>> {
+        let result = sqlx::query_as!(
+            FlaggedAsset,
+            r#"
+            SELECT
+                obj.id, obj.name, obj.address, obj.visibility,
+                COUNT(report.id) AS "pending_reports!"
+            FROM obj
+            LEFT JOIN report ON report.asset_address = obj.address AND report.status IN (0, 1)
+            WHERE obj.is_flagged = true
+            GROUP BY obj.id
+            ORDER BY COUNT(report.id) DESC, obj.id
+            LIMIT $1 OFFSET $2
+            "#,
+            limit,
+            offset
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        return Ok(result)
     }
 
     pub async fn find_by_id(&self, report_id: i64) -> ClientResult<Option<Report>> {
         let result = sqlx::query_as!(
             Report,
             r#"
-            SELECT id, asset_address, email, category_id, subcategory_id, description
+            SELECT id, asset_address, email, category_id, subcategory_id, description, status, note, created_at
             FROM report WHERE id = $1
             "#,
             report_id
@@ -63,7 +257,7 @@ impl ReportRepo {
         let result = sqlx::query_as!(
             Report,
             r#"
-            SELECT id, asset_address, email, category_id, subcategory_id, description
+            SELECT id, asset_address, email, category_id, subcategory_id, description, status, note, created_at
             FROM report
             WHERE asset_address = $1
             LIMIT $2 OFFSET $3
@@ -90,7 +284,7 @@ impl ReportRepo {
         let result = sqlx::query_as!(
             Report,
             r#"
-            SELECT id, asset_address, email, category_id, subcategory_id, description
+            SELECT id, asset_address, email, category_id, subcategory_id, description, status, note, created_at
             FROM report
             WHERE email = $1
             LIMIT $2 OFFSET $3
@@ -118,4 +312,109 @@ impl ReportRepo {
 
         Ok(result.rows_affected())
     }
-}
\ No newline at end of file
+}
+#[test]
+fn check_report_category_number() {
+    use crate::data::id::ReportCategory;
+
+    let report = |category: &str| serde_json::from_str::<NewReport>(
+        format!(r#"{{"assetAddress":"0x0","categoryId":{},"subcategoryId":0,"email":"a@b.c"}}"#, category).as_str()
+    );
+
+    assert_eq!(report("4").unwrap().category_id, ReportCategory::CybercrimeMalware);
+    assert_eq!(report("99").unwrap().category_id, ReportCategory::Unspecified);
+    assert!(report(r#""CybercrimeMalware""#).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a migrated DATABASE_URL"]
+async fn check_report_throttle_is_atomic() {
+    use crate::env;
+    use crate::data::id::ReportCategory;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let repo = ReportRepo::new(PgClient::connect(env::psql_url().as_ref()).await.unwrap());
+
+    // Nothing is flagged, the asset isn't in `obj`
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    let ip = format!("test-{}", nanos % 1_000_000_000_000);
+    let tasks = (0..report_limit_per_hour() * 2)
+        .map(|i| {
+            let repo = repo.clone();
+            let ip = ip.clone();
+            let report = NewReport {
+                asset_address: format!("0x{:040x}", nanos),
+                category_id: ReportCategory::CybercrimeMalware,
+                subcategory_id: 0,
+                email: format!("{}-{}@throttle.test", nanos, i),
+                description: None,
+            };
+            tokio::spawn(async move { repo.create(report, ip.as_str()).await })
+        })
+        .collect::<Vec<_>>();
+
+    let mut created = 0;
+    let mut limited = 0;
+    for task in tasks {
+        match task.await.unwrap() {
+            Ok(_) => created += 1,
+            Err(ClientError::RateLimited(_)) => limited += 1,
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+
+    sqlx::query("DELETE FROM report WHERE ip = $1")
+        .bind(&ip)
+        .execute(repo.pool())
+        .await
+        .unwrap();
+
+    assert_eq!(created, report_limit_per_hour());
+    assert_eq!(limited, report_limit_per_hour());
+}
+
+#[tokio::test]
+#[ignore = "needs a migrated DATABASE_URL"]
+async fn check_report_transitions() {
+    use crate::env;
+    use crate::data::id::ReportCategory;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let repo = ReportRepo::new(PgClient::connect(env::psql_url().as_ref()).await.unwrap());
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    let ip = format!("test-{}", nanos % 1_000_000_000_000);
+    let report = NewReport {
+        asset_address: format!("0x{:040x}", nanos),
+        category_id: ReportCategory::CybercrimeMalware,
+        subcategory_id: 0,
+        email: format!("{}@transition.test", nanos),
+        description: None,
+    };
+    repo.create(report, ip.as_str()).await.unwrap();
+    let report_id = sqlx::query_scalar::<_, i64>("SELECT id FROM report WHERE ip = $1")
+        .bind(&ip)
+        .fetch_one(repo.pool())
+        .await
+        .unwrap();
+
+    let mut tx = repo.pool().begin().await.unwrap();
+    let triaged = repo.set_status(&mut tx, report_id, ReportStatus::Triaged, None).await;
+    let dismissed = repo.set_status(&mut tx, report_id, ReportStatus::Dismissed, None).await;
+    let actioned = repo.set_status(&mut tx, report_id, ReportStatus::Actioned, None).await;
+    let reopened = repo.set_status(&mut tx, report_id, ReportStatus::Open, None).await;
+    let missing = repo.set_status(&mut tx, -1, ReportStatus::Dismissed, None).await;
+    drop(tx);
+
+    sqlx::query("DELETE FROM report WHERE ip = $1")
+        .bind(&ip)
+        .execute(repo.pool())
+        .await
+        .unwrap();
+
+    assert!(triaged.unwrap().is_some());
+    assert!(dismissed.unwrap().is_some());
+    assert!(matches!(actioned, Err(ClientError::Conflict(_))));
+    assert!(matches!(reopened, Err(ClientError::Conflict(_))));
+    assert!(missing.unwrap().is_none());
+}
@@ -1,8 +1,8 @@
 use std::ops::{Range, RangeInclusive};
-use crate::data::models::{NewReview, Review};
+use crate::data::models::{NewReview, RatingSummary, Review, ReviewCursor};
 use crate::result::{ClientError, ClientResult};
 use db_psql::client::PgClient;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 
 #[derive(Clone)]
 pub struct ReviewRepo {
@@ -21,22 +21,141 @@ impl ReviewRepo {
         self.client.pool()
     }
 
-    pub async fn create(&self, new_review: NewReview) -> ClientResult<()> {
+    pub async fn start(
+        &self
+    ) -> ClientResult<Transaction<'static, Postgres>> {
+        let transaction = self.client.start()
+            .await?;
+
+        return Ok(transaction);
+    }
+
+    // One review per wallet and asset, a newer signature replaces the previous review.
+    // Rating summary of the asset is updated in the same transaction.
+    pub async fn upsert(&self, new_review: NewReview) -> ClientResult<()> {
         if !RATING_RAGE.contains(&new_review.rating) {
             return Err(ClientError::Conflict("Rating must be between 1 and 5".to_string()));
         }
 
-        sqlx::query!(
+        let mut tx = self.start().await?;
+
+        // Serializes review writes of the asset, so the previous rating can't change under us
+        let locked = sqlx::query_scalar!(
+            "SELECT id FROM obj WHERE id = $1 FOR UPDATE",
+            new_review.asset_id
+        )
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        if locked.is_none() {
+            return Err(ClientError::NotFound);
+        }
+
+        let previous = sqlx::query_scalar!(
+            "SELECT rating FROM review WHERE asset_id = $1 AND user_id = $2",
+            new_review.asset_id,
+            new_review.user_id
+        )
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let result = sqlx::query!(
             r#"
-            INSERT INTO review (asset_id, user_id, rating, text)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO review (asset_id, user_id, rating, text, signature, signed_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (asset_id, user_id) DO UPDATE SET
+                rating = EXCLUDED.rating,
+                text = EXCLUDED.text,
+                signature = EXCLUDED.signature,
+                signed_at = EXCLUDED.signed_at,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE review.signed_at < EXCLUDED.signed_at
             "#,
             new_review.asset_id,
             new_review.user_id,
             new_review.rating,
-            new_review.text
+            new_review.text,
+            new_review.signature,
+            new_review.signed_at
         )
-            .execute(self.pool())
+            .execute(&mut *tx)
+            .await;
+
+        let result = match result {
+            Ok(result) => result,
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                return Err(ClientError::Conflict("Signature was already used".to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if result.rows_affected() == 0 {
+            return Err(ClientError::Conflict("Newer review already exists".to_string()));
+        }
+
+        let delta = rating_delta(Some(new_review.rating), previous);
+        self.apply_rating_delta(&mut tx, new_review.asset_id, &delta).await?;
+
+        tx.commit().await?;
+        return Ok(())
+    }
+
+    pub async fn find_summary(&self, asset_id: i64) -> ClientResult<Option<RatingSummary>> {
+        let result = sqlx::query_as!(
+            RatingSummary,
+            r#"
+            SELECT
+                rating AS average,
+                rating_count AS count,
+                ARRAY[rating_1, rating_2, rating_3, rating_4, rating_5] AS "histogram!"
+            FROM obj WHERE id = $1
+            "#,
+            asset_id
+        )
+            .fetch_optional(self.pool())
+            .await?;
+
+        return Ok(result)
+    }
+
+    async fn apply_rating_delta(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        asset_id: i64,
+        delta: &[i64; 5],
+    ) -> ClientResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE obj SET
+                rating_1 = rating_1 + $2,
+                rating_2 = rating_2 + $3,
+                rating_3 = rating_3 + $4,
+                rating_4 = rating_4 + $5,
+                rating_5 = rating_5 + $6,
+                rating_count = rating_count + $2 + $3 + $4 + $5 + $6
+            WHERE id = $1
+            "#,
+            asset_id,
+            delta[0],
+            delta[1],
+            delta[2],
+            delta[3],
+            delta[4]
+        )
+            .execute(&mut **tx)
+            .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE obj SET rating = CASE WHEN rating_count > 0
+                THEN (rating_1 + 2 * rating_2 + 3 * rating_3 + 4 * rating_4 + 5 * rating_5)::REAL / rating_count
+                ELSE 0
+            END
+            WHERE id = $1
+            "#,
+            asset_id
+        )
+            .execute(&mut **tx)
             .await?;
 
         return Ok(())
@@ -57,10 +176,11 @@ impl ReviewRepo {
         return Ok(result)
     }
 
-    // Find reviews for a specific object (leveraging index)
+    // Find reviews for a specific object, newest first (leveraging index)
     pub async fn find_by_asset_id(
         &self,
         asset_id: i64,
+        cursor: Option<ReviewCursor>,
         limit: i64,
         offset: i64,
     ) -> ClientResult<Vec<Review>> {
@@ -70,9 +190,12 @@ impl ReviewRepo {
             SELECT id, asset_id, user_id, rating, text
             FROM review
             WHERE asset_id = $1
-            LIMIT $2 OFFSET $3
+            AND ($2::BIGINT IS NULL OR id < $2)
+            ORDER BY id DESC
+            LIMIT $3 OFFSET $4
             "#,
             asset_id,
+            cursor.map(|cursor| cursor.id),
             limit,
             offset
         )
@@ -108,29 +231,75 @@ impl ReviewRepo {
     }
 
     pub async fn delete(&self, review_id: i64) -> ClientResult<u64> {
+        let mut tx = self.start().await?;
+
         let result = sqlx::query!(
             r#"
             DELETE FROM review WHERE id = $1
+            RETURNING asset_id, rating
             "#,
             review_id
         )
-            .execute(self.pool())
+            .fetch_optional(&mut *tx)
             .await?;
 
-        Ok(result.rows_affected())
+        let Some(deleted) = result else {
+            return Ok(0);
+        };
+
+        let delta = rating_delta(None, Some(deleted.rating));
+        self.apply_rating_delta(&mut tx, deleted.asset_id, &delta).await?;
+
+        tx.commit().await?;
+        Ok(1)
     }
 
     pub async fn delete_by_user_object(&self, usr_id: &str, obj_id: i64) -> ClientResult<u64> {
-        let result = sqlx::query!(
+        let mut tx = self.start().await?;
+
+        let result = sqlx::query_scalar!(
             r#"
             DELETE FROM review WHERE user_id = $1 AND asset_id = $2
+            RETURNING rating
             "#,
             usr_id,
             obj_id
         )
-            .execute(self.pool())
+            .fetch_optional(&mut *tx)
             .await?;
 
-        Ok(result.rows_affected())
+        let Some(rating) = result else {
+            return Ok(0);
+        };
+
+        let delta = rating_delta(None, Some(rating));
+        self.apply_rating_delta(&mut tx, obj_id, &delta).await?;
+
+        tx.commit().await?;
+        Ok(1)
+    }
+}
+
+// Histogram change for 1..=5 stars, ratings outside of the range are not counted
+fn rating_delta(added: Option<i32>, removed: Option<i32>) -> [i64; 5] {
+    let mut delta = [0i64; 5];
+
+    if let Some(rating) = added.filter(|rating| RATING_RAGE.contains(rating)) {
+        delta[rating as usize - 1] += 1;
+    }
+
+    if let Some(rating) = removed.filter(|rating| RATING_RAGE.contains(rating)) {
+        delta[rating as usize - 1] -= 1;
     }
-}
\ No newline at end of file
+
+    return delta;
+}
+
+#[test]
+fn check_rating_delta() {
+    assert_eq!(rating_delta(Some(5), None), [0, 0, 0, 0, 1]);
+    assert_eq!(rating_delta(None, Some(1)), [-1, 0, 0, 0, 0]);
+    assert_eq!(rating_delta(Some(4), Some(2)), [0, -1, 0, 1, 0]);
+    assert_eq!(rating_delta(Some(3), Some(3)), [0, 0, 0, 0, 0]);
+    assert_eq!(rating_delta(None, Some(0)), [0, 0, 0, 0, 0]);
+}
@@ -1,4 +1,4 @@
-use crate::data::models::Asset;
+use crate::data::models::{Asset, SearchCursor};
 use crate::result::ClientResult;
 use db_psql::client::PgClient;
 use sqlx::PgPool;
@@ -23,6 +23,7 @@ impl SearchRepo {
         term: &str,
         platform_id: i32,
         type_id: Option<ObjTypeId>, // TODO split on app/game when it will be many apps
+        cursor: Option<SearchCursor>,
         limit: i64,
         offset: i64,
     ) -> ClientResult<Vec<Asset>> {
@@ -34,26 +35,30 @@ impl SearchRepo {
                 name, package_name, address, logo, description,
                 type_id, category_id, platform_id,
                 is_os_verified, is_hidden,
-                price, obj.id, rating, downloads, assetlink_sync.domain as website
+                price, obj.id, rating, rating_count, downloads, assetlink_sync.domain as website
             FROM obj
             INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id = 1
             INNER JOIN assetlink_sync ON assetlink_sync.asset_address = obj.address AND assetlink_sync.status = 1
             INNER JOIN build_request ON build_request.asset_address = obj.address AND build_request.status = 1
-            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1
+            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1 AND validation_proof.request_id IS NULL
             
             WHERE name ILIKE $1
             AND build_request.owner_version = assetlink_sync.owner_version
             AND build_request.owner_version = validation_proof.owner_version
             AND build_request.version_code = publishing.version_code
+            AND obj.visibility = 0
             AND platform_id = $2
 --             AND type_id = $2
+            AND ($3::BIGINT IS NULL OR (downloads, obj.id) < ($3, $4::BIGINT))
             
-            ORDER BY downloads DESC
-            LIMIT $3 OFFSET $4
+            ORDER BY downloads DESC, obj.id DESC
+            LIMIT $5 OFFSET $6
             "#,
             search_pattern,
             platform_id,
             // type_id,
+            cursor.as_ref().map(|cursor| cursor.downloads),
+            cursor.as_ref().map(|cursor| cursor.id),
             limit,
             offset,
         )
@@ -68,6 +73,7 @@ impl SearchRepo {
         term: &str,
         platform_id: i32,
         category_id: i32,
+        cursor: Option<SearchCursor>,
         limit: i64,
         offset: i64,
     ) -> ClientResult<Vec<Asset>> {
@@ -79,26 +85,30 @@ impl SearchRepo {
                 name, package_name, address, logo, description,
                 type_id, category_id, platform_id,
                 is_os_verified, is_hidden,
-                price, obj.id, rating, downloads, assetlink_sync.domain as website
+                price, obj.id, rating, rating_count, downloads, assetlink_sync.domain as website
             FROM obj
             INNER JOIN publishing ON publishing.asset_address = obj.address AND publishing.track_id = 1
             INNER JOIN assetlink_sync ON assetlink_sync.asset_address = obj.address AND assetlink_sync.status = 1
             INNER JOIN build_request ON build_request.asset_address = obj.address AND build_request.status = 1
-            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1
+            INNER JOIN validation_proof ON validation_proof.asset_address = obj.address AND validation_proof.status = 1 AND validation_proof.request_id IS NULL
             
             WHERE name ILIKE $1
             AND build_request.owner_version = assetlink_sync.owner_version
             AND build_request.owner_version = validation_proof.owner_version
             AND build_request.version_code = publishing.version_code
+            AND obj.visibility = 0
             AND platform_id = $2
             AND category_id = $3
+            AND ($4::BIGINT IS NULL OR (downloads, obj.id) < ($4, $5::BIGINT))
             
-            ORDER BY downloads DESC
-            LIMIT $4 OFFSET $5
+            ORDER BY downloads DESC, obj.id DESC
+            LIMIT $6 OFFSET $7
             "#,
             search_pattern,
             platform_id,
             category_id,
+            cursor.as_ref().map(|cursor| cursor.downloads),
+            cursor.as_ref().map(|cursor| cursor.id),
             limit,
             offset,
         )
@@ -1,9 +1,9 @@
 use crate::data::id::{ReqTypeId, TrackId};
-use crate::data::models::{BuildRequest, NewBuildRequest};
+use crate::data::models::{AssetVersion, BuildRequest, NewBuildRequest, VersionCursor};
 use crate::result::ClientResult;
 use codegen_contracts::ext::ToChecksum;
 use db_psql::client::PgClient;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 #[derive(Clone)]
 pub struct ValidationRepo {
@@ -64,7 +64,12 @@ impl ValidationRepo {
         Ok(build_requests)
     }
 
-    pub async fn insert_or_update(&self, new_req: &NewBuildRequest) -> ClientResult<()> {
+    pub async fn insert_or_update<'e, E: PgExecutor<'e>>(
+        &self,
+        executor: E,
+        new_req: &NewBuildRequest,
+        block_number: Option<i64>,
+    ) -> ClientResult<()> {
         let req_type_id: i32 = new_req.request_type_id.clone().into();
         let track_id: i32 = new_req.track_id.clone().into();
         sqlx::query_as!(
@@ -78,13 +83,28 @@ impl ValidationRepo {
                 status,
                 version_code,
                 owner_version,
-                created_at
+                created_at,
+                block_id,
+                finalized_at,
+                block_number
             )
             
-            VALUES ($1, $2, $3, $4, $5, $6, $7, COALESCE($8, CURRENT_TIMESTAMP))
+            VALUES ($1, $2, $3, $4, $5, $6, $7, COALESCE($8, CURRENT_TIMESTAMP), $9, $10, $11)
             
             ON CONFLICT (id) DO UPDATE SET
-                status = EXCLUDED.status
+                -- Result of the finalized block outlives the status the request was created with,
+                -- and a retried log of an older block doesn't replace a newer result
+                status = CASE WHEN EXCLUDED.block_id IS NULL AND build_request.block_id IS NOT NULL
+                    OR build_request.block_id > EXCLUDED.block_id
+                    THEN build_request.status
+                    ELSE EXCLUDED.status
+                END,
+                block_id = GREATEST(EXCLUDED.block_id, build_request.block_id),
+                finalized_at = CASE WHEN build_request.block_id > EXCLUDED.block_id
+                    THEN build_request.finalized_at
+                    ELSE COALESCE(EXCLUDED.finalized_at, build_request.finalized_at)
+                END,
+                block_number = COALESCE(build_request.block_number, EXCLUDED.block_number)
             "#,
             new_req.id,
             req_type_id,
@@ -93,11 +113,80 @@ impl ValidationRepo {
             new_req.status,
             new_req.version_code,
             new_req.owner_version as i64,
-            new_req.created_at
+            new_req.created_at,
+            new_req.block_id,
+            new_req.finalized_at,
+            block_number
         )
-            .execute(self.pool())
+            .execute(executor)
             .await?;
 
         return Ok(())
     }
-}
\ No newline at end of file
+
+    // Build history of a listed asset, newest request first
+    pub async fn find_versions(
+        &self,
+        asset_id: i64,
+        track_id: Option<TrackId>,
+        cursor: Option<VersionCursor>,
+        limit: i64,
+        offset: i64,
+    ) -> ClientResult<Vec<AssetVersion>> {
+        let track_id: Option<i32> = track_id.map(|track_id| track_id.into());
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                br.id,
+                br.track_id,
+                br.version_code,
+                br.status,
+                br.block_id,
+                br.created_at,
+                br.finalized_at,
+                art.version_name AS "version_name?",
+                art.checksum AS "checksum?",
+                art.size AS "size?",
+                proof.status AS "proof_status?"
+
+            FROM obj
+            INNER JOIN build_request br ON br.asset_address = obj.address
+            LEFT JOIN artifact art ON art.asset_address = br.asset_address AND art.version_code = br.version_code
+            LEFT JOIN validation_proof proof ON proof.asset_address = br.asset_address AND proof.owner_version = br.owner_version
+                AND proof.request_id IS NULL
+
+            WHERE obj.id = $1
+            AND obj.visibility = 0
+            AND ($2::INT IS NULL OR br.track_id = $2)
+            AND ($3::BIGINT IS NULL OR br.id < $3)
+            ORDER BY br.id DESC
+            LIMIT $4 OFFSET $5
+            "#,
+            asset_id,
+            track_id,
+            cursor.map(|cursor| cursor.id),
+            limit,
+            offset
+        )
+            .fetch_all(self.pool())
+            .await?;
+
+        let versions = rows.into_iter()
+            .map(|row| AssetVersion {
+                id: row.id,
+                track_id: TrackId::from(row.track_id),
+                version_code: row.version_code,
+                version_name: row.version_name,
+                checksum: row.checksum,
+                size: row.size,
+                status: row.status,
+                proof_status: row.proof_status,
+                block_id: row.block_id,
+                created_at: row.created_at,
+                finalized_at: row.finalized_at,
+            })
+            .collect();
+
+        return Ok(versions)
+    }
+}
@@ -1,6 +1,7 @@
 use alloy::primitives::Address;
 use std::env;
 use std::env::VarError;
+use std::net::IpAddr;
 use std::str::FromStr;
 
 const GF_NODE_URL: &str = "GF_NODE_URL";
@@ -13,14 +14,29 @@ const WALLET_PK: &str = "WALLET_PK";
 
 const HISTORICAL_SYNC_THRESHOLD: &str = "HISTORICAL_SYNC_THRESHOLD";
 const HISTORICAL_SYNC_BLOCK: &str = "HISTORICAL_SYNC_BLOCK";
+const SYNC_CONFIRMATIONS: &str = "SYNC_CONFIRMATIONS";
 
 const ORACLE_ADDRESS: &str = "ORACLE_ADDRESS";
 const STORE_ADDRESS: &str = "STORE_ADDRESS";
+const PUBLISHER_FACTORY_ADDRESS: &str = "PUBLISHER_FACTORY_ADDRESS";
 
 const CLIENT_HOST_URL: &str = "CLIENT_HOST_URL";
+const TRUSTED_PROXIES: &str = "TRUSTED_PROXIES";
+const DEPRECATED_API_VERSION: &str = "DEPRECATED_API_VERSION";
+const API_DEPRECATED_AT: &str = "API_DEPRECATED_AT";
+const API_SUNSET_AT: &str = "API_SUNSET_AT";
 const REDIS_URL: &str = "REDIS_URL";
 const DATABASE_URL: &str = "DATABASE_URL";
 
+const CLICKHOUSE_URL: &str = "CLICKHOUSE_URL";
+const CLICKHOUSE_USER: &str = "CLICKHOUSE_USER";
+const CLICKHOUSE_PASSWORD: &str = "CLICKHOUSE_PASSWORD";
+const CLICKHOUSE_DATABASE: &str = "CLICKHOUSE_DATABASE";
+
+const KAFKA_BROKERS: &str = "KAFKA_BROKERS";
+const KAFKA_CLIENT: &str = "KAFKA_CLIENT";
+const CHANGE_TOPIC: &str = "CHANGE_TOPIC";
+
 //////////////////////
 // DAEMON
 /////////////////////
@@ -123,10 +139,28 @@ pub fn sync_timeout_ms() -> u64 {
     return 60_000
 }
 
+pub fn sync_confirmations_env() -> Result<String, VarError> { env::var(SYNC_CONFIRMATIONS) }
+pub fn sync_confirmations() -> u64 {
+    sync_confirmations_env()
+        .unwrap_or("15".to_string())
+        .parse::<u64>()
+        .expect("invalid sync confirmations")
+}
+
+// Synced block hashes checked for a fork point before the rollback gives up on precision
+pub fn sync_reorg_depth() -> u64 {
+    return 256
+}
+
 pub fn max_logs_per_request() -> u32 {
     return 1_000
 }
 
+// Addresses in one RPC log filter
+pub fn log_addresses_per_request() -> usize {
+    return 500
+}
+
 // Addresses
 pub fn assetlink_env() -> Result<String, VarError> { env::var(ORACLE_ADDRESS) }
 pub fn assetlink() -> String {
@@ -148,17 +182,135 @@ pub fn openstore_address() -> Address {
         .expect("invalid openstore address")
 }
 
+pub fn publisher_factory_env() -> Result<String, VarError> { env::var(PUBLISHER_FACTORY_ADDRESS) }
+// Optional, deployments without the factory don't sync publisher accounts and their apps
+pub fn publisher_factory_address() -> Option<Address> {
+    publisher_factory_env()
+        .ok()
+        .map(|address| Address::from_str(address.as_str()).expect("invalid publisher factory address"))
+}
+
+// Stats
+pub fn ch_url_env() -> Result<String, VarError> { env::var(CLICKHOUSE_URL) }
+pub fn ch_url() -> String {
+    ch_url_env()
+        .expect("Can't find `CLICKHOUSE_URL` in .env")
+}
+
+pub fn ch_user_env() -> Result<String, VarError> { env::var(CLICKHOUSE_USER) }
+pub fn ch_pass_env() -> Result<String, VarError> { env::var(CLICKHOUSE_PASSWORD) }
+pub fn ch_db_env() -> Result<String, VarError> { env::var(CLICKHOUSE_DATABASE) }
+
+pub fn downloads_recount_interval_sec() -> u64 { 10 * 60 }
+// Stat events are flushed in batches, the recount window stays this far behind now
+pub fn downloads_recount_lag_sec() -> u64 { 5 * 60 }
+pub fn trending_refresh_sec() -> u64 { 30 * 60 }
+
+// Failed logs, retry delay doubles per attempt up to the max, then entries are dead
+pub fn failed_log_retry_interval_sec() -> u64 { 60 }
+pub fn failed_log_retry_batch() -> i64 { 100 }
+pub fn failed_log_retry_base_sec() -> f64 { 60.0 }
+pub fn failed_log_retry_max_sec() -> f64 { 6.0 * 60.0 * 60.0 }
+pub fn failed_log_max_attempts() -> i32 { 10 }
+
+// Change events, without `KAFKA_BROKERS` they stay in the outbox until a producer is configured
+pub fn kf_broker_env() -> Result<String, VarError> { env::var(KAFKA_BROKERS) }
+pub fn kf_client() -> String {
+    env::var(KAFKA_CLIENT)
+        .unwrap_or("daemon-client".to_string())
+}
+pub fn change_topic() -> String {
+    env::var(CHANGE_TOPIC)
+        .unwrap_or("store-changes".to_string())
+}
+pub fn change_outbox_interval_sec() -> u64 { 30 }
+pub fn change_outbox_batch() -> i64 { 500 }
+pub fn change_outbox_retention_sec() -> u64 { 7 * 24 * 60 * 60 }
+
 //////////////////////
 // API
 /////////////////////
 // Client
 pub fn default_page_size() -> i64 { 20 }
+pub fn chart_min_rating_count() -> i64 { 5 }
+
+// Versions
+pub fn min_api_version() -> u32 { 1 }
+pub fn max_api_version() -> u32 { 2 }
+// Versions up to this one are deprecated and get `Deprecation`/`Sunset` headers, none by default
+pub fn deprecated_api_version_env() -> Result<String, VarError> { env::var(DEPRECATED_API_VERSION) }
+pub fn deprecated_api_version() -> u32 {
+    deprecated_api_version_env()
+        .unwrap_or("0".to_string())
+        .parse::<u32>()
+        .expect("invalid deprecated api version")
+}
+
+// Unix timestamps, each header is sent only when its date is set
+pub fn api_deprecated_at_env() -> Result<String, VarError> { env::var(API_DEPRECATED_AT) }
+pub fn api_deprecated_at() -> Option<i64> {
+    api_deprecated_at_env()
+        .ok()
+        .map(|value| value.parse::<i64>().expect("invalid api deprecation timestamp"))
+}
+
+pub fn api_sunset_at_env() -> Result<String, VarError> { env::var(API_SUNSET_AT) }
+pub fn api_sunset_at() -> Option<i64> {
+    api_sunset_at_env()
+        .ok()
+        .map(|value| value.parse::<i64>().expect("invalid api sunset timestamp"))
+}
+
+// Rate limits, requests per window of `rate_limit_window_sec`
+pub fn rate_limit_window_sec() -> u64 { 60 }
+pub fn search_rate_limit() -> u64 { 60 }
+pub fn review_rate_limit() -> u64 { 10 }
+pub fn report_rate_limit() -> u64 { 5 }
+pub fn admin_rate_limit() -> u64 { 120 }
+pub fn update_check_rate_limit() -> u64 { 30 }
+pub fn download_rate_limit() -> u64 { 30 }
+
+// Feed, built sections are cached for this long and rebuilt on schedule
+pub fn feed_refresh_sec() -> u64 { 15 * 60 }
+
+// Update check, installed apps per request
+pub fn update_check_max_apps() -> usize { 200 }
+
+// Downloads, resolved SP urls are reused for this long
+pub fn download_url_ttl_sec() -> u64 { 10 * 60 }
+
+// Reports
+pub fn report_limit_per_hour() -> i64 { 5 }
+pub fn report_flag_threshold() -> i64 { 3 }
 pub fn client_host_url_env() -> Result<String, VarError> { env::var(CLIENT_HOST_URL) }
 pub fn client_host_url() -> String {
     client_host_url_env()
         .unwrap_or("127.0.0.1:8081".to_string())
 }
 
+// Proxies whose forwarding headers are trusted, comma separated ips
+pub fn trusted_proxies() -> Vec<IpAddr> {
+    return env::var(TRUSTED_PROXIES)
+        .unwrap_or("127.0.0.1,::1".to_string())
+        .split(',')
+        .filter_map(|ip| IpAddr::from_str(ip.trim()).ok())
+        .collect();
+}
+
+// Signatures
+pub fn admin_signature_ttl_sec() -> u64 {
+    return 5 * 60
+}
+
+// Bodies of admin requests are hashed into the signed message
+pub fn admin_body_limit() -> usize {
+    return 1024 * 1024
+}
+
+pub fn review_signature_ttl_sec() -> u64 {
+    return 5 * 60
+}
+
 // Redis
 pub fn redis_url_env() -> Result<String, VarError> {
     env::var(REDIS_URL)
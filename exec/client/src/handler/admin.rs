@@ -1,42 +1,405 @@
-use crate::data::models::{Category, NewCategory, Asset};
-use crate::data::id::ObjTypeId;
+use crate::data::change_event;
+use crate::data::id::{AdminRole, FailedLogStatus, FeedLayout, FeedSource, ObjTypeId, PlatformId, ReportStatus, Visibility};
+use crate::data::models::{CacheEvent, NewCategory, NewFeedSection};
+use crate::env::default_page_size;
+use crate::net::admin_auth::AdminAuth;
+use crate::net::extract::{Json, Path, Query};
 use crate::result::{ClientError, ClientResult};
 use crate::state::ClientState;
-use axum::extract::Query;
+use crate::util::cursor::check_page_params;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use axum::{extract::State, Json};
-use net_result::{response_null, response_nullable};
-use serde::{Deserialize, Serialize};
-use tracing::info;
+use axum::extract::State;
+use chrono::Utc;
+use net_result::{response_data, response_nullable};
+use serde::Deserialize;
+use tracing::{info, warn};
 
-#[derive(Debug, Deserialize)]
-pub struct CategoryParams {
+// For PUT /admin/categories
+#[derive(Deserialize, Debug)]
+pub struct SetCategoriesRequest {
+    pub categories: Vec<NewCategory>,
+}
+
+// For PUT /admin/asset/{asset_id}/visibility
+#[derive(Deserialize, Debug)]
+pub struct SetVisibilityRequest {
+    pub visibility: Visibility,
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FeedSectionParams {
     #[serde(rename = "type")]
     pub type_id: ObjTypeId,
 }
 
-// For POST /admin/set_categories
+// For PUT /admin/feed
 #[derive(Deserialize, Debug)]
-pub struct SetCategoriesRequest {
-    pub categories: Vec<NewCategory>,
+pub struct SetFeedRequest {
+    pub sections: Vec<NewFeedSection>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ChartOrderParams {
+    pub platform: PlatformId,
+    pub category_id: Option<i32>,
+}
+
+// For PUT /admin/chart/order
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetChartOrderRequest {
+    pub asset_ids: Vec<i64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReportListParams {
+    pub status: ReportStatus,
+    #[serde(default = "default_page_size")]
+    pub size: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+// For PUT /admin/reports/{report_id}
+#[derive(Deserialize, Debug)]
+pub struct ResolveReportRequest {
+    pub status: ReportStatus,
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FailedLogListParams {
+    pub status: FailedLogStatus,
+    #[serde(default = "default_page_size")]
+    pub size: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AuditParams {
+    #[serde(default = "default_page_size")]
+    pub size: i64,
+    #[serde(default)]
+    pub offset: i64,
 }
 
 pub async fn set_categories(
     State(state): State<ClientState>,
-    Query(params): Query<CategoryParams>,
+    auth: AdminAuth,
     Json(payload): Json<SetCategoriesRequest>,
 ) -> ClientResult<impl IntoResponse> {
-    info!(
-        "Admin: Setting categories for type {:?}: {:?}",
-        params.type_id, // Log the param even if unused by current logic
-        payload.categories
-    );
+    auth.require(AdminRole::Curator)?;
+    info!("Admin {}: Setting categories: {:?}", auth.actor, payload.categories);
+
+    if payload.categories.iter().any(|category| category.name.trim().is_empty()) {
+        return Err(ClientError::invalid("categories", "name can't be empty"));
+    }
 
-    // let mut created_categories = Vec::new();
-    // for new_cat in payload.categories {
-    //     created_categories.push(new_cat);
-    // }
+    let ids = payload.categories.iter()
+        .map(|category| category.id.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let mut tx = state.admin_repo.start().await?;
+    state.category_repo.put_all(&mut tx, &payload.categories).await?;
+    state.admin_repo.insert_audit(&mut tx, &auth.audit("set_categories", ids, None)).await?;
+    tx.commit().await?;
+
+    publish(&state, CacheEvent::CategoryChanged).await;
 
     Ok((StatusCode::CREATED, response_nullable()))
-}
\ No newline at end of file
+}
+
+pub async fn delete_category(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Path(category_id): Path<i32>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Curator)?;
+
+    let mut tx = state.admin_repo.start().await?;
+    let deleted = state.category_repo.delete(&mut tx, category_id).await?;
+    if deleted == 0 {
+        return Err(ClientError::NotFound);
+    }
+
+    state.admin_repo.insert_audit(&mut tx, &auth.audit("delete_category", category_id.to_string(), None)).await?;
+    tx.commit().await?;
+
+    publish(&state, CacheEvent::CategoryChanged).await;
+
+    Ok(response_nullable())
+}
+
+pub async fn set_visibility(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Path(asset_id): Path<i64>,
+    Json(payload): Json<SetVisibilityRequest>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Moderator)?;
+
+    if payload.reason.as_ref().is_some_and(|reason| reason.len() > 255) {
+        return Err(ClientError::invalid("reason", "max 255 characters"));
+    }
+
+    let content = serde_json::to_string(&serde_json::json!({
+        "visibility": payload.visibility,
+        "reason": payload.reason,
+    }))?;
+
+    let mut tx = state.admin_repo.start().await?;
+    let address = state.object_repo
+        .set_visibility(&mut tx, asset_id, payload.visibility, payload.reason.clone())
+        .await?
+        .ok_or(ClientError::NotFound)?;
+
+    let event = change_event::visibility_changed(asset_id, &address, payload.visibility, payload.reason.as_deref(), Utc::now());
+    state.outbox_repo.insert_event(&mut *tx, &event).await?;
+    state.admin_repo.insert_audit(&mut tx, &auth.audit("set_visibility", asset_id.to_string(), Some(content))).await?;
+    tx.commit().await?;
+
+    publish(&state, CacheEvent::AssetChanged { addresses: vec![address] }).await;
+
+    Ok(response_nullable())
+}
+
+pub async fn get_feed_sections(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Query(params): Query<FeedSectionParams>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Curator)?;
+
+    let result = state.feed_repo
+        .find_sections(&params.type_id)
+        .await?;
+
+    Ok(response_data(result))
+}
+
+// Replaces feed definitions of the type, the feed itself is built by `FeedBuilder` on request
+pub async fn set_feed(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Query(params): Query<FeedSectionParams>,
+    Json(payload): Json<SetFeedRequest>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Curator)?;
+    check_feed_sections(&payload.sections)?;
+
+    let type_id: i32 = params.type_id.clone().into();
+    let content = serde_json::to_string(&payload.sections)?;
+
+    let mut tx = state.admin_repo.start().await?;
+    state.feed_repo.replace_sections(&mut tx, &params.type_id, &payload.sections).await?;
+    state.admin_repo.insert_audit(&mut tx, &auth.audit("set_feed", type_id.to_string(), Some(content))).await?;
+    tx.commit().await?;
+
+    publish(&state, CacheEvent::FeedChanged { type_id: params.type_id }).await;
+
+    Ok(response_nullable())
+}
+
+fn check_feed_sections(sections: &[NewFeedSection]) -> ClientResult<()> {
+    if sections.len() > 30 {
+        return Err(ClientError::invalid("sections", "max 30 sections"));
+    }
+
+    for (i, section) in sections.iter().enumerate() {
+        let field = |name: &str| format!("sections[{}].{}", i, name);
+
+        if section.layout == FeedLayout::Unspecified {
+            return Err(ClientError::invalid(field("layout"), "is unknown"));
+        }
+
+        if section.layout.has_assets() && section.source == FeedSource::None {
+            return Err(ClientError::invalid(field("source"), "is required for asset layouts"));
+        }
+
+        if section.source == FeedSource::Editorial && !(1..=50).contains(&section.asset_ids.len()) {
+            return Err(ClientError::invalid(field("assetIds"), "from 1 to 50 assets"));
+        }
+
+        let needs_categories = section.source == FeedSource::TopCategory || section.layout == FeedLayout::Categories;
+        if needs_categories && section.category_ids.is_empty() {
+            return Err(ClientError::invalid(field("categoryIds"), "can't be empty"));
+        }
+
+        if !(1..=50).contains(&section.size) {
+            return Err(ClientError::invalid(field("size"), "from 1 to 50"));
+        }
+
+        if section.covers.len() > 10 {
+            return Err(ClientError::invalid(field("covers"), "max 10 covers"));
+        }
+
+        if section.title_key.as_ref().is_some_and(|key| key.is_empty() || key.len() > 64) {
+            return Err(ClientError::invalid(field("titleKey"), "from 1 to 64 characters"));
+        }
+    }
+
+    return Ok(());
+}
+
+pub async fn get_chart_order(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Query(params): Query<ChartOrderParams>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Curator)?;
+
+    let result = state.admin_repo
+        .get_chart_order(params.platform.into(), params.category_id.unwrap_or(0))
+        .await?;
+
+    Ok(response_data(result))
+}
+
+pub async fn set_chart_order(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Query(params): Query<ChartOrderParams>,
+    Json(payload): Json<SetChartOrderRequest>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Curator)?;
+
+    if payload.asset_ids.len() > 100 {
+        return Err(ClientError::invalid("assetIds", "max 100 assets"));
+    }
+
+    let platform_id: i32 = params.platform.into();
+    let category_id = params.category_id.unwrap_or(0);
+    let target = format!("{}:{}", platform_id, category_id);
+    let content = serde_json::to_string(&payload.asset_ids)?;
+
+    let mut tx = state.admin_repo.start().await?;
+    state.admin_repo.set_chart_order(&mut tx, platform_id, category_id, &payload.asset_ids).await?;
+    state.admin_repo.insert_audit(&mut tx, &auth.audit("set_chart_order", target, Some(content))).await?;
+    tx.commit().await?;
+
+    publish(&state, CacheEvent::ChartChanged).await;
+
+    Ok(response_nullable())
+}
+
+pub async fn get_reports(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Query(params): Query<ReportListParams>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Moderator)?;
+
+    check_page_params(params.size, params.offset, None)?;
+
+    let result = state.report_repo
+        .find_by_status(params.status, params.size, params.offset)
+        .await?;
+
+    Ok(response_data(result))
+}
+
+pub async fn resolve_report(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Path(report_id): Path<i64>,
+    Json(payload): Json<ResolveReportRequest>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Moderator)?;
+
+    let content = serde_json::to_string(&serde_json::json!({
+        "status": payload.status,
+        "note": payload.note,
+    }))?;
+
+    let mut tx = state.admin_repo.start().await?;
+    let report = state.report_repo
+        .set_status(&mut tx, report_id, payload.status, payload.note)
+        .await?
+        .ok_or(ClientError::NotFound)?;
+
+    state.admin_repo.insert_audit(&mut tx, &auth.audit("resolve_report", report_id.to_string(), Some(content))).await?;
+    tx.commit().await?;
+
+    Ok(response_data(report))
+}
+
+pub async fn get_flagged_assets(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Query(params): Query<AuditParams>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Moderator)?;
+
+    check_page_params(params.size, params.offset, None)?;
+
+    let result = state.report_repo
+        .find_flagged(params.size, params.offset)
+        .await?;
+
+    Ok(response_data(result))
+}
+
+pub async fn get_audit_log(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Query(params): Query<AuditParams>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Admin)?;
+
+    check_page_params(params.size, params.offset, None)?;
+
+    let result = state.admin_repo
+        .find_audit(params.size, params.offset)
+        .await?;
+
+    Ok(response_data(result))
+}
+
+pub async fn get_failed_logs(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Query(params): Query<FailedLogListParams>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Admin)?;
+
+    check_page_params(params.size, params.offset, None)?;
+
+    let result = state.error_repo
+        .find_by_status(params.status, params.size, params.offset)
+        .await?;
+
+    Ok(response_data(result))
+}
+
+// Daemon picks the entry up on its next retry run with a fresh attempt count
+pub async fn redrive_failed_log(
+    State(state): State<ClientState>,
+    auth: AdminAuth,
+    Path(failed_log_id): Path<i64>,
+) -> ClientResult<impl IntoResponse> {
+    auth.require(AdminRole::Admin)?;
+
+    let mut tx = state.admin_repo.start().await?;
+    let Some(entry) = state.error_repo.redrive(&mut tx, failed_log_id).await? else {
+        return match state.error_repo.find_by_id(failed_log_id).await? {
+            Some(_) => Err(ClientError::Conflict("Failed log is already resolved".to_string())),
+            None => Err(ClientError::NotFound),
+        };
+    };
+
+    state.admin_repo.insert_audit(&mut tx, &auth.audit("redrive_failed_log", failed_log_id.to_string(), None)).await?;
+    tx.commit().await?;
+
+    Ok(response_data(entry))
+}
+
+// Every api instance purges its etags on the event, see `net::invalidation`
+async fn publish(state: &ClientState, event: CacheEvent) {
+    if let Err(e) = state.cache_repo.publish_event(&event).await {
+        warn!("Can't publish cache event {:?}: {}", event, e);
+    }
+}
@@ -1,6 +1,8 @@
-use crate::result::{ClientError, ClientResult};
+use crate::handler::object::{unavailable_error, TrackParams};
+use crate::net::extract::{Path, Query};
+use crate::result::ClientResult;
 use crate::state::ClientState;
-use axum::extract::{Path, State};
+use axum::extract::State;
 use axum::response::IntoResponse;
 use net_result::response_data;
 
@@ -14,7 +16,33 @@ pub async fn get_artifact(
         .await?;
 
     let Some(artifact) = result else {
-        return Err(ClientError::NotFound);
+        let visibility = state.object_repo
+            .find_visibility_by_id(asset_id)
+            .await?;
+
+        return Err(unavailable_error(visibility));
+    };
+
+    Ok(response_data(artifact))
+}
+
+pub async fn get_latest_artifact(
+    State(state): State<ClientState>,
+    Path(asset_id): Path<i64>,
+    Query(params): Query<TrackParams>,
+) -> ClientResult<impl IntoResponse> {
+    let track_id = params.track_id()?;
+    let result = state
+        .artifact_repo
+        .find_latest_by_track(asset_id, &track_id)
+        .await?;
+
+    let Some(artifact) = result else {
+        let visibility = state.object_repo
+            .find_visibility_by_id(asset_id)
+            .await?;
+
+        return Err(unavailable_error(visibility));
     };
 
     Ok(response_data(artifact))
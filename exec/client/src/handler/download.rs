@@ -0,0 +1,362 @@
+use crate::data::id::TrackId;
+use crate::data::models::{Artifact, TrackArtifact};
+use crate::env;
+use crate::handler::object::{unavailable_error, TrackParams};
+use crate::net::extract::{Path, Query};
+use crate::net::headers::{ARTIFACT_SIZE, CHECKSUM_BLAKE3};
+use crate::result::{ClientError, ClientResult};
+use crate::state::ClientState;
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::header::{ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, LOCATION, RANGE};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use client_gf::client::{GfError, GreenfieldClient};
+use futures_util::stream;
+use serde::Deserialize;
+use tracing::warn;
+use url::Url;
+
+const APK_CONTENT_TYPE: &str = "application/vnd.android.package-archive";
+
+// Redirect sends the client straight to the SP, proxy is for clients which can't follow it
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DownloadMode {
+    #[default]
+    Redirect,
+    Proxy,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DownloadParams {
+    pub track: Option<TrackId>,
+    #[serde(default)]
+    pub mode: DownloadMode,
+}
+
+impl DownloadParams {
+
+    pub fn track_id(&self) -> ClientResult<TrackId> {
+        return TrackParams { track: self.track.clone() }.track_id();
+    }
+}
+
+// Inclusive byte positions like in `Content-Range`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    Full,
+    Partial(u64, u64),
+    Unsatisfiable,
+}
+
+pub async fn download_artifact(
+    State(state): State<ClientState>,
+    Path(asset_id): Path<i64>,
+    Query(params): Query<DownloadParams>,
+    headers: HeaderMap,
+) -> ClientResult<Response> {
+    let track_id = params.track_id()?;
+    let result = state
+        .artifact_repo
+        .find_latest_by_track(asset_id, &track_id)
+        .await?;
+
+    let Some(TrackArtifact { artifact, .. }) = result else {
+        let visibility = state.object_repo
+            .find_visibility_by_id(asset_id)
+            .await?;
+
+        return Err(unavailable_error(visibility));
+    };
+
+    let url = resolve_view_url(&state, &artifact.ref_id)
+        .await?;
+
+    let response = match params.mode {
+        DownloadMode::Redirect => redirect_response(&url, &artifact)?,
+        DownloadMode::Proxy => {
+            let header = headers.get(RANGE)
+                .and_then(|value| value.to_str().ok());
+
+            let range = parse_range(header, artifact_size(&artifact));
+            proxy_response(&state.greenfield, url, range, &artifact)
+                .await?
+        }
+    };
+
+    Ok(response)
+}
+
+// The primary SP of a bucket rarely changes, so the resolved url is reused for a while
+async fn resolve_view_url(state: &ClientState, ref_id: &str) -> ClientResult<Url> {
+    let key = format!("cache:download:url:{ref_id}");
+    let cached = state.cache_repo.get_content(key.as_str())
+        .await
+        .and_then(|value| Url::parse(value.as_str()).ok());
+
+    if let Some(url) = cached {
+        return Ok(url);
+    }
+
+    let url = state.greenfield.get_object_view_url(ref_id)
+        .await?;
+
+    let result = state.cache_repo
+        .set_content_with_ttl(key.as_str(), url.as_str(), env::download_url_ttl_sec())
+        .await;
+
+    if let Err(e) = result {
+        warn!("Failed to cache download url for {}: {}", ref_id, e);
+    }
+
+    return Ok(url);
+}
+
+fn redirect_response(url: &Url, artifact: &Artifact) -> ClientResult<Response> {
+    let mut response = StatusCode::FOUND.into_response();
+
+    let headers = response.headers_mut();
+    headers.insert(LOCATION, header_value(url.to_string())?);
+    insert_artifact_headers(headers, artifact)?;
+
+    return Ok(response);
+}
+
+pub async fn proxy_response(
+    greenfield: &GreenfieldClient,
+    url: Url,
+    range: ByteRange,
+    artifact: &Artifact,
+) -> ClientResult<Response> {
+    let size = artifact_size(artifact);
+    let bounds = match range {
+        ByteRange::Full => None,
+        ByteRange::Partial(start, end) => Some((start, end)),
+        ByteRange::Unsatisfiable => return Err(ClientError::RangeNotSatisfiable(size)),
+    };
+
+    // Stored object can be shorter than the synced size, the client has to restart the download
+    let upstream = match greenfield.get_object_by_url(url, bounds).await {
+        Ok(upstream) => upstream,
+        Err(GfError::RpcError(416, _)) => return Err(ClientError::RangeNotSatisfiable(size)),
+        Err(e) => return Err(e.into()),
+    };
+
+    let status = StatusCode::from_u16(upstream.status().as_u16())
+        .unwrap_or(StatusCode::OK);
+
+    let mut forwarded = HeaderMap::new();
+    for name in [CONTENT_LENGTH, CONTENT_RANGE] {
+        let value = upstream.headers()
+            .get(name.as_str())
+            .and_then(|value| HeaderValue::from_bytes(value.as_bytes()).ok());
+
+        if let Some(value) = value {
+            forwarded.insert(name, value);
+        }
+    }
+
+    // Error state is dropped after it's yielded, otherwise the stream would poll a broken body forever
+    let body = stream::unfold(Some(upstream), |upstream| async move {
+        let mut upstream = upstream?;
+        return match upstream.chunk().await {
+            Ok(Some(chunk)) => Some((Ok(chunk), Some(upstream))),
+            Ok(None) => None,
+            Err(e) => Some((Err(e), None)),
+        };
+    });
+
+    let mut response = Response::new(Body::from_stream(body));
+    *response.status_mut() = status;
+
+    let headers = response.headers_mut();
+    headers.extend(forwarded);
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static(APK_CONTENT_TYPE));
+    headers.insert(ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    insert_artifact_headers(headers, artifact)?;
+
+    return Ok(response);
+}
+
+// Only a single range is supported, anything else falls back to the full body as RFC 9110 allows
+pub fn parse_range(header: Option<&str>, size: u64) -> ByteRange {
+    let Some(spec) = header.and_then(|value| value.trim().strip_prefix("bytes=")) else {
+        return ByteRange::Full;
+    };
+
+    if size == 0 || spec.contains(',') {
+        return ByteRange::Full;
+    }
+
+    let Some((start, end)) = spec.split_once('-') else {
+        return ByteRange::Full;
+    };
+
+    let (start, end) = (start.trim(), end.trim());
+    if start.is_empty() {
+        let Ok(suffix) = end.parse::<u64>() else {
+            return ByteRange::Full;
+        };
+
+        if suffix == 0 {
+            return ByteRange::Unsatisfiable;
+        }
+
+        return ByteRange::Partial(size.saturating_sub(suffix), size - 1);
+    }
+
+    let Ok(start) = start.parse::<u64>() else {
+        return ByteRange::Full;
+    };
+
+    if start >= size {
+        return ByteRange::Unsatisfiable;
+    }
+
+    if end.is_empty() {
+        return ByteRange::Partial(start, size - 1);
+    }
+
+    let Ok(end) = end.parse::<u64>() else {
+        return ByteRange::Full;
+    };
+
+    if end < start {
+        return ByteRange::Full;
+    }
+
+    return ByteRange::Partial(start, end.min(size - 1));
+}
+
+fn insert_artifact_headers(headers: &mut HeaderMap, artifact: &Artifact) -> ClientResult<()> {
+    headers.insert(CHECKSUM_BLAKE3, header_value(artifact.checksum.clone())?);
+    headers.insert(ARTIFACT_SIZE, HeaderValue::from(artifact.size));
+
+    return Ok(());
+}
+
+fn artifact_size(artifact: &Artifact) -> u64 {
+    return u64::try_from(artifact.size)
+        .unwrap_or(0);
+}
+
+fn header_value(value: String) -> ClientResult<HeaderValue> {
+    return HeaderValue::try_from(value)
+        .map_err(|e| ClientError::HttpError(e.into()));
+}
+
+#[test]
+fn check_parse_range() {
+    assert_eq!(parse_range(None, 100), ByteRange::Full);
+    assert_eq!(parse_range(Some("bytes=0-9"), 100), ByteRange::Partial(0, 9));
+    assert_eq!(parse_range(Some("bytes=90-"), 100), ByteRange::Partial(90, 99));
+    assert_eq!(parse_range(Some("bytes=-10"), 100), ByteRange::Partial(90, 99));
+    assert_eq!(parse_range(Some("bytes=-500"), 100), ByteRange::Partial(0, 99));
+    assert_eq!(parse_range(Some("bytes=50-500"), 100), ByteRange::Partial(50, 99));
+    assert_eq!(parse_range(Some("bytes=100-"), 100), ByteRange::Unsatisfiable);
+    assert_eq!(parse_range(Some("bytes=-0"), 100), ByteRange::Unsatisfiable);
+    assert_eq!(parse_range(Some("bytes=0-1,5-6"), 100), ByteRange::Full);
+    assert_eq!(parse_range(Some("bytes=9-1"), 100), ByteRange::Full);
+    assert_eq!(parse_range(Some("items=0-1"), 100), ByteRange::Full);
+}
+
+#[tokio::test]
+async fn check_proxy_from_primary_sp() {
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use net_client::http::HttpProviderFactory;
+    use serde_json::json;
+
+    const CONTENT: &[u8] = b"0123456789";
+
+    async fn view(headers: HeaderMap) -> Response {
+        let header = headers.get(RANGE)
+            .and_then(|value| value.to_str().ok());
+
+        return match parse_range(header, CONTENT.len() as u64) {
+            ByteRange::Partial(start, end) => (
+                StatusCode::PARTIAL_CONTENT,
+                [(CONTENT_RANGE, format!("bytes {start}-{end}/{}", CONTENT.len()))],
+                CONTENT[start as usize..=end as usize].to_vec(),
+            ).into_response(),
+            ByteRange::Unsatisfiable => (
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                [(CONTENT_RANGE, format!("bytes */{}", CONTENT.len()))],
+            ).into_response(),
+            ByteRange::Full => CONTENT.to_vec().into_response(),
+        };
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let node_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let sp_url = node_url.clone();
+    let node = Router::new()
+        .route("/greenfield/storage/head_object_by_id/{id}", get(|| async {
+            Json(json!({ "object_info": {
+                "id": "7", "owner": "0x1", "creator": "0x1", "bucket_name": "store", "object_name": "app.apk",
+                "payload_size": "10", "visibility": "VISIBILITY_TYPE_PUBLIC_READ"
+            }}))
+        }))
+        .route("/greenfield/storage/head_bucket/{bucket}", get(|| async {
+            Json(json!({
+                "bucket_info": {
+                    "id": "1", "owner": "0x1", "source_type": "SOURCE_TYPE_ORIGIN", "charged_read_quota": "0",
+                    "bucket_status": "BUCKET_STATUS_CREATED", "global_virtual_group_family_id": 3
+                },
+                "extra_info": { "is_rate_limited": false, "flow_rate_limit": "0", "current_flow_rate": "0" }
+            }))
+        }))
+        .route("/greenfield/storage_providers", get(move || async move {
+            Json(json!({ "sps": [
+                { "id": 1, "operator_address": "0x1", "endpoint": "https://unused.sp", "status": "STATUS_IN_SERVICE" },
+                { "id": 2, "operator_address": "0x2", "endpoint": sp_url, "status": "STATUS_IN_SERVICE" }
+            ]}))
+        }))
+        .route("/greenfield/virtualgroup/global_virtual_group_family", get(|| async {
+            Json(json!({ "global_virtual_group_family": {
+                "id": 3, "primary_sp_id": 2, "global_virtual_group_ids": [1]
+            }}))
+        }))
+        .route("/view/{bucket}/{object}", get(view));
+
+    tokio::spawn(async move { axum::serve(listener, node).await.unwrap() });
+
+    let greenfield = GreenfieldClient::new(HttpProviderFactory::http_client().unwrap(), node_url.clone(), None);
+    let url = greenfield.get_object_view_url("7").await.unwrap();
+    assert_eq!(url.as_str(), format!("{node_url}/view/store/app.apk"));
+
+    let artifact = Artifact {
+        id: 1,
+        ref_id: "7".to_string(),
+        asset_address: "0x1".to_string(),
+        protocol_id: 0,
+        size: CONTENT.len() as i64,
+        version_name: None,
+        version_code: 1,
+        checksum: "abc".to_string(),
+    };
+
+    let range = parse_range(Some("bytes=2-5"), artifact_size(&artifact));
+    let response = proxy_response(&greenfield, url.clone(), range, &artifact).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()[CONTENT_RANGE], "bytes 2-5/10");
+    assert_eq!(response.headers()[CHECKSUM_BLAKE3], "abc");
+    assert_eq!(response.headers()[ARTIFACT_SIZE], "10");
+
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(bytes.as_ref(), b"2345");
+
+    let response = proxy_response(&greenfield, url.clone(), ByteRange::Full, &artifact).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(bytes.as_ref(), CONTENT);
+
+    // SP has less than the synced size
+    let artifact = Artifact { size: 20, ..artifact };
+    let range = parse_range(Some("bytes=15-"), artifact_size(&artifact));
+    let response = proxy_response(&greenfield, url, range, &artifact).await.unwrap_err().into_response();
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(response.headers()[CONTENT_RANGE], "bytes */20");
+}
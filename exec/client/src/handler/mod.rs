@@ -6,4 +6,7 @@ pub mod search;
 pub mod review;
 pub mod report;
 pub mod artifact;
+pub mod download;
+pub mod publisher;
+pub mod update;
 pub mod util;
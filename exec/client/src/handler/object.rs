@@ -1,43 +1,94 @@
-use crate::data::dto::AndroidPublishingResponse;
+use crate::data::dto::{AndroidPublishingResponse, AssetDetails, AssetDto, RichAssetDto};
+use crate::data::id::{TrackId, Visibility};
+use crate::data::models::{AssetVisibility, OwnershipCursor, VersionCursor};
+use crate::env::default_page_size;
+use crate::net::extract::{Path, Query};
 use crate::net::headers::{ApiNamedVersion, ServiceHeaders};
 use crate::result::{ClientError, ClientResult};
 use crate::state::ClientState;
+use crate::util::cursor::{check_page_params, decode_cursor, split_page};
 use alloy::transports::http::reqwest::header::HeaderMap;
-use axum::extract::{Path, State};
+use axum::extract::State;
 use axum::response::IntoResponse;
 use codegen_contracts::ext::ToChecksum;
-use net_result::response_data;
+use net_result::{response_data, response_page};
+use serde::Deserialize;
+
+// Beta testers pass `?track=beta` and still get release builds where no beta one is newer
+#[derive(Deserialize, Debug)]
+pub struct TrackParams {
+    pub track: Option<TrackId>,
+}
+
+impl TrackParams {
+
+    pub fn track_id(&self) -> ClientResult<TrackId> {
+        return match self.track {
+            None => Ok(TrackId::Release),
+            Some(TrackId::Unspecified) => Err(ClientError::invalid("track", "is unknown")),
+            Some(ref track) => Ok(track.clone()),
+        };
+    }
+}
 
 pub async fn get_object_by_id(
     State(state): State<ClientState>,
     Path(asset_id): Path<i64>,
+    Query(params): Query<TrackParams>,
+    headers: HeaderMap,
 ) -> ClientResult<impl IntoResponse> {
+    let version = headers.api_version()?;
+    let track_id = params.track_id()?;
     let object = state
         .object_repo
-        .find_by_id(asset_id)
+        .find_by_id(asset_id, &track_id)
         .await?;
 
     let Some(obj) = object else {
-        return Err(ClientError::NotFound);
+        let visibility = state.object_repo
+            .find_visibility_by_id(asset_id)
+            .await?;
+
+        return Err(unavailable_error(visibility));
     };
 
-    Ok(response_data(obj))
+    let rating_summary = state.review_repo
+        .find_summary(obj.id)
+        .await?
+        .ok_or(ClientError::NotFound)?;
+
+    let asset = AssetDto::versioned(obj, version);
+    Ok(response_data(AssetDetails { asset, rating_summary }))
 }
 
 pub async fn get_object_by_address(
     State(state): State<ClientState>,
     Path(address): Path<String>,
+    Query(params): Query<TrackParams>,
+    headers: HeaderMap,
 ) -> ClientResult<impl IntoResponse> {
+    let version = headers.api_version()?;
+    let track_id = params.track_id()?;
     let addr = address.checksum();
     let object = state.object_repo
-        .find_by_address(addr.as_str())
+        .find_by_address(addr.as_str(), &track_id)
         .await?;
 
     let Some(obj) = object else {
-        return Err(ClientError::NotFound);
+        let visibility = state.object_repo
+            .find_visibility_by_address(addr.as_str())
+            .await?;
+
+        return Err(unavailable_error(visibility));
     };
 
-    Ok(response_data(obj))
+    let rating_summary = state.review_repo
+        .find_summary(obj.id)
+        .await?
+        .ok_or(ClientError::NotFound)?;
+
+    let asset = RichAssetDto::versioned(obj, version);
+    Ok(response_data(AssetDetails { asset, rating_summary }))
 }
 
 pub async fn get_object_status_by_address(
@@ -69,4 +120,98 @@ pub async fn get_object_status_by_address(
     };
 
     Ok(response_data(response))
-}
\ No newline at end of file
+}
+
+#[derive(Deserialize, Debug)]
+pub struct VersionListParams {
+    #[serde(default = "default_page_size")]
+    pub size: i64,
+    #[serde(default)]
+    pub offset: i64,
+    pub cursor: Option<String>,
+    pub track: Option<TrackId>,
+}
+
+pub async fn get_asset_versions(
+    State(state): State<ClientState>,
+    Path(asset_id): Path<i64>,
+    Query(params): Query<VersionListParams>,
+) -> ClientResult<impl IntoResponse> {
+    check_page_params(params.size, params.offset, params.cursor.as_deref())?;
+    if matches!(params.track, Some(TrackId::Unspecified)) {
+        return Err(ClientError::invalid("track", "is unknown"));
+    }
+    let cursor = decode_cursor::<VersionCursor>(params.cursor.as_deref())?;
+
+    let visibility = state.object_repo
+        .find_visibility_by_id(asset_id)
+        .await?;
+
+    if !visibility.as_ref().is_some_and(|it| Visibility::from(it.visibility) == Visibility::Visible) {
+        return Err(unavailable_error(visibility));
+    }
+
+    let versions = state.validation_repo
+        .find_versions(asset_id, params.track, cursor, params.size + 1, params.offset)
+        .await?;
+
+    let (versions, next_cursor) = split_page(versions, params.size, |version| VersionCursor {
+        id: version.id,
+    })?;
+
+    Ok(response_page(versions, next_cursor))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OwnerListParams {
+    #[serde(default = "default_page_size")]
+    pub size: i64,
+    #[serde(default)]
+    pub offset: i64,
+    pub cursor: Option<String>,
+}
+
+// Owner changes show when an app changed hands, newest first
+pub async fn get_asset_owners(
+    State(state): State<ClientState>,
+    Path(asset_id): Path<i64>,
+    Query(params): Query<OwnerListParams>,
+) -> ClientResult<impl IntoResponse> {
+    check_page_params(params.size, params.offset, params.cursor.as_deref())?;
+    let cursor = decode_cursor::<OwnershipCursor>(params.cursor.as_deref())?;
+
+    let visibility = state.object_repo
+        .find_visibility_by_id(asset_id)
+        .await?;
+
+    if !visibility.as_ref().is_some_and(|it| Visibility::from(it.visibility) == Visibility::Visible) {
+        return Err(unavailable_error(visibility));
+    }
+
+    let owners = state.ownership_repo
+        .find_history(asset_id, cursor, params.size + 1, params.offset)
+        .await?;
+
+    let (owners, next_cursor) = split_page(owners, params.size, |change| OwnershipCursor {
+        block_number: change.block_number,
+        log_index: change.log_index,
+    })?;
+
+    Ok(response_page(owners, next_cursor))
+}
+
+// Blocked assets are reported explicitly, hidden and missing ones look the same
+pub fn unavailable_error(visibility: Option<AssetVisibility>) -> ClientError {
+    let Some(visibility) = visibility else {
+        return ClientError::NotFound;
+    };
+
+    if Visibility::from(visibility.visibility) != Visibility::Blocked {
+        return ClientError::NotFound;
+    }
+
+    let reason = visibility.visibility_reason
+        .unwrap_or("Asset is blocked".to_string());
+
+    return ClientError::Blocked(reason);
+}
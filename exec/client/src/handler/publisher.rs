@@ -0,0 +1,30 @@
+use crate::data::dto::PublisherDto;
+use crate::net::extract::Path;
+use crate::net::headers::ServiceHeaders;
+use crate::result::{ClientError, ClientResult};
+use crate::state::ClientState;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::response::IntoResponse;
+use codegen_contracts::ext::ToChecksum;
+use net_result::response_data;
+
+pub async fn get_publisher(
+    State(state): State<ClientState>,
+    Path(address): Path<String>,
+    headers: HeaderMap,
+) -> ClientResult<impl IntoResponse> {
+    let version = headers.api_version()?;
+    let address = address.checksum();
+
+    let publisher = state.publisher_repo
+        .find_by_address(address.as_str())
+        .await?
+        .ok_or(ClientError::NotFound)?;
+
+    let apps = state.publisher_repo
+        .find_apps(address.as_str())
+        .await?;
+
+    Ok(response_data(PublisherDto::versioned(publisher, apps, version)))
+}
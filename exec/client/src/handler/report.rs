@@ -1,42 +1,50 @@
+use crate::data::id::ReportCategory;
 use crate::data::models::NewReport;
+use crate::net::client_ip::client_ip;
+use crate::net::extract::Json;
 use crate::result::{ClientError, ClientResult};
 use crate::state::ClientState;
-use axum::extract::State;
-use axum::http::StatusCode;
+use crate::util::email::is_valid_email;
+use axum::extract::{ConnectInfo, State};
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
-use axum::Json;
+use codegen_contracts::ext::ToChecksum;
 use net_result::response_nullable;
+use std::net::SocketAddr;
+use tracing::warn;
 
-// TODO add filter
-// TRAFFICKING_NARCOTICS(1),
-// TRAFFICKING_COUNTERFEIT(2),
-// TRAFFICKING_STOLEN_GOODS(3),
-// CYBERCRIME_MALWARE(4),
-// CYBERCRIME_HACKING(5),
-// CYBERCRIME_CARDING(6),
-// FINANCIAL_CRIME_LAUNDERING(7),
-// FINANCIAL_CRIME_FRAUD(8),
-// FINANCIAL_CRIME_STOLEN_DATA(9),
-// EXPLOITATION_CSAM(10),
-// EXPLOITATION_HUMAN_TRAFFICKING(11),
-// EXPLOITATION_VIOLENCE_FOR_HIRE(12),
-// EXPLOITATION_TERRORISM(13),
-// PRIVACY_DOXING(14),
-// PRIVACY_SURVEILLANCE(15);
 pub async fn create_report(
     State(state): State<ClientState>,
-    Json(payload): Json<NewReport>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(mut payload): Json<NewReport>,
 ) -> ClientResult<impl IntoResponse> {
-    if payload.email.is_empty() || !payload.email.contains('@') { // TODO check email
-        return Err(ClientError::BadInput("A valid email address is required".to_string()));
+    payload.email = payload.email.trim().to_lowercase();
+    if !is_valid_email(payload.email.as_str()) {
+        return Err(ClientError::invalid("email", "must be a valid email address"));
     }
 
-    if payload.category_id <= 0 {
-        return Err(ClientError::BadInput("A valid reason ID is required".to_string()));
+    if payload.category_id == ReportCategory::Unspecified {
+        return Err(ClientError::invalid("categoryId", "unknown report category"));
     }
 
-    state.report_repo.create(payload)
+    if payload.description.as_ref().is_some_and(|description| description.len() > 2000) {
+        return Err(ClientError::invalid("description", "max 2000 characters"));
+    }
+
+    if !state.object_repo.has_by_address(payload.asset_address.checksum().as_str()).await {
+        return Err(ClientError::NotFound);
+    }
+
+    let ip = client_ip(&headers, &remote).to_string();
+    let address = payload.asset_address.clone();
+
+    let flagged = state.report_repo.create(payload, ip.as_str())
         .await?;
 
+    if flagged {
+        warn!("Asset {} is flagged for review by reports", address);
+    }
+
     Ok((StatusCode::CREATED, response_nullable()))
 }
@@ -1,12 +1,16 @@
-use crate::env::default_page_size;
-use crate::data::models::NewReview;
+use crate::env::{default_page_size, review_signature_ttl_sec};
+use crate::data::id::TrackId;
+use crate::data::models::{NewReview, ReviewCursor};
+use crate::net::extract::{Json, Path, Query};
+use crate::net::signature::{is_fresh, recover_signer};
 use crate::result::{ClientError, ClientResult};
 use crate::state::ClientState;
-use axum::extract::{Path, Query, State};
+use crate::util::cursor::{check_page_params, decode_cursor, split_page};
+use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use axum::Json;
-use net_result::{response_data, response_null};
+use codegen_contracts::ext::ToChecksum;
+use net_result::{response_null, response_page};
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug)]
@@ -15,6 +19,7 @@ pub struct ReviewListParams {
     pub size: i64,
     #[serde(default)]
     pub offset: i64,
+    pub cursor: Option<String>,
 }
 
 pub async fn get_reviews_for_object(
@@ -22,42 +27,88 @@ pub async fn get_reviews_for_object(
     Path(asset_id): Path<i64>,
     Query(params): Query<ReviewListParams>,
 ) -> ClientResult<impl IntoResponse> {
-    if params.size > 100 {
-        return Err(
-            ClientError::BadInput(
-                "query parameter 'size' max 100".to_string(),
-            )
-        );
-    }
+    check_page_params(params.size, params.offset, params.cursor.as_deref())?;
+    let cursor = decode_cursor::<ReviewCursor>(params.cursor.as_deref())?;
 
-    state.object_repo.find_by_id(asset_id)
+    state.object_repo.find_by_id(asset_id, &TrackId::Release)
         .await?;
 
     let reviews = state
         .review_repo
-        .find_by_asset_id(asset_id, params.size, params.offset)
+        .find_by_asset_id(asset_id, cursor, params.size + 1, params.offset)
         .await?;
 
-    Ok(response_data(reviews))
+    let (reviews, next_cursor) = split_page(reviews, params.size, |review| ReviewCursor {
+        id: review.id,
+    })?;
+
+    Ok(response_page(reviews, next_cursor))
+}
+
+// For POST /review/create, `signature` is EIP-191 over `review_sign_message`
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReviewRequest {
+    pub asset_id: i64,
+    pub rating: i32,
+    pub text: Option<String>,
+    pub timestamp: i64,
+    pub signature: String,
 }
 
 pub async fn create_review(
     State(state): State<ClientState>,
-    Json(payload): Json<NewReview>,
+    Json(payload): Json<CreateReviewRequest>,
 ) -> ClientResult<impl IntoResponse> {
     if !(1..=5).contains(&payload.rating) {
-        return Err(ClientError::BadInput("Rating must be between 1 and 5".to_string()));
+        return Err(ClientError::invalid("rating", "must be between 1 and 5"));
     }
 
-    if payload.user_id.is_empty() {
-        return Err(ClientError::BadInput("User ID cannot be empty".to_string()));
+    if !is_fresh(payload.timestamp, review_signature_ttl_sec()) {
+        return Err(ClientError::Unauthorized("Signature is expired".to_string()));
     }
-    
-    state.object_repo.find_by_id(payload.asset_id)
+
+    let message = review_sign_message(
+        payload.asset_id, payload.rating,
+        payload.text.as_deref(), payload.timestamp
+    );
+
+    let author = recover_signer(message.as_str(), payload.signature.as_str())?;
+
+    let object = state.object_repo.find_by_id(payload.asset_id, &TrackId::Release)
         .await?;
 
-    state.review_repo.create(payload)
+    if object.is_none() {
+        return Err(ClientError::NotFound);
+    }
+
+    let review = NewReview {
+        asset_id: payload.asset_id,
+        user_id: author.checksum(),
+        rating: payload.rating,
+        text: payload.text,
+        signature: payload.signature.to_lowercase(),
+        signed_at: payload.timestamp,
+    };
+
+    state.review_repo.upsert(review)
         .await?; 
 
     Ok((StatusCode::CREATED, response_null::<String>()))
-}
\ No newline at end of file
+}
+
+pub fn review_sign_message(asset_id: i64, rating: i32, text: Option<&str>, timestamp: i64) -> String {
+    return format!(
+        "OpenStore review\nasset: {}\nrating: {}\ntext: {}\ntimestamp: {}",
+        asset_id, rating, text.unwrap_or(""), timestamp
+    );
+}
+
+#[test]
+fn check_review_sign_message() {
+    let message = review_sign_message(7, 5, Some("Great app"), 1_700_000_000);
+    assert_eq!(message, "OpenStore review\nasset: 7\nrating: 5\ntext: Great app\ntimestamp: 1700000000");
+
+    let message = review_sign_message(7, 1, None, 1_700_000_000);
+    assert_eq!(message, "OpenStore review\nasset: 7\nrating: 1\ntext: \ntimestamp: 1700000000");
+}
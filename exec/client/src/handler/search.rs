@@ -1,10 +1,16 @@
+use crate::data::dto::AssetDto;
+use crate::data::models::SearchCursor;
 use crate::env::default_page_size;
 use crate::data::id::{ObjTypeId, PlatformId};
+use crate::net::extract::Query;
+use crate::net::headers::ServiceHeaders;
 use crate::result::{ClientError, ClientResult};
 use crate::state::ClientState;
-use axum::extract::{Query, State};
+use crate::util::cursor::{check_page_params, decode_cursor, split_page};
+use axum::extract::State;
+use axum::http::HeaderMap;
 use axum::response::IntoResponse;
-use net_result::{response_data, response_empty};
+use net_result::{response_empty, response_page};
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug)]
@@ -20,27 +26,21 @@ pub struct SearchParams {
     pub size: i64,
     #[serde(default)]
     pub offset: i64,
+    pub cursor: Option<String>,
 }
 
 pub async fn search_objects(
     State(state): State<ClientState>,
     Query(params): Query<SearchParams>,
+    headers: HeaderMap,
 ) -> ClientResult<impl IntoResponse> {
-    if params.size > 100 {
-        return Err(
-            ClientError::BadInput(
-                "query parameter 'size' max 100".to_string(),
-            )
-        );
-    }
+    let version = headers.api_version()?;
+    check_page_params(params.size, params.offset, params.cursor.as_deref())?;
+    let cursor = decode_cursor::<SearchCursor>(params.cursor.as_deref())?;
 
     let search_term = params.content;
     if search_term.len() < 3 {
-        return Err(
-            ClientError::BadInput(
-                "query parameter 'content' at least 3 symbols".to_string(),
-            )
-        );
+        return Err(ClientError::invalid("content", "at least 3 symbols"));
     }
 
     let results = match params.category_id {
@@ -50,7 +50,8 @@ pub async fn search_objects(
                 &search_term,
                 params.platform_id.into(),
                 value,
-                params.size,
+                cursor,
+                params.size + 1,
                 params.offset,
             )
             .await?,
@@ -60,11 +61,17 @@ pub async fn search_objects(
                 &search_term,
                 params.platform_id.into(),
                 params.type_id,
-                params.size,
+                cursor,
+                params.size + 1,
                 params.offset,
             )
             .await?
     };
 
-    Ok(response_data(results))
+    let (results, next_cursor) = split_page(results, params.size, |asset| SearchCursor {
+        downloads: asset.downloads,
+        id: asset.id,
+    })?;
+
+    Ok(response_page(AssetDto::versioned_list(results, version), next_cursor))
 }
@@ -1,11 +1,17 @@
-use crate::data::id::{ObjTypeId, PlatformId};
-use crate::env::default_page_size;
-use crate::result::{ClientError, ClientResult};
+use crate::data::dto::AssetDto;
+use crate::data::id::{ChartSort, ObjTypeId, PlatformId};
+use crate::data::models::{Asset, ChartCursor};
+use crate::env::{default_page_size, feed_refresh_sec};
+use crate::net::extract::Query;
+use crate::net::headers::ServiceHeaders;
+use crate::result::ClientResult;
 use crate::state::ClientState;
-use axum::extract::{Query, State};
+use crate::util::cursor::{check_page_params, decode_cursor, split_page};
+use axum::extract::State;
 use axum::http::HeaderMap;
 use axum::response::Response;
 use headers::{HeaderMapExt, IfNoneMatch};
+use net_result::{response_data, response_page};
 use serde::Deserialize;
 use std::str::FromStr;
 
@@ -27,38 +33,53 @@ pub struct ChartParams {
     #[serde(rename = "type")]
     pub type_id: Option<ObjTypeId>,
     pub platform: PlatformId,
+    #[serde(default)]
+    pub sort: ChartSort,
+    pub cursor: Option<String>,
+}
+
+pub fn get_feed_etag_pattern() -> String {
+    return "cache:etag:get_feed:*".to_string();
 }
 
-fn get_feed_key(params: &FeedParams) -> String {
-    let type_id: i32 = params.type_id.clone().into();
-    return format!("static:get_feed:{}", type_id);
+pub fn get_feed_etag_type_pattern(type_id: &ObjTypeId) -> String {
+    let type_id: i32 = type_id.clone().into();
+    return format!("cache:etag:get_feed:{}:*", type_id);
 }
 
-fn get_feed_etag_key(params: &FeedParams) -> String {
-    let type_id: i32 = params.type_id.clone().into(); 
-    return format!("cache:etag:get_feed:{}", type_id);
+pub fn get_feed_etag_key(type_id: &ObjTypeId, platform: &PlatformId, version: u32) -> String {
+    let type_id: i32 = type_id.clone().into();
+    let platform: i32 = platform.clone().into();
+    return format!("cache:etag:get_feed:{}:{}:v{}", type_id, platform, version);
 }
 
 fn get_feed_ttl() -> Option<u64> {
-    return Some(60 * 60);
+    return Some(feed_refresh_sec());
 }
 
+// Feed is composed from `feed_section` rows, the etag is derived from the built content
 pub async fn get_feed(
     State(state): State<ClientState>,
     Query(params): Query<FeedParams>,
     headers: HeaderMap,
 ) -> ClientResult<Response> {
-    let etag_key = get_feed_etag_key(&params);
+    let version = headers.api_version()?;
+    let etag_key = get_feed_etag_key(&params.type_id, &params.platform, version);
     let user_etag = headers.typed_get::<IfNoneMatch>();
 
-    return state.etag_handler.etag_cache_or_static(
-        etag_key,
-        user_etag,
-        get_feed_key(&params)
+    return state.etag_handler.etag_cache_or(
+        etag_key, user_etag, get_feed_ttl(),
+        || async {
+            let feed = state.feed_builder
+                .build(&params.type_id, params.platform, version)
+                .await?;
+
+            return Ok(response_data(feed));
+        }
     ).await
 }
 
-fn get_categories_etag() -> String {
+pub fn get_categories_etag() -> String {
     return "cache:etag:get_categories".to_string();
 }
 
@@ -76,22 +97,28 @@ pub async fn get_categories(
     return state.etag_handler.etag_cache_or(
         etag_key, user_etag, get_categories_ttl(),
         || async {
-            state.category_repo.get_all().await
+            let categories = state.category_repo.get_all().await?;
+            return Ok(response_data(categories));
         }
     ).await
 }
 
-fn get_chart_etag(params: &ChartParams) -> String {
+fn get_chart_etag(params: &ChartParams, version: u32) -> String {
     let category = params.category_id.unwrap_or(0);
     let platform: i32 = params.platform.clone().into();
     let type_id: i32 = params.type_id.clone().unwrap_or(ObjTypeId::Unspecified).into();
     return format!(
-        "cache:etag:get_chart:{}:{}:{}:{}:{}",
-        platform, type_id,
-        category, params.size, params.offset
+        "cache:etag:get_chart:v{}:{}:{}:{}:{}:{}:{}:{}",
+        version, platform, type_id, category,
+        params.sort, params.size, params.offset,
+        params.cursor.as_deref().unwrap_or_default()
     );
 }
 
+pub fn get_chart_etag_pattern() -> String {
+    return "cache:etag:get_chart:*".to_string();
+}
+
 fn get_chart_ttl() -> Option<u64> {
     return Some(60 * 60);
 }
@@ -101,38 +128,49 @@ pub async fn get_chart(
     Query(params): Query<ChartParams>,
     headers: HeaderMap,
 ) -> ClientResult<Response> {
-    if params.size > 100 {
-        return Err(ClientError::BadInput(
-            "query parameter 'size' max 100".to_string(),
-        ));
-    }
+    check_page_params(params.size, params.offset, params.cursor.as_deref())?;
+    let cursor = decode_cursor::<ChartCursor>(params.cursor.as_deref())?;
+    let by_rating = params.sort == ChartSort::Rating;
+    let by_trending = params.sort == ChartSort::Trending;
 
-    let etag_key = get_chart_etag(&params);
+    let version = headers.api_version()?;
+    let etag_key = get_chart_etag(&params, version);
     let user_etag = headers.typed_get::<IfNoneMatch>();
 
     return state.etag_handler.etag_cache_or(
         etag_key, user_etag, get_chart_ttl(),
         || async {
-            return match params.category_id {
+            let assets = match params.category_id {
                 Some(cat_id) => {
                     state
                         .object_repo
                         .chart_by_category(
-                            params.platform.into(), cat_id,
-                            params.size, params.offset
+                            params.platform.into(), cat_id, params.sort,
+                            cursor, params.size + 1, params.offset
                         )
-                        .await
+                        .await?
                 }
                 None => {
                     state
                         .object_repo
                         .chart_by_app_type(
-                            params.platform.into(), params.type_id, 
-                            params.size, params.offset
+                            params.platform.into(), params.type_id, params.sort,
+                            cursor, params.size + 1, params.offset
                         )
-                        .await
+                        .await?
                 }
             };
+
+            let (assets, next_cursor) = split_page(assets, params.size, |asset| ChartCursor {
+                position: asset.chart_position,
+                rating: if by_rating { asset.rating } else { 0.0 },
+                score: if by_trending { asset.trending_score } else { 0.0 },
+                downloads: asset.downloads,
+                id: asset.id,
+            })?;
+
+            let assets = assets.into_iter().map(Asset::from).collect();
+            return Ok(response_page(AssetDto::versioned_list(assets, version), next_cursor));
         }
     ).await
 }
@@ -0,0 +1,71 @@
+use crate::data::dto::UpdateCheckDto;
+use crate::data::id::TrackId;
+use crate::data::models::InstalledApp;
+use crate::env::update_check_max_apps;
+use crate::handler::object::TrackParams;
+use crate::net::extract::Json;
+use crate::result::{ClientError, ClientResult};
+use crate::state::ClientState;
+use crate::util::fingerprint::normalize_fingerprint;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use codegen_contracts::ext::ToChecksum;
+use net_result::response_data;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledAppRequest {
+    pub package_name: Option<String>,
+    pub address: Option<String>,
+    pub version_code: i64,
+    pub fingerprint: String,
+}
+
+// For POST /asset/updates
+#[derive(Deserialize, Debug)]
+pub struct UpdateCheckRequest {
+    pub apps: Vec<InstalledAppRequest>,
+    pub track: Option<TrackId>,
+}
+
+pub async fn check_updates(
+    State(state): State<ClientState>,
+    Json(payload): Json<UpdateCheckRequest>,
+) -> ClientResult<impl IntoResponse> {
+    let track_id = TrackParams { track: payload.track }.track_id()?;
+
+    if payload.apps.is_empty() || payload.apps.len() > update_check_max_apps() {
+        return Err(ClientError::invalid("apps", format!("from 1 to {} apps", update_check_max_apps())));
+    }
+
+    let mut apps = Vec::with_capacity(payload.apps.len());
+    for (i, app) in payload.apps.into_iter().enumerate() {
+        let field = |name: &str| format!("apps[{}].{}", i, name);
+
+        if app.package_name.is_none() && app.address.is_none() {
+            return Err(ClientError::invalid(field("packageName"), "package name or address is required"));
+        }
+
+        if app.version_code < 0 {
+            return Err(ClientError::invalid(field("versionCode"), "can't be negative"));
+        }
+
+        let Some(fingerprint) = normalize_fingerprint(app.fingerprint.as_str()) else {
+            return Err(ClientError::invalid(field("fingerprint"), "must be a sha-256 certificate fingerprint"));
+        };
+
+        apps.push(InstalledApp {
+            package_name: app.package_name,
+            address: app.address.map(|address| address.checksum()),
+            version_code: app.version_code,
+            fingerprint,
+        });
+    }
+
+    let candidates = state.artifact_repo
+        .find_updates(apps.as_slice(), &track_id)
+        .await?;
+
+    Ok(response_data(UpdateCheckDto::from(candidates)))
+}
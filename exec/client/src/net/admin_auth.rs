@@ -0,0 +1,168 @@
+use crate::data::id::AdminRole;
+use crate::data::models::NewAuditEntry;
+use crate::env;
+use crate::net::headers::{ADMIN_ADDRESS, ADMIN_NONCE, ADMIN_SIGNATURE, ADMIN_TIMESTAMP, API_KEY};
+use crate::net::signature::{is_fresh, recover_signer};
+use crate::result::{ClientError, ClientResult};
+use crate::state::ClientState;
+use axum::body::{Body, Bytes};
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::request::Parts;
+use axum::http::HeaderMap;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use codegen_contracts::ext::ToChecksum;
+use openssl::sha::sha256;
+use std::str::FromStr;
+
+// Admin caller resolved either from `X-API-KEY` or from an EIP-191 wallet signature over
+// `admin_sign_message` passed in `X-ADMIN-ADDRESS`, `X-ADMIN-SIGNATURE`, `X-ADMIN-TIMESTAMP`, `X-ADMIN-NONCE`.
+// Resolved by the `admin_auth` middleware, the signed message covers the body
+#[derive(Debug, Clone)]
+pub struct AdminAuth {
+    pub actor: String,
+    pub role: AdminRole,
+}
+
+impl AdminAuth {
+
+    pub fn require(&self, role: AdminRole) -> ClientResult<()> {
+        if !self.role.allows(role) {
+            return Err(ClientError::Forbidden(format!("Role `{}` required", role)));
+        }
+
+        return Ok(())
+    }
+
+    pub fn audit(&self, action: &str, target: String, payload: Option<String>) -> NewAuditEntry {
+        return NewAuditEntry {
+            actor: self.actor.clone(),
+            role: self.role,
+            action: action.to_string(),
+            target,
+            payload,
+        }
+    }
+}
+
+impl FromRequestParts<ClientState> for AdminAuth {
+    type Rejection = ClientError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &ClientState,
+    ) -> Result<Self, Self::Rejection> {
+        return parts.extensions.get::<AdminAuth>()
+            .cloned()
+            .ok_or(ClientError::Unauthorized("Credentials are missing".into()));
+    }
+}
+
+// Buffers the body to check the signature over it, handlers get it back untouched
+pub async fn admin_auth(
+    State(state): State<ClientState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (mut parts, body) = request.into_parts();
+    let Ok(body) = axum::body::to_bytes(body, env::admin_body_limit()).await else {
+        return ClientError::BadInput("Request body is too large".into()).into_response();
+    };
+
+    match authenticate(&state, &parts, &body).await {
+        Ok(auth) => {
+            parts.extensions.insert(auth);
+        }
+        Err(e) => return e.into_response(),
+    }
+
+    return next.run(Request::from_parts(parts, Body::from(body))).await;
+}
+
+async fn authenticate(state: &ClientState, parts: &Parts, body: &Bytes) -> ClientResult<AdminAuth> {
+    if let Some(key) = header_str(&parts.headers, API_KEY)? {
+        let key_hash = admin_key_hash(key);
+        let Some((name, role)) = state.admin_repo.find_key(key_hash.as_str()).await? else {
+            return Err(ClientError::Unauthorized("Unknown api key".into()));
+        };
+
+        return Ok(AdminAuth { actor: format!("key:{}", name), role: AdminRole::from(role) });
+    }
+
+    let Some(address) = header_str(&parts.headers, ADMIN_ADDRESS)? else {
+        return Err(ClientError::Unauthorized("Credentials are missing".into()));
+    };
+
+    let Some(signature) = header_str(&parts.headers, ADMIN_SIGNATURE)? else {
+        return Err(ClientError::Unauthorized("Signature is missing".into()));
+    };
+
+    let Some(timestamp) = header_str(&parts.headers, ADMIN_TIMESTAMP)? else {
+        return Err(ClientError::Unauthorized("Timestamp is missing".into()));
+    };
+
+    let Some(nonce) = header_str(&parts.headers, ADMIN_NONCE)? else {
+        return Err(ClientError::Unauthorized("Nonce is missing".into()));
+    };
+
+    if nonce.len() < 8 || nonce.len() > 64 || !nonce.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(ClientError::BadInput("X-Admin-Nonce format is incorrect!".into()));
+    }
+
+    let Ok(timestamp) = i64::from_str(timestamp) else {
+        return Err(ClientError::BadInput("X-Admin-Timestamp format is incorrect!".into()));
+    };
+
+    let ttl = env::admin_signature_ttl_sec();
+    if !is_fresh(timestamp, ttl) {
+        return Err(ClientError::Unauthorized("Signature is expired".into()));
+    }
+
+    let path = parts.uri.path_and_query()
+        .map(|path| path.as_str())
+        .unwrap_or(parts.uri.path());
+
+    let message = admin_sign_message(parts.method.as_str(), path, timestamp, nonce, &admin_body_hash(body));
+    let signer = recover_signer(message.as_str(), signature)?;
+    let address = address.to_string().checksum();
+
+    if signer.checksum() != address {
+        return Err(ClientError::Unauthorized("Signature doesn't match address".into()));
+    }
+
+    // Timestamps are accepted `ttl` around now, the nonce is kept until no signature with it can be fresh
+    if !state.cache_repo.claim_admin_nonce(address.as_str(), nonce, ttl * 2).await? {
+        return Err(ClientError::Unauthorized("Nonce is already used".into()));
+    }
+
+    let Some(role) = state.admin_repo.find_wallet_role(address.as_str()).await? else {
+        return Err(ClientError::Unauthorized("Unknown wallet".into()));
+    };
+
+    return Ok(AdminAuth { actor: format!("wallet:{}", address), role: AdminRole::from(role) });
+}
+
+pub fn admin_key_hash(key: &str) -> String {
+    return blake3::hash(key.as_bytes()).to_hex().to_string();
+}
+
+// Hex sha256 of the raw body, of an empty body for requests without one
+pub fn admin_body_hash(body: &[u8]) -> String {
+    return hex::encode(sha256(body));
+}
+
+pub fn admin_sign_message(method: &str, path: &str, timestamp: i64, nonce: &str, body_hash: &str) -> String {
+    return format!("{} {}\n{}\n{}\n{}", method, path, timestamp, nonce, body_hash);
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> ClientResult<Option<&'a str>> {
+    let Some(value) = headers.get(name) else {
+        return Ok(None);
+    };
+
+    let Ok(value) = value.to_str() else {
+        return Err(ClientError::BadInput(format!("{} format is incorrect!", name)));
+    };
+
+    return Ok(Some(value));
+}
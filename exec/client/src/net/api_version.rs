@@ -0,0 +1,100 @@
+use crate::env;
+use crate::net::headers::{ApiNamedVersion, ServiceHeaders, API_VERSION, DEPRECATION, SUNSET};
+use crate::result::{ClientError, ClientResult};
+use axum::extract::Request;
+use axum::http::header::VARY;
+use axum::http::{HeaderMap, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::DateTime;
+
+// Rejects versions outside of the supported range, echoes the negotiated version
+// and marks responses of deprecated versions with `Deprecation` (RFC 9745) and `Sunset` (RFC 8594)
+pub async fn negotiate_api_version(request: Request, next: Next) -> ClientResult<Response> {
+    let version = request.headers().api_version()?;
+    if !version.is_supported() {
+        return Err(ClientError::UnsupportedVersion(version));
+    }
+
+    let mut response = next.run(request).await;
+
+    let headers = response.headers_mut();
+    headers.insert(API_VERSION, HeaderValue::from(version));
+    headers.append(VARY, HeaderValue::from_static(API_VERSION));
+
+    if version.is_deprecated() {
+        insert_deprecation(headers, env::api_deprecated_at(), env::api_sunset_at())?;
+    }
+
+    return Ok(response);
+}
+
+fn insert_deprecation(headers: &mut HeaderMap, deprecated_at: Option<i64>, sunset_at: Option<i64>) -> ClientResult<()> {
+    if let Some(deprecated_at) = deprecated_at {
+        headers.insert(DEPRECATION, header_value(format!("@{}", deprecated_at))?);
+    }
+
+    if let Some(sunset_at) = sunset_at {
+        headers.insert(SUNSET, header_value(http_date(sunset_at))?);
+    }
+
+    return Ok(());
+}
+
+fn header_value(value: String) -> ClientResult<HeaderValue> {
+    return HeaderValue::try_from(value)
+        .map_err(|e| ClientError::HttpError(e.into()));
+}
+
+fn http_date(timestamp: i64) -> String {
+    return DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string();
+}
+
+#[tokio::test]
+async fn check_negotiate_api_version() {
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    let app = Router::new()
+        .route("/", get(|| async { "ok" }))
+        .layer(axum::middleware::from_fn(negotiate_api_version));
+
+    let request = |version: &str| Request::builder()
+        .uri("/")
+        .header(API_VERSION, version)
+        .body(Body::empty())
+        .unwrap();
+
+    // Nothing is deprecated without `DEPRECATED_API_VERSION`
+    let response = app.clone().oneshot(request("1")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[API_VERSION], "1");
+    assert!(response.headers().get(DEPRECATION).is_none());
+    assert!(response.headers().get(SUNSET).is_none());
+
+    let response = app.clone().oneshot(request("2")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(DEPRECATION).is_none());
+
+    let response = app.clone().oneshot(request("99")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn check_deprecation_headers() {
+    let mut headers = HeaderMap::new();
+    insert_deprecation(&mut headers, Some(1792281600), Some(1808006400)).unwrap();
+    assert_eq!(headers[DEPRECATION], "@1792281600");
+    assert_eq!(headers[SUNSET], "Sun, 18 Apr 2027 00:00:00 GMT");
+
+    let mut headers = HeaderMap::new();
+    insert_deprecation(&mut headers, Some(1792281600), None).unwrap();
+    assert_eq!(headers[DEPRECATION], "@1792281600");
+    assert!(headers.get(SUNSET).is_none());
+}
@@ -0,0 +1,66 @@
+use crate::env;
+use axum::http::HeaderMap;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+
+pub static FORWARDED_FOR: &'static str = "X-FORWARDED-FOR";
+pub static REAL_IP: &'static str = "X-REAL-IP";
+
+pub fn client_ip(headers: &HeaderMap, remote: &SocketAddr) -> IpAddr {
+    return forwarded_ip(headers, remote, &env::trusted_proxies());
+}
+
+// Forwarding headers are only read from trusted proxies, any client can send them. Proxies append
+// the address they got the request from, so the right-most hop that isn't a proxy is the client
+pub fn forwarded_ip(headers: &HeaderMap, remote: &SocketAddr, trusted: &[IpAddr]) -> IpAddr {
+    if !trusted.contains(&remote.ip()) {
+        return remote.ip();
+    }
+
+    let forwarded = headers.get_all(FORWARDED_FOR)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .collect::<Vec<&str>>();
+
+    for hop in forwarded.into_iter().rev() {
+        let Ok(ip) = IpAddr::from_str(hop.trim()) else {
+            // Garbage left of a proxy hop is client input, nothing beyond it can be trusted
+            break;
+        };
+
+        if !trusted.contains(&ip) {
+            return ip;
+        }
+    }
+
+    let real = headers.get(REAL_IP)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| IpAddr::from_str(value.trim()).ok());
+
+    return real.unwrap_or(remote.ip());
+}
+
+#[test]
+fn check_forwarded_ip() {
+    let proxy = SocketAddr::from(([10, 0, 0, 1], 443));
+    let client = SocketAddr::from(([203, 0, 113, 7], 443));
+    let trusted = [IpAddr::from([10, 0, 0, 1]), IpAddr::from([10, 0, 0, 2])];
+    let ip = |value: &str| IpAddr::from_str(value).unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(FORWARDED_FOR, "1.1.1.1, 198.51.100.4, 10.0.0.2".parse().unwrap());
+
+    // Spoofed left-most entries are skipped
+    assert_eq!(forwarded_ip(&headers, &proxy, &trusted), ip("198.51.100.4"));
+    // Headers of a direct client are ignored
+    assert_eq!(forwarded_ip(&headers, &client, &trusted), ip("203.0.113.7"));
+
+    headers.insert(FORWARDED_FOR, "1.1.1.1, junk, 10.0.0.2".parse().unwrap());
+    assert_eq!(forwarded_ip(&headers, &proxy, &trusted), ip("10.0.0.1"));
+
+    headers.remove(FORWARDED_FOR);
+    headers.insert(REAL_IP, "198.51.100.5".parse().unwrap());
+    assert_eq!(forwarded_ip(&headers, &proxy, &trusted), ip("198.51.100.5"));
+    assert_eq!(forwarded_ip(&headers, &client, &trusted), ip("203.0.113.7"));
+}
@@ -1,11 +1,11 @@
 use crate::data::repo::cache_repo::CacheRepo;
-use crate::result::{ClientError, ClientResult};
+use crate::result::ClientResult;
 use axum::http::StatusCode;
 use axum::response::Response;
 use bytes::Bytes;
 use core_std::empty::Empty;
 use headers::{ETag, IfNoneMatch};
-use net_result::{response_data, ResponseConstructor};
+use net_result::{ResponseConstructor, ResponseOk};
 use serde::Serialize;
 use std::future::Future;
 use std::str::FromStr;
@@ -23,40 +23,6 @@ impl EtagHandler {
     }
 
     // TODO move to admin and calculate etag
-    pub async fn etag_cache_or_static(
-        &self,
-        etag_key: String,
-        none_match: Option<IfNoneMatch>,
-        cache_key: String,
-    ) -> ClientResult<Response> {
-        let actual_etag = self.cache_repo.get_etag(etag_key.as_ref())
-            .await
-            .or_empty();
-
-        if self.compare_etag(none_match, actual_etag.as_ref()) {
-            debug!("etag not-modified");
-            return Ok(
-                Response::with_code(StatusCode::NOT_MODIFIED)?
-            );
-        }
-        
-        let content = self.cache_repo.get_content(cache_key.as_ref())
-            .await
-            .ok_or(ClientError::NotFound)?;
-
-        let result = self.cache_repo.set_etag_by_content(
-            etag_key.as_ref(), content.as_ref()
-        ).await;
-
-        debug!("etag cache-miss");
-        return Ok(
-            match result {
-                Ok(new_etag) => Response::with_etag(new_etag, Bytes::from_owner(content))?,
-                Err(_) => Response::with_body(Bytes::from_owner(content))?
-            }
-        );
-    }
-    
     pub async fn etag_cache_or<F, Fut, R>(
         &self,
         etag_key: String,
@@ -67,7 +33,7 @@ impl EtagHandler {
     where
         F: FnOnce() -> Fut,
         R: Serialize,
-        Fut: Future<Output=ClientResult<R>> {
+        Fut: Future<Output=ClientResult<ResponseOk<R>>> {
         let actual_etag = self.cache_repo.get_etag(etag_key.as_ref())
             .await
             .or_empty();
@@ -88,10 +54,9 @@ impl EtagHandler {
             }
         }
 
-        let result = producer()
+        let response = producer()
             .await?;
 
-        let response = response_data(result);
         let content = serde_json::to_string(&response.data)?;
 
         let result = self.cache_repo.set_etag_with_content(
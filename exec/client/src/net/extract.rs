@@ -0,0 +1,177 @@
+use crate::result::ClientError;
+use axum::extract::path::ErrorKind;
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Deref;
+
+// Axum extractors answering with the error catalog instead of plain text rejections,
+// values that don't deserialize come back as `Validation` of their field
+pub struct Json<T>(pub T);
+
+pub struct Query<T>(pub T);
+
+pub struct Path<T>(pub T);
+
+impl<S: Send + Sync, T: DeserializeOwned> FromRequest<S> for Json<T> {
+    type Rejection = ClientError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::Json(value) = axum::Json::<T>::from_request(request, state)
+            .await?;
+
+        return Ok(Json(value));
+    }
+}
+
+impl<S: Send + Sync, T: DeserializeOwned> FromRequestParts<S> for Query<T> {
+    type Rejection = ClientError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Query(value) = axum::extract::Query::<T>::from_request_parts(parts, state)
+            .await?;
+
+        return Ok(Query(value));
+    }
+}
+
+impl<S: Send + Sync, T: DeserializeOwned + Send> FromRequestParts<S> for Path<T> {
+    type Rejection = ClientError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Path(value) = axum::extract::Path::<T>::from_request_parts(parts, state)
+            .await?;
+
+        return Ok(Path(value));
+    }
+}
+
+impl<T> Deref for Query<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        return &self.0;
+    }
+}
+
+impl<T> Deref for Path<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        return &self.0;
+    }
+}
+
+impl From<JsonRejection> for ClientError {
+    fn from(rejection: JsonRejection) -> Self {
+        return match rejection {
+            JsonRejection::JsonDataError(ref e) => {
+                field_error::<serde_json::Error>(e, "body")
+                    .unwrap_or(ClientError::BadInput(rejection.body_text()))
+            }
+            JsonRejection::JsonSyntaxError(_) => ClientError::BadInput("Invalid JSON format".to_string()),
+            _ => ClientError::BadInput(rejection.body_text()),
+        }
+    }
+}
+
+impl From<QueryRejection> for ClientError {
+    fn from(rejection: QueryRejection) -> Self {
+        return match rejection {
+            QueryRejection::FailedToDeserializeQueryString(ref e) => {
+                field_error::<serde::de::value::Error>(e, "query")
+                    .unwrap_or(ClientError::BadInput(rejection.body_text()))
+            }
+            _ => ClientError::BadInput(rejection.body_text()),
+        }
+    }
+}
+
+impl From<PathRejection> for ClientError {
+    fn from(rejection: PathRejection) -> Self {
+        let PathRejection::FailedToDeserializePathParams(ref e) = rejection else {
+            return ClientError::BadInput(rejection.body_text());
+        };
+
+        return match e.kind() {
+            ErrorKind::ParseErrorAtKey { key, expected_type, .. } => {
+                ClientError::invalid(key.as_str(), format!("expected {}", expected_type))
+            }
+            ErrorKind::ParseError { expected_type, .. } | ErrorKind::ParseErrorAtIndex { expected_type, .. } => {
+                ClientError::invalid("path", format!("expected {}", expected_type))
+            }
+            ErrorKind::DeserializeError { key, message, .. } => ClientError::invalid(key.as_str(), message.as_str()),
+            _ => ClientError::BadInput(rejection.body_text()),
+        }
+    }
+}
+
+// Rejections keep the `serde_path_to_error` error two sources deep
+fn field_error<E: Error + Display + 'static>(rejection: &dyn Error, root: &str) -> Option<ClientError> {
+    let error = rejection.source()?
+        .source()?
+        .downcast_ref::<serde_path_to_error::Error<E>>()?;
+
+    let field = match error.path().to_string() {
+        path if path == "." => root.to_string(),
+        path => path,
+    };
+
+    return Some(ClientError::invalid(field, error.inner().to_string()));
+}
+
+#[tokio::test]
+async fn check_rejections_use_catalog() {
+    use crate::handler::object::TrackParams;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::{get, post};
+    use axum::Router;
+    use tower::ServiceExt;
+
+    async fn body(app: Router, request: Request) -> (StatusCode, String) {
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        return (status, String::from_utf8(bytes.to_vec()).unwrap());
+    }
+
+    let app = Router::new()
+        .route("/track", get(|Query(_): Query<TrackParams>| async { "ok" }))
+        .route("/id/{id}", get(|Path(_): Path<i64>| async { "ok" }))
+        .route("/body", post(|Json(_): Json<TrackParams>| async { "ok" }));
+
+    let request = Request::get("/track?track=nope").body(Body::empty()).unwrap();
+    let (status, json) = body(app.clone(), request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json.starts_with(r#"{"code":1001,"message":"Validation failed","details":[{"field":"track","#), "{}", json);
+
+    let request = Request::get("/track?track=beta").body(Body::empty()).unwrap();
+    let (status, _) = body(app.clone(), request).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let request = Request::get("/id/abc").body(Body::empty()).unwrap();
+    let (status, json) = body(app.clone(), request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json.starts_with(r#"{"code":1001,"message":"Validation failed","details":[{"field":"#), "{}", json);
+
+    let request = Request::post("/body")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"track":"nope"}"#))
+        .unwrap();
+    let (status, json) = body(app.clone(), request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json.contains(r#""field":"track""#), "{}", json);
+
+    let request = Request::post("/body")
+        .header("content-type", "application/json")
+        .body(Body::from("{"))
+        .unwrap();
+    let (status, json) = body(app, request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json, r#"{"code":1000,"message":"Invalid JSON format"}"#);
+}
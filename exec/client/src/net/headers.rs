@@ -6,6 +6,21 @@ use lazy_static::lazy_static;
 use std::str::FromStr;
 
 pub static API_VERSION: &'static str = "X-API-VERSION";
+pub static API_KEY: &'static str = "X-API-KEY";
+pub static ADMIN_ADDRESS: &'static str = "X-ADMIN-ADDRESS";
+pub static ADMIN_SIGNATURE: &'static str = "X-ADMIN-SIGNATURE";
+pub static ADMIN_TIMESTAMP: &'static str = "X-ADMIN-TIMESTAMP";
+pub static ADMIN_NONCE: &'static str = "X-ADMIN-NONCE";
+pub static DEPRECATION: &'static str = "Deprecation";
+pub static SUNSET: &'static str = "Sunset";
+pub static RATE_LIMIT_LIMIT: &'static str = "RateLimit-Limit";
+pub static RATE_LIMIT_REMAINING: &'static str = "RateLimit-Remaining";
+pub static RATE_LIMIT_RESET: &'static str = "RateLimit-Reset";
+pub static CHECKSUM_BLAKE3: &'static str = "X-Checksum-Blake3";
+pub static ARTIFACT_SIZE: &'static str = "X-Artifact-Size";
+
+pub const API_V1: u32 = 1;
+pub const API_V2: u32 = 2;
 
 lazy_static! {
     static ref DEFAULT_API_VERSION: HeaderValue = HeaderValue::from(env::api_version());
@@ -34,10 +49,20 @@ impl ServiceHeaders for HeaderMap {
 
 pub trait ApiNamedVersion {
     fn is_protocol_zero(&self) -> bool;
+    fn is_supported(&self) -> bool;
+    fn is_deprecated(&self) -> bool;
 }
 
 impl ApiNamedVersion for u32 {
     fn is_protocol_zero(&self) -> bool {
-        return true;
+        return *self < API_V2;
+    }
+
+    fn is_supported(&self) -> bool {
+        return (env::min_api_version()..=env::max_api_version()).contains(self);
+    }
+
+    fn is_deprecated(&self) -> bool {
+        return *self <= env::deprecated_api_version();
     }
 }
@@ -0,0 +1,65 @@
+use crate::data::models::CacheEvent;
+use crate::data::repo::cache_repo::CacheRepo;
+use crate::handler::store::{get_categories_etag, get_chart_etag_pattern, get_feed_etag_pattern, get_feed_etag_type_pattern};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+const RECONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Etag patterns served from data touched by the event. Assets are embedded in charts and feeds,
+// so any asset change purges all of them. Etag TTL stays only as a safety net for lost events.
+pub fn invalidation_patterns(event: &CacheEvent) -> Vec<String> {
+    return match event {
+        CacheEvent::AssetChanged { .. } => vec![get_chart_etag_pattern(), get_feed_etag_pattern()],
+        CacheEvent::CategoryChanged => vec![get_categories_etag(), get_chart_etag_pattern()],
+        CacheEvent::FeedChanged { type_id } => vec![get_feed_etag_type_pattern(type_id)],
+        CacheEvent::ChartChanged => vec![get_chart_etag_pattern()],
+    }
+}
+
+pub async fn purge(cache_repo: &CacheRepo, event: &CacheEvent) {
+    for pattern in invalidation_patterns(event) {
+        match cache_repo.delete_pattern(pattern.as_str()).await {
+            Ok(count) => info!("Purged {} etags by {} for {:?}", count, pattern, event),
+            Err(e) => warn!("Can't purge etags by {}: {}", pattern, e),
+        }
+    }
+}
+
+// Events published while the api was disconnected are lost, so everything is purged on (re)subscribe
+pub async fn listen_invalidations(cache_repo: Arc<CacheRepo>) {
+    loop {
+        for event in [CacheEvent::CategoryChanged, CacheEvent::AssetChanged { addresses: vec![] }] {
+            purge(&cache_repo, &event).await;
+        }
+
+        let result = cache_repo.listen_events(|event| {
+            let cache_repo = cache_repo.clone();
+            async move {
+                purge(&cache_repo, &event).await;
+            }
+        }).await;
+
+        match result {
+            Ok(()) => warn!("Cache events subscription is closed, reconnecting"),
+            Err(e) => error!("Cache events subscription failed, reconnecting: {}", e),
+        }
+
+        sleep(RECONNECT_TIMEOUT).await;
+    }
+}
+
+#[test]
+fn check_invalidation_patterns() {
+    use crate::data::id::ObjTypeId;
+
+    let event: CacheEvent = serde_json::from_str(r#"{"type":"feed_changed","type_id":"game"}"#).unwrap();
+    assert_eq!(event, CacheEvent::FeedChanged { type_id: ObjTypeId::Game });
+    assert_eq!(invalidation_patterns(&event), vec!["cache:etag:get_feed:2:*".to_string()]);
+
+    let event = CacheEvent::AssetChanged { addresses: vec!["0x01".to_string()] };
+    assert_eq!(serde_json::to_string(&event).unwrap(), r#"{"type":"asset_changed","addresses":["0x01"]}"#);
+    assert_eq!(invalidation_patterns(&event), vec!["cache:etag:get_chart:*".to_string(), "cache:etag:get_feed:*".to_string()]);
+}
@@ -1,3 +1,9 @@
-
 pub mod etag_handler;
-pub mod headers;
\ No newline at end of file
+pub mod headers;
+pub mod admin_auth;
+pub mod signature;
+pub mod client_ip;
+pub mod api_version;
+pub mod rate_limit;
+pub mod invalidation;
+pub mod extract;
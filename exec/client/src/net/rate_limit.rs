@@ -0,0 +1,221 @@
+use crate::env;
+use crate::net::client_ip::client_ip;
+use crate::net::headers::{RATE_LIMIT_LIMIT, RATE_LIMIT_REMAINING, RATE_LIMIT_RESET};
+use crate::result::{ClientError, ClientResult};
+use async_trait::async_trait;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::header::RETRY_AFTER;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use db_redis::cache::RedisCache;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+#[derive(Debug, Clone)]
+pub struct RateLimitPolicy {
+    pub name: &'static str,
+    pub limit: u64,
+    pub window_sec: u64,
+}
+
+impl RateLimitPolicy {
+
+    pub fn new(name: &'static str, limit: u64) -> Self {
+        return Self { name, limit, window_sec: env::rate_limit_window_sec() }
+    }
+
+    pub fn search() -> Self {
+        return Self::new("search", env::search_rate_limit())
+    }
+
+    pub fn review() -> Self {
+        return Self::new("review", env::review_rate_limit())
+    }
+
+    pub fn report() -> Self {
+        return Self::new("report", env::report_rate_limit())
+    }
+
+    pub fn update_check() -> Self {
+        return Self::new("update_check", env::update_check_rate_limit())
+    }
+
+    // Proxy downloads stream the whole apk through the api
+    pub fn download() -> Self {
+        return Self::new("download", env::download_rate_limit())
+    }
+
+    pub fn admin() -> Self {
+        return Self::new("admin", env::admin_rate_limit())
+    }
+}
+
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    // Increments the counter of `bucket` and returns it together with the counter of the previous bucket
+    async fn hit(&self, key: &str, bucket: u64, ttl: u64) -> ClientResult<(u64, u64)>;
+}
+
+pub struct RedisRateLimitStore {
+    cache: Arc<RedisCache>,
+}
+
+impl RedisRateLimitStore {
+    pub fn new(cache: Arc<RedisCache>) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for RedisRateLimitStore {
+    async fn hit(&self, key: &str, bucket: u64, ttl: u64) -> ClientResult<(u64, u64)> {
+        let current = self.cache.incr(format!("{}:{}", key, bucket).as_str(), ttl)
+            .await?;
+
+        let previous = self.cache.get::<u64>(format!("{}:{}", key, bucket.saturating_sub(1)).as_str())
+            .await?
+            .unwrap_or(0);
+
+        return Ok((current, previous));
+    }
+}
+
+// Counters never expire, meant for tests and local runs without redis
+#[derive(Default)]
+pub struct MemoryRateLimitStore {
+    counters: Mutex<HashMap<String, u64>>,
+}
+
+#[async_trait]
+impl RateLimitStore for MemoryRateLimitStore {
+    async fn hit(&self, key: &str, bucket: u64, _ttl: u64) -> ClientResult<(u64, u64)> {
+        let mut counters = self.counters.lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        let current = counters.entry(format!("{}:{}", key, bucket)).or_insert(0);
+        *current += 1;
+        let current = *current;
+
+        let previous = counters.get(&format!("{}:{}", key, bucket.saturating_sub(1)))
+            .copied()
+            .unwrap_or(0);
+
+        return Ok((current, previous));
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    store: Arc<dyn RateLimitStore>,
+    policy: RateLimitPolicy,
+}
+
+impl RateLimiter {
+    pub fn new(store: Arc<dyn RateLimitStore>, policy: RateLimitPolicy) -> Self {
+        Self { store, policy }
+    }
+}
+
+// Sliding window counter: the previous window is weighted by the part of it still inside the sliding window.
+// Store failures don't block the api, the request is passed through without limit headers.
+pub async fn rate_limit(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let policy = &limiter.policy;
+    let remote = request.extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|info| info.0)
+        .unwrap_or(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)));
+
+    // Admin credentials aren't checked yet here, a bucket per claimed key would let callers guess keys unlimited
+    let key = format!("rate:{}:{}", policy.name, client_ip(request.headers(), &remote));
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or_default();
+
+    let window = policy.window_sec.max(1);
+    let bucket = now / window;
+    let elapsed = now % window;
+
+    let (current, previous) = match limiter.store.hit(key.as_str(), bucket, window * 2).await {
+        Ok(counters) => counters,
+        Err(e) => {
+            warn!("Rate limit store failed for {}: {}", key, e);
+            return next.run(request).await;
+        }
+    };
+
+    let used = current + previous * (window - elapsed) / window;
+    let remaining = policy.limit.saturating_sub(used);
+    let reset = window - elapsed;
+
+    let mut response = if used > policy.limit {
+        let mut response = ClientError::RateLimited(format!("Too many requests, retry in {} seconds", reset))
+            .into_response();
+
+        response.headers_mut().insert(RETRY_AFTER, HeaderValue::from(reset));
+        response
+    } else {
+        next.run(request).await
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(RATE_LIMIT_LIMIT, HeaderValue::from(policy.limit));
+    headers.insert(RATE_LIMIT_REMAINING, HeaderValue::from(remaining));
+    headers.insert(RATE_LIMIT_RESET, HeaderValue::from(reset));
+
+    return response;
+}
+
+#[tokio::test]
+async fn check_rate_limit() {
+    use crate::net::client_ip::FORWARDED_FOR;
+    use crate::net::headers::API_KEY;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    let policy = RateLimitPolicy { name: "test", limit: 2, window_sec: 3600 };
+    let limiter = RateLimiter::new(Arc::new(MemoryRateLimitStore::default()), policy);
+
+    let app = Router::new()
+        .route("/", get(|| async { "ok" }))
+        .layer(axum::middleware::from_fn_with_state(limiter, rate_limit));
+
+    // Forwarded addresses of a client that isn't a trusted proxy and claimed api keys are ignored
+    let request = |ip: [u8; 4], spoofed: &str| Request::builder()
+        .uri("/")
+        .header(FORWARDED_FOR, spoofed)
+        .header(API_KEY, spoofed)
+        .extension(ConnectInfo(SocketAddr::from((ip, 443))))
+        .body(Body::empty())
+        .unwrap();
+
+    for (remaining, spoofed) in [("1", "1.1.1.1"), ("0", "2.2.2.2")] {
+        let response = app.clone().oneshot(request([10, 0, 0, 1], spoofed)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[RATE_LIMIT_LIMIT], "2");
+        assert_eq!(response.headers()[RATE_LIMIT_REMAINING], remaining);
+    }
+
+    let response = app.clone().oneshot(request([10, 0, 0, 1], "3.3.3.3")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().contains_key(RETRY_AFTER));
+
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(json["code"], 4000);
+
+    let response = app.clone().oneshot(request([10, 0, 0, 2], "3.3.3.3")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,51 @@
+use crate::result::{ClientError, ClientResult};
+use alloy::primitives::{Address, Signature};
+use core_std::time::current_time;
+use std::str::FromStr;
+
+// EIP-191 personal_sign recovery
+pub fn recover_signer(message: &str, signature: &str) -> ClientResult<Address> {
+    let Ok(signature) = Signature::from_str(signature) else {
+        return Err(ClientError::Unauthorized("Signature format is incorrect".into()));
+    };
+
+    let Ok(signer) = signature.recover_address_from_msg(message) else {
+        return Err(ClientError::Unauthorized("Can't recover signer".into()));
+    };
+
+    return Ok(signer);
+}
+
+pub fn is_fresh(timestamp: i64, ttl_sec: u64) -> bool {
+    return current_time().timestamp().abs_diff(timestamp) <= ttl_sec;
+}
+
+#[tokio::test]
+async fn check_recover_signer() {
+    use crate::env;
+    use crate::net::admin_auth::{admin_body_hash, admin_sign_message};
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::signers::Signer;
+
+    let signer = PrivateKeySigner::from_str(env::validator_pk().as_str()).unwrap();
+    let body = admin_body_hash(br#"{"categories":[]}"#);
+    let message = admin_sign_message("POST", "/v1/admin/categories", 1_700_000_000, "n0nce-1", &body);
+    let signature = signer.sign_message(message.as_bytes()).await.unwrap();
+
+    let recovered = recover_signer(message.as_str(), signature.to_string().as_str()).unwrap();
+    assert_eq!(recovered, signer.address());
+
+    let other = admin_sign_message("POST", "/v1/admin/visibility", 1_700_000_000, "n0nce-1", &body);
+    let recovered = recover_signer(other.as_str(), signature.to_string().as_str()).unwrap();
+    assert_ne!(recovered, signer.address());
+
+    // A captured signature doesn't cover another body or nonce
+    let other_body = admin_body_hash(br#"{"categories":[{"id":1}]}"#);
+    for other in [
+        admin_sign_message("POST", "/v1/admin/categories", 1_700_000_000, "n0nce-1", &other_body),
+        admin_sign_message("POST", "/v1/admin/categories", 1_700_000_000, "n0nce-2", &body),
+    ] {
+        let recovered = recover_signer(other.as_str(), signature.to_string().as_str()).unwrap();
+        assert_ne!(recovered, signer.address());
+    }
+}
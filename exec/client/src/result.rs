@@ -1,16 +1,18 @@
+use crate::env;
 use alloy::transports::RpcError;
 use axum::{
-    http::StatusCode,
+    http::{header::CONTENT_RANGE, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use db_redis::cache::KeyValueError;
 use net_client::node::result::EthError;
-use net_result::response_err;
+use net_result::{response_err_details, ResponseErrorDetail};
 use prost::DecodeError;
 use serde_json::json;
 use thiserror::Error;
 use client_ethscan::error::EthScanError;
+use client_gf::client::GfError;
 
 pub type ClientResult<T> = Result<T, ClientError>;
 
@@ -25,10 +27,19 @@ pub enum ClientError {
     #[error("Item not found")]
     NotFound,
 
+    #[error("Item is blocked: {0}")]
+    Blocked(String),
+
     #[error("Invalid input: {0}")]
     BadInput(String),
 
-    #[error("JSON deserialization error: {0}")]
+    #[error("Unsupported api version: {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("Validation failed: {0:?}")]
+    Validation(Vec<ResponseErrorDetail>),
+
+    #[error("JSON serialization error: {0}")]
     JsonError(#[from] serde_json::Error),
 
     #[error("Error during working with cache: {0}")]
@@ -40,6 +51,15 @@ pub enum ClientError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Too many requests: {0}")]
+    RateLimited(String),
+
     #[error("Eth error: {0}")]
     EthError(#[from] EthError),
 
@@ -48,49 +68,125 @@ pub enum ClientError {
 
     #[error("Decode error: {0}")]
     DecodeError(#[from] DecodeError),
+
+    #[error("Storage error: {0}")]
+    StorageError(#[from] GfError),
+
+    #[error("Range is not satisfiable for size {0}")]
+    RangeNotSatisfiable(u64),
 }
 
+impl ClientError {
+    pub fn invalid<F: Into<String>, M: Into<String>>(field: F, message: M) -> Self {
+        return ClientError::Validation(vec![ResponseErrorDetail::new(field, message)]);
+    }
+}
+
+// Codes are part of the public api, never reuse or renumber them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClientErrorCodes {
     Unknown = 0,
+
+    // Request
+    BadInput = 1000,
+    Validation = 1001,
+    UnsupportedVersion = 1002,
+    RangeNotSatisfiable = 1003,
+
+    // Access
+    Unauthorized = 2000,
+    Forbidden = 2001,
+
+    // State
+    NotFound = 3000,
+    Blocked = 3001,
+    Conflict = 3002,
+
+    // Limits
+    RateLimited = 4000,
+
+    // Dependencies
+    Internal = 5000,
+    Database = 5001,
+    Cache = 5002,
+    Chain = 5003,
+    ChainScan = 5004,
+    Storage = 5005,
 }
 
 impl IntoResponse for ClientError {
     fn into_response(self) -> Response {
+        let mut details = vec![];
+        let mut content_range = None;
         let (status, code, message) = match self {
             ClientError::Sqlx(ref e) => {
                 tracing::error!("Database error: {:?}", e);
                 (
-                    StatusCode::INTERNAL_SERVER_ERROR, ClientErrorCodes::Unknown,
+                    StatusCode::INTERNAL_SERVER_ERROR, ClientErrorCodes::Database,
                     "An internal error occurred".to_string(),
                 )
             }
             ClientError::NotFound => (
-                StatusCode::NOT_FOUND, ClientErrorCodes::Unknown,
+                StatusCode::NOT_FOUND, ClientErrorCodes::NotFound,
                 "Resource not found".to_string()
             ),
+            ClientError::Blocked(reason) => (
+                StatusCode::GONE, ClientErrorCodes::Blocked,
+                reason
+            ),
             ClientError::BadInput(msg) => (
-                StatusCode::BAD_REQUEST, ClientErrorCodes::Unknown,
+                StatusCode::BAD_REQUEST, ClientErrorCodes::BadInput,
                 msg
             ),
+            ClientError::UnsupportedVersion(version) => (
+                StatusCode::BAD_REQUEST, ClientErrorCodes::UnsupportedVersion,
+                format!(
+                    "Api version {} is not supported, use {}..={}",
+                    version, env::min_api_version(), env::max_api_version()
+                )
+            ),
+            ClientError::Validation(fields) => {
+                details = fields;
+                (
+                    StatusCode::BAD_REQUEST, ClientErrorCodes::Validation,
+                    "Validation failed".to_string()
+                )
+            }
+            // Request bodies are rejected by the extractors, this one is our own data
             ClientError::JsonError(ref e) => {
                 tracing::error!("JSON error: {:?}", e);
                 (
-                    StatusCode::BAD_REQUEST, ClientErrorCodes::Unknown,
-                    "Invalid JSON format".to_string()
+                    StatusCode::INTERNAL_SERVER_ERROR, ClientErrorCodes::Internal,
+                    "An internal error occurred".to_string()
                 )
             }
             ClientError::Conflict(msg) => (
-                StatusCode::CONFLICT, ClientErrorCodes::Unknown,
+                StatusCode::CONFLICT, ClientErrorCodes::Conflict,
                 msg
             ),
-            ClientError::CacheError(msg) => (
-                StatusCode::CONFLICT, ClientErrorCodes::Unknown,
-                "An internal error occurred".to_string(),
+            ClientError::Unauthorized(msg) => (
+                StatusCode::UNAUTHORIZED, ClientErrorCodes::Unauthorized,
+                msg
+            ),
+            ClientError::Forbidden(msg) => (
+                StatusCode::FORBIDDEN, ClientErrorCodes::Forbidden,
+                msg
             ),
+            ClientError::RateLimited(msg) => (
+                StatusCode::TOO_MANY_REQUESTS, ClientErrorCodes::RateLimited,
+                msg
+            ),
+            ClientError::CacheError(e) => {
+                tracing::error!("Cache error: {:?}", e);
+                (
+                    StatusCode::SERVICE_UNAVAILABLE, ClientErrorCodes::Cache,
+                    "Service temporarily unavailable".to_string(),
+                )
+            }
             ClientError::Migration(e) => {
                 tracing::error!("Migration error: {:?}", e);
                 (
-                    StatusCode::INTERNAL_SERVER_ERROR, ClientErrorCodes::Unknown,
+                    StatusCode::INTERNAL_SERVER_ERROR, ClientErrorCodes::Database,
                     "An internal error occurred".to_string(),
                 )
             },
@@ -98,28 +194,91 @@ impl IntoResponse for ClientError {
                 tracing::error!("HTTP error: {:?}", e);
 
                 (
-                    StatusCode::INTERNAL_SERVER_ERROR, ClientErrorCodes::Unknown,
+                    StatusCode::INTERNAL_SERVER_ERROR, ClientErrorCodes::Internal,
                     "An internal error occurred".to_string(),
                 )
             },
             ClientError::EthScanError(e) => {
                 tracing::error!("EthScan error: {:?}", e);
                 (
-                    StatusCode::INTERNAL_SERVER_ERROR, ClientErrorCodes::Unknown,
-                    "An internal error occurred".to_string(),
+                    StatusCode::BAD_GATEWAY, ClientErrorCodes::ChainScan,
+                    "Upstream service error".to_string(),
                 )
             },
-            ClientError::EthError(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR, ClientErrorCodes::Unknown,
-                "An internal error occurred".to_string(),
-            ),
-            ClientError::DecodeError(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR, ClientErrorCodes::Unknown,
-                "An internal error occurred".to_string(),
-            )
+            ClientError::EthError(e) => {
+                tracing::error!("Eth error: {:?}", e);
+                (
+                    StatusCode::BAD_GATEWAY, ClientErrorCodes::Chain,
+                    "Upstream service error".to_string(),
+                )
+            },
+            ClientError::DecodeError(e) => {
+                tracing::error!("Decode error: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR, ClientErrorCodes::Internal,
+                    "An internal error occurred".to_string(),
+                )
+            }
+            ClientError::StorageError(e) => {
+                tracing::error!("Storage error: {:?}", e);
+                (
+                    StatusCode::BAD_GATEWAY, ClientErrorCodes::Storage,
+                    "Upstream service error".to_string(),
+                )
+            }
+            ClientError::RangeNotSatisfiable(size) => {
+                content_range = Some(format!("bytes */{size}"));
+                (
+                    StatusCode::RANGE_NOT_SATISFIABLE, ClientErrorCodes::RangeNotSatisfiable,
+                    "Requested range is not satisfiable".to_string(),
+                )
+            }
+        };
+
+        let error = response_err_details(code as i32, message, details);
+        let Some(content_range) = content_range else {
+            return (status, error).into_response();
         };
 
-        let error = response_err(code as i32, message );
-        (status, error).into_response()
+        (status, [(CONTENT_RANGE, content_range)], error).into_response()
+    }
+}
+
+#[tokio::test]
+async fn check_error_response_format() {
+    async fn body(error: ClientError) -> (StatusCode, String) {
+        let response = error.into_response();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        return (status, String::from_utf8(bytes.to_vec()).unwrap());
     }
+
+    let (status, json) = body(ClientError::NotFound).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(json, r#"{"code":3000,"message":"Resource not found"}"#);
+
+    let (status, json) = body(ClientError::invalid("content", "at least 3 symbols")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json, r#"{"code":1001,"message":"Validation failed","details":[{"field":"content","message":"at least 3 symbols"}]}"#);
+
+    let (status, json) = body(ClientError::UnsupportedVersion(99)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json, r#"{"code":1002,"message":"Api version 99 is not supported, use 1..=2"}"#);
+
+    let (status, json) = body(ClientError::RateLimited("Too many requests".to_string())).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(json, r#"{"code":4000,"message":"Too many requests"}"#);
+
+    let (status, json) = body(ClientError::CacheError(KeyValueError::Json(serde_json::from_str::<i32>("-").unwrap_err()))).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(json, r#"{"code":5002,"message":"Service temporarily unavailable"}"#);
+
+    let (status, json) = body(ClientError::JsonError(serde_json::from_str::<i32>("-").unwrap_err())).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(json, r#"{"code":5000,"message":"An internal error occurred"}"#);
+
+    let response = ClientError::RangeNotSatisfiable(100).into_response();
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(response.headers()[CONTENT_RANGE], "bytes */100");
 }
@@ -1,14 +1,23 @@
 use std::sync::Arc;
+use crate::data::feed_builder::FeedBuilder;
+use crate::data::repo::admin_repo::AdminRepo;
 use crate::data::repo::artifact_repo::ArtifactRepo;
 use crate::data::repo::assetlink_repo::AssetlinkRepo;
+use crate::data::repo::cache_repo::CacheRepo;
+use crate::data::repo::error_repo::ErrorRepo;
+use crate::data::repo::feed_repo::FeedRepo;
 use crate::data::repo::category_repo::CategoryRepo;
 use crate::data::repo::object_repo::ObjectRepo;
+use crate::data::repo::outbox_repo::OutboxRepo;
+use crate::data::repo::ownership_repo::OwnershipRepo;
+use crate::data::repo::publisher_repo::PublisherRepo;
 use crate::data::repo::publishing_repo::PublishingRepo;
 use crate::data::repo::report_repo::ReportRepo;
 use crate::data::repo::review_repo::ReviewRepo;
 use crate::data::repo::search_repo::SearchRepo;
 use crate::data::repo::validation_repo::ValidationRepo;
 use crate::net::etag_handler::EtagHandler;
+use client_gf::client::GreenfieldClient;
 
 #[derive(Clone)]
 pub struct ClientState {
@@ -21,5 +30,14 @@ pub struct ClientState {
     pub validation_repo: Arc<ValidationRepo>,
     pub artifact_repo: Arc<ArtifactRepo>,
     pub report_repo: Arc<ReportRepo>,
+    pub admin_repo: Arc<AdminRepo>,
+    pub cache_repo: Arc<CacheRepo>,
     pub etag_handler: Arc<EtagHandler>,
+    pub feed_repo: Arc<FeedRepo>,
+    pub feed_builder: Arc<FeedBuilder>,
+    pub greenfield: Arc<GreenfieldClient>,
+    pub publisher_repo: Arc<PublisherRepo>,
+    pub error_repo: Arc<ErrorRepo>,
+    pub ownership_repo: Arc<OwnershipRepo>,
+    pub outbox_repo: Arc<OutboxRepo>,
 }
@@ -0,0 +1,85 @@
+use crate::result::{ClientError, ClientResult};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Cursor is opaque for clients: base64 of the json keyset of the last returned row
+pub fn encode_cursor<K: Serialize>(key: &K) -> ClientResult<String> {
+    let json = serde_json::to_vec(key)?;
+    return Ok(URL_SAFE_NO_PAD.encode(json));
+}
+
+pub fn decode_cursor<K: DeserializeOwned>(cursor: Option<&str>) -> ClientResult<Option<K>> {
+    let Some(cursor) = cursor else {
+        return Ok(None);
+    };
+
+    let Ok(json) = URL_SAFE_NO_PAD.decode(cursor) else {
+        return Err(ClientError::invalid("cursor", "is malformed"));
+    };
+
+    let Ok(key) = serde_json::from_slice(json.as_slice()) else {
+        return Err(ClientError::invalid("cursor", "is malformed"));
+    };
+
+    return Ok(Some(key));
+}
+
+// Repos are queried for `size + 1` rows, the extra row only tells that the next page exists
+pub fn split_page<T, K: Serialize>(
+    mut items: Vec<T>,
+    size: i64,
+    key: impl Fn(&T) -> K,
+) -> ClientResult<(Vec<T>, Option<String>)> {
+    if items.len() as i64 <= size {
+        return Ok((items, None));
+    }
+
+    items.truncate(size as usize);
+
+    let next_cursor = match items.last() {
+        Some(last) => Some(encode_cursor(&key(last))?),
+        None => None,
+    };
+
+    return Ok((items, next_cursor));
+}
+
+// Offset paging is deprecated, it's still served when no cursor is passed
+pub fn check_page_params(size: i64, offset: i64, cursor: Option<&str>) -> ClientResult<()> {
+    if size > 100 {
+        return Err(ClientError::invalid("size", "max 100"));
+    }
+
+    if size < 1 {
+        return Err(ClientError::invalid("size", "at least 1"));
+    }
+
+    if offset < 0 {
+        return Err(ClientError::invalid("offset", "can't be negative"));
+    }
+
+    if offset > 0 && cursor.is_some() {
+        return Err(ClientError::invalid("offset", "can't be combined with cursor"));
+    }
+
+    return Ok(());
+}
+
+#[test]
+fn check_split_page() {
+    #[derive(Debug, PartialEq, serde::Deserialize, Serialize)]
+    struct Key { id: i64 }
+
+    let (items, next_cursor) = split_page(vec![5, 4, 3], 2, |id| Key { id: *id }).unwrap();
+    assert_eq!(items, vec![5, 4]);
+
+    let key: Option<Key> = decode_cursor(next_cursor.as_deref()).unwrap();
+    assert_eq!(key, Some(Key { id: 4 }));
+
+    let (items, next_cursor) = split_page(vec![5, 4], 2, |id| Key { id: *id }).unwrap();
+    assert_eq!(items, vec![5, 4]);
+    assert_eq!(next_cursor, None);
+
+    assert!(decode_cursor::<Key>(Some("not a cursor")).is_err());
+}
@@ -0,0 +1,63 @@
+// Pragmatic subset of RFC 5322: dot-atom local part and a dns domain with alphabetic tld
+pub fn is_valid_email(email: &str) -> bool {
+    if email.len() > 254 {
+        return false
+    }
+
+    let Some((local, domain)) = email.split_once('@') else {
+        return false
+    };
+
+    if local.is_empty() || local.len() > 64 || domain.contains('@') {
+        return false
+    }
+
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return false
+    }
+
+    let is_local_char = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~.".contains(c);
+    if !local.chars().all(is_local_char) {
+        return false
+    }
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2 {
+        return false
+    }
+
+    let is_valid_label = |label: &&str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+
+    if !labels.iter().all(is_valid_label) {
+        return false
+    }
+
+    let tld = labels[labels.len() - 1];
+    return tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic());
+}
+
+#[test]
+fn check_is_valid_email() {
+    assert!(is_valid_email("user@example.com"));
+    assert!(is_valid_email("first.last+tag@mail.example.co"));
+    assert!(is_valid_email("o'brien@sub-domain.example.org"));
+
+    assert!(!is_valid_email(""));
+    assert!(!is_valid_email("@"));
+    assert!(!is_valid_email("user@"));
+    assert!(!is_valid_email("@example.com"));
+    assert!(!is_valid_email("user@localhost"));
+    assert!(!is_valid_email("user@@example.com"));
+    assert!(!is_valid_email("user@exa mple.com"));
+    assert!(!is_valid_email(".user@example.com"));
+    assert!(!is_valid_email("us..er@example.com"));
+    assert!(!is_valid_email("user@-example.com"));
+    assert!(!is_valid_email("user@example.c"));
+    assert!(!is_valid_email("user@example.123"));
+}
@@ -0,0 +1,27 @@
+// Signing certificate SHA-256 in the stored form, accepts `AB:CD:..`, `0xabcd..` and plain hex
+pub fn normalize_fingerprint(fingerprint: &str) -> Option<String> {
+    let fingerprint = fingerprint.trim();
+    let fingerprint = fingerprint.strip_prefix("0x")
+        .unwrap_or(fingerprint);
+
+    let hex = fingerprint.replace(':', "")
+        .to_ascii_lowercase();
+
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None
+    }
+
+    return Some(hex)
+}
+
+#[test]
+fn check_normalize_fingerprint() {
+    let hex = "14:6d:e9:83:c5:73:06:50:d8:ee:b9:95:2f:34:fc:64:16:a0:83:42:e6:1d:be:a8:8a:04:96:b2:3f:cf:44:e5";
+    let expected = "146de983c5730650d8eeb9952f34fc6416a08342e61dbea88a0496b23fcf44e5";
+
+    assert_eq!(normalize_fingerprint(hex.to_uppercase().as_str()).as_deref(), Some(expected));
+    assert_eq!(normalize_fingerprint(format!("0x{}", expected).as_str()).as_deref(), Some(expected));
+    assert_eq!(normalize_fingerprint(expected), Some(expected.to_string()));
+    assert_eq!(normalize_fingerprint("14:6d:e9"), None);
+    assert_eq!(normalize_fingerprint(&"zz".repeat(32)), None);
+}
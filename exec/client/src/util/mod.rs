@@ -1,3 +1,6 @@
-
 pub mod etag;
-pub mod proof_validator;
\ No newline at end of file
+pub mod proof_validator;
+pub mod email;
+pub mod cursor;
+pub mod fingerprint;
+pub mod state_diff;
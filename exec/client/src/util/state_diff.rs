@@ -0,0 +1,84 @@
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+// One leaf of the asset state that differs, `None` when the leaf is missing on that side
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateChange {
+    pub key: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl Display for StateChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match (&self.before, &self.after) {
+            (None, Some(after)) => write!(f, "+ {}: {}", self.key, after),
+            (Some(before), None) => write!(f, "- {}: {}", self.key, before),
+            (Some(before), Some(after)) => write!(f, "~ {}: {} -> {}", self.key, before, after),
+            (None, None) => write!(f, "  {}", self.key),
+        };
+    }
+}
+
+// Compares two json states leaf by leaf, keys are dot separated paths
+pub fn diff_states(before: &str, after: &str) -> serde_json::Result<Vec<StateChange>> {
+    let before = flatten(&serde_json::from_str(before)?);
+    let after = flatten(&serde_json::from_str(after)?);
+
+    let keys = before.keys()
+        .chain(after.keys())
+        .collect::<BTreeSet<&String>>();
+
+    let changes = keys.into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .map(|key| StateChange {
+            key: key.clone(),
+            before: before.get(key).cloned(),
+            after: after.get(key).cloned(),
+        })
+        .collect();
+
+    return Ok(changes);
+}
+
+fn flatten(value: &Value) -> BTreeMap<String, Value> {
+    let mut leaves = BTreeMap::new();
+    flatten_into("", value, &mut leaves);
+
+    return leaves;
+}
+
+fn flatten_into(prefix: &str, value: &Value, leaves: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten_into(&path, value, leaves);
+            }
+        }
+        Value::Null => {}
+        _ => {
+            leaves.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+#[test]
+fn check_diff_states() {
+    use serde_json::json;
+
+    let before = r#"{"obj": {"name": "Old", "logo": "a.png"}, "tracks": {"1": {"versionCode": 3}}}"#;
+    let after = r#"{"obj": {"name": "New", "logo": "a.png", "owner": "0xB"}, "tracks": {}}"#;
+
+    let changes = diff_states(before, after).unwrap();
+    assert_eq!(changes, vec![
+        StateChange { key: "obj.name".to_string(), before: Some(json!("Old")), after: Some(json!("New")) },
+        StateChange { key: "obj.owner".to_string(), before: None, after: Some(json!("0xB")) },
+        StateChange { key: "tracks.1.versionCode".to_string(), before: Some(json!(3)), after: None },
+    ]);
+    assert_eq!(changes[0].to_string(), r#"~ obj.name: "Old" -> "New""#);
+    assert_eq!(changes[2].to_string(), "- tracks.1.versionCode: 3");
+
+    assert!(diff_states(after, after).unwrap().is_empty());
+}
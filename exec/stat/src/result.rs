@@ -55,7 +55,7 @@ impl IntoResponse for StatError {
             }
         };
 
-        let error = ResponseDataError { code: code as i32, message };
+        let error = ResponseDataError { code: code as i32, message, details: vec![] };
 
         (status, Json(error)).into_response()
     }
@@ -12,8 +12,12 @@ pub struct ResponseOk<T> where T : Serialize {
 }
 
 #[derive(Debug, Clone, Display, Serialize)]
+#[display("{data}")]
 pub struct ResponseData<T> where T : Serialize {
     pub data: T,
+    // Opaque token of the next page for paginated lists, absent on the last page
+    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Clone, Display)]
@@ -26,30 +30,53 @@ pub struct ResponseErr {
 pub struct ResponseDataError {
     pub code: i32,
     pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<ResponseErrorDetail>,
+}
+
+#[derive(Debug, Clone, PartialEq, Display, Serialize)]
+#[display("{field}: {message}")]
+pub struct ResponseErrorDetail {
+    pub field: String,
+    pub message: String,
+}
+
+impl ResponseErrorDetail {
+    pub fn new<F: Into<String>, M: Into<String>>(field: F, message: M) -> Self {
+        return Self { field: field.into(), message: message.into() }
+    }
 }
 
 pub fn response_err(code: i32, message: String) -> ResponseErr {
-    return ResponseErr { data: ResponseDataError { code, message } }
+    return ResponseErr { data: ResponseDataError { code, message, details: vec![] } }
+}
+
+pub fn response_err_details(code: i32, message: String, details: Vec<ResponseErrorDetail>) -> ResponseErr {
+    return ResponseErr { data: ResponseDataError { code, message, details } }
 }
 
 pub fn response_ok() -> ResponseOk<String> {
-    return ResponseOk { data: ResponseData { data: "Ok".to_string() } }
+    return ResponseOk { data: ResponseData { data: "Ok".to_string(), next_cursor: None } }
 }
 
 pub fn response_null<T>() -> ResponseOk<Option<T>> where T : Serialize {
-    return ResponseOk { data: ResponseData { data: None } }
+    return ResponseOk { data: ResponseData { data: None, next_cursor: None } }
 }
 
 pub fn response_nullable() -> ResponseOk<Option<String>> {
-    return ResponseOk { data: ResponseData { data: None } }
+    return ResponseOk { data: ResponseData { data: None, next_cursor: None } }
 }
 
 pub fn response_empty<T>() -> ResponseOk<Vec<T>> where T : Serialize {
-    return ResponseOk { data: ResponseData { data: vec![] } }
+    return ResponseOk { data: ResponseData { data: vec![], next_cursor: None } }
 }
 
 pub fn response_data<T>(data: T) -> ResponseOk<T> where T : Serialize {
-    return ResponseOk { data: ResponseData { data } }
+    return ResponseOk { data: ResponseData { data, next_cursor: None } }
+}
+
+pub fn response_page<T>(data: T, next_cursor: Option<String>) -> ResponseOk<T> where T : Serialize {
+    return ResponseOk { data: ResponseData { data, next_cursor } }
 }
 
 impl <T> IntoResponse for ResponseOk<T> where T : Serialize {
@@ -105,3 +132,28 @@ impl ResponseConstructor for Response {
             .body(Body::from(body))
     }
 }
+
+#[test]
+fn check_error_wire_format() {
+    let error = response_err(3000, "Resource not found".to_string());
+    let json = serde_json::to_string(&error.data).unwrap();
+    assert_eq!(json, r#"{"code":3000,"message":"Resource not found"}"#);
+
+    let error = response_err_details(
+        1001, "Validation failed".to_string(),
+        vec![ResponseErrorDetail::new("size", "max 100")]
+    );
+    let json = serde_json::to_string(&error.data).unwrap();
+    assert_eq!(json, r#"{"code":1001,"message":"Validation failed","details":[{"field":"size","message":"max 100"}]}"#);
+}
+
+#[test]
+fn check_page_wire_format() {
+    let page = response_page(vec![1, 2], Some("abc".to_string()));
+    let json = serde_json::to_string(&page.data).unwrap();
+    assert_eq!(json, r#"{"data":[1,2],"nextCursor":"abc"}"#);
+
+    let page = response_page(vec![3], None);
+    let json = serde_json::to_string(&page.data).unwrap();
+    assert_eq!(json, r#"{"data":[3]}"#);
+}